@@ -0,0 +1,297 @@
+//! Algebras beyond GA3: projective (PGA) and conformal (CGA) 3D geometry
+//!
+//! GA3 can rotate but not translate: a translation is vector addition,
+//! which only makes sense for positions. The larger algebras add basis
+//! vectors so that translations become versors too, applied with the same
+//! sandwich product as rotors:
+//!
+//! - `PGA3 = Cl(3,0,1)`: e1, e2, e3 and a null vector e0. Planes are
+//!   vectors, lines are bivectors and points are trivectors, so
+//!   intersections are outer products (see [`pga`]).
+//! - `CGA3 = Cl(4,1,0)`: e1, e2, e3, e+ and e-. Points are null vectors
+//!   `x + ½x²n∞ + n₀`.
+//!
+//! `GeometricState`, `Rotor`, `Versor`, `Transform` and `Projection` are
+//! generic over the signature and default to GA3. The traits here describe
+//! what an algebra needs to provide for positions and translations.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::{GeometricState, Rotor, Translation, Versor};
+//! use std::f64::consts::PI;
+//!
+//! let point = GeometricState::<3, 0, 1>::from_point(1.0, 0.0, 0.0);
+//!
+//! // A motor: rotate 90° about Z, then translate along X
+//! let motor: Versor<3, 0, 1> =
+//!     Versor::motor(&Rotor::xy(PI / 2.0).embed(), &Translation::new(1.0, 0.0, 0.0));
+//! let (x, y, z) = point.apply_versor(&motor).as_point();
+//!
+//! assert!((x - 1.0).abs() < 1e-10);
+//! assert!((y - 1.0).abs() < 1e-10);
+//! assert!(z.abs() < 1e-10);
+//! ```
+
+use crate::geometric::{CGA3, GA3, PGA3};
+
+/// Algebras that embed Euclidean 3D positions
+///
+/// Implemented for `GA3`, `PGA3` and `CGA3`.
+pub trait EuclideanEmbedding: Sized {
+    /// The multivector representing the point (x, y, z)
+    fn point(x: f64, y: f64, z: f64) -> Self;
+
+    /// Read back the Euclidean coordinates of a point
+    ///
+    /// Points at infinity (PGA directions) have no finite coordinates and
+    /// yield non-finite values.
+    fn to_point(&self) -> (f64, f64, f64);
+
+    /// Translate by (x, y, z)
+    fn translate(&self, x: f64, y: f64, z: f64) -> Self;
+}
+
+/// Algebras in which a translation is a versor
+///
+/// Implemented for `PGA3` and `CGA3`.
+pub trait TranslatorAlgebra: EuclideanEmbedding {
+    /// The translator versor for (x, y, z)
+    ///
+    /// Applying it with the sandwich product `T X T̃` translates `X`.
+    fn translator(x: f64, y: f64, z: f64) -> Self;
+}
+
+// ============================================================================
+// GA3 - positions are vectors, translation is addition
+// ============================================================================
+
+impl EuclideanEmbedding for GA3 {
+    fn point(x: f64, y: f64, z: f64) -> Self {
+        GA3::from_coefficients(vec![0.0, x, y, 0.0, z, 0.0, 0.0, 0.0])
+    }
+
+    fn to_point(&self) -> (f64, f64, f64) {
+        (self.get(1), self.get(2), self.get(4))
+    }
+
+    fn translate(&self, x: f64, y: f64, z: f64) -> Self {
+        self + &GA3::point(x, y, z)
+    }
+}
+
+// ============================================================================
+// PGA3 - Cl(3,0,1), e0 is the fourth basis vector (index 8)
+// ============================================================================
+
+/// Blade helpers for PGA3, built from basis vector products so the signs
+/// match amari's blade ordering
+fn pga_blade(vectors: &[usize]) -> PGA3 {
+    vectors.iter().fold(PGA3::scalar(1.0), |acc, &i| {
+        acc.geometric_product(&PGA3::basis_vector(i))
+    })
+}
+
+/// Coefficient of the blade `vectors` in `mv`
+fn pga_coefficient(mv: &PGA3, vectors: &[usize]) -> f64 {
+    let blade = pga_blade(vectors);
+    let index = vectors.iter().map(|&i| 1 << i).sum();
+    mv.get(index) * blade.get(index)
+}
+
+/// Index of e0 among the PGA3 basis vectors
+const PGA_E0: usize = 3;
+
+impl EuclideanEmbedding for PGA3 {
+    /// `e123 - x e023 + y e013 - z e012`, the meet of the planes x, y and z
+    fn point(x: f64, y: f64, z: f64) -> Self {
+        let origin = pga_blade(&[0, 1, 2]);
+        let e023 = pga_blade(&[PGA_E0, 1, 2]);
+        let e013 = pga_blade(&[PGA_E0, 0, 2]);
+        let e012 = pga_blade(&[PGA_E0, 0, 1]);
+        &(&(&origin - &(&e023 * x)) + &(&e013 * y)) - &(&e012 * z)
+    }
+
+    fn to_point(&self) -> (f64, f64, f64) {
+        let w = pga_coefficient(self, &[0, 1, 2]);
+        (
+            -pga_coefficient(self, &[PGA_E0, 1, 2]) / w,
+            pga_coefficient(self, &[PGA_E0, 0, 2]) / w,
+            -pga_coefficient(self, &[PGA_E0, 0, 1]) / w,
+        )
+    }
+
+    fn translate(&self, x: f64, y: f64, z: f64) -> Self {
+        let translator = PGA3::translator(x, y, z);
+        translator
+            .geometric_product(self)
+            .geometric_product(&translator.reverse())
+    }
+}
+
+impl TranslatorAlgebra for PGA3 {
+    /// `1 - ½ e0 t`
+    fn translator(x: f64, y: f64, z: f64) -> Self {
+        let e0t = &(&(&pga_blade(&[PGA_E0, 0]) * x) + &(&pga_blade(&[PGA_E0, 1]) * y))
+            + &(&pga_blade(&[PGA_E0, 2]) * z);
+        &PGA3::scalar(1.0) - &(&e0t * 0.5)
+    }
+}
+
+// ============================================================================
+// CGA3 - Cl(4,1,0), e+ is index 8 and e- is index 16
+// ============================================================================
+
+/// The point at infinity n∞ = e- + e+
+fn cga_infinity() -> CGA3 {
+    &CGA3::basis_vector(4) + &CGA3::basis_vector(3)
+}
+
+/// The origin n₀ = ½(e- - e+)
+fn cga_origin() -> CGA3 {
+    &(&CGA3::basis_vector(4) - &CGA3::basis_vector(3)) * 0.5
+}
+
+fn cga_vector(x: f64, y: f64, z: f64) -> CGA3 {
+    let mut mv = CGA3::zero();
+    mv.set(1, x);
+    mv.set(2, y);
+    mv.set(4, z);
+    mv
+}
+
+impl EuclideanEmbedding for CGA3 {
+    /// `x + ½x²n∞ + n₀`
+    fn point(x: f64, y: f64, z: f64) -> Self {
+        let half_square = 0.5 * (x * x + y * y + z * z);
+        &(&cga_vector(x, y, z) + &(&cga_infinity() * half_square)) + &cga_origin()
+    }
+
+    fn to_point(&self) -> (f64, f64, f64) {
+        // A point X has weight -X·n∞ (1 for normalized points)
+        let w = -self.scalar_product(&cga_infinity());
+        (self.get(1) / w, self.get(2) / w, self.get(4) / w)
+    }
+
+    fn translate(&self, x: f64, y: f64, z: f64) -> Self {
+        let translator = CGA3::translator(x, y, z);
+        translator
+            .geometric_product(self)
+            .geometric_product(&translator.reverse())
+    }
+}
+
+impl TranslatorAlgebra for CGA3 {
+    /// `1 - ½ t n∞`
+    fn translator(x: f64, y: f64, z: f64) -> Self {
+        let tn = cga_vector(x, y, z).geometric_product(&cga_infinity());
+        &CGA3::scalar(1.0) - &(&tn * 0.5)
+    }
+}
+
+/// Incidence constructions in projective geometric algebra
+///
+/// Planes are vectors; the outer product (meet) of two planes is their
+/// line of intersection, and the meet of a line and a plane is their
+/// point of intersection, read back with `EuclideanEmbedding::to_point`.
+///
+/// ```rust
+/// use cliffy_core::algebra::{pga, EuclideanEmbedding};
+///
+/// let floor = pga::plane(0.0, 0.0, 1.0, 0.0); // z = 0
+/// let wall = pga::plane(1.0, 0.0, 0.0, -2.0); // x = 2
+/// let side = pga::plane(0.0, 1.0, 0.0, -3.0); // y = 3
+///
+/// let corner = pga::meet(&pga::meet(&floor, &wall), &side);
+/// let (x, y, z) = corner.to_point();
+/// assert!((x - 2.0).abs() < 1e-10 && (y - 3.0).abs() < 1e-10 && z.abs() < 1e-10);
+/// ```
+pub mod pga {
+    use super::{pga_blade, PGA_E0};
+    use crate::geometric::PGA3;
+
+    /// The plane `a x + b y + c z + d = 0`
+    pub fn plane(a: f64, b: f64, c: f64, d: f64) -> PGA3 {
+        let mut mv = PGA3::zero();
+        mv.set(1, a);
+        mv.set(2, b);
+        mv.set(4, c);
+        &mv + &(&pga_blade(&[PGA_E0]) * d)
+    }
+
+    /// The pseudoscalar e0123
+    ///
+    /// It squares to zero and commutes with even elements, so multiples of
+    /// it behave like the dual part of a dual number (as in dual quaternions).
+    pub fn pseudoscalar() -> PGA3 {
+        pga_blade(&[PGA_E0, 0, 1, 2])
+    }
+
+    /// Intersection (outer product) of two elements
+    ///
+    /// Plane ∧ plane is a line, line ∧ plane is a point. The result is
+    /// zero when the elements are parallel.
+    pub fn meet(a: &PGA3, b: &PGA3) -> PGA3 {
+        a.outer_product(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_point(actual: (f64, f64, f64), expected: (f64, f64, f64)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-10
+                && (actual.1 - expected.1).abs() < 1e-10
+                && (actual.2 - expected.2).abs() < 1e-10,
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_point_roundtrip() {
+        assert_point(GA3::point(1.0, -2.0, 3.0).to_point(), (1.0, -2.0, 3.0));
+        assert_point(PGA3::point(1.0, -2.0, 3.0).to_point(), (1.0, -2.0, 3.0));
+        assert_point(CGA3::point(1.0, -2.0, 3.0).to_point(), (1.0, -2.0, 3.0));
+    }
+
+    #[test]
+    fn test_pga_translator() {
+        let moved = PGA3::point(1.0, 2.0, 3.0).translate(0.5, -1.0, 2.0);
+        assert_point(moved.to_point(), (1.5, 1.0, 5.0));
+    }
+
+    #[test]
+    fn test_cga_translator() {
+        let moved = CGA3::point(1.0, 2.0, 3.0).translate(0.5, -1.0, 2.0);
+        assert_point(moved.to_point(), (1.5, 1.0, 5.0));
+
+        // Translated points stay on the null cone
+        assert!(moved.scalar_product(&moved).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_pga_plane_meet_matches_point() {
+        let x = pga::plane(1.0, 0.0, 0.0, -1.0);
+        let y = pga::plane(0.0, 1.0, 0.0, -2.0);
+        let z = pga::plane(0.0, 0.0, 1.0, -3.0);
+
+        let point = pga::meet(&pga::meet(&x, &y), &z);
+        assert_point(point.to_point(), (1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_pga_line_translates_with_points() {
+        // The line x = 1, y = 2 moved by (1, 1, 0) meets z = 0 at (2, 3, 0)
+        let line = pga::meet(
+            &pga::plane(1.0, 0.0, 0.0, -1.0),
+            &pga::plane(0.0, 1.0, 0.0, -2.0),
+        );
+        let moved = line.translate(1.0, 1.0, 0.0);
+        let point = pga::meet(&moved, &pga::plane(0.0, 0.0, 1.0, 0.0));
+        assert_point(point.to_point(), (2.0, 3.0, 0.0));
+    }
+}
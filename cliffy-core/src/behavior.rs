@@ -6,28 +6,81 @@
 //!
 //! This is inspired by Conal Elliott's classical FRP semantics where
 //! `Behavior a = Time -> a` - a function from time to a value.
+//!
+//! # Ownership
+//!
+//! A derived behavior (`map`, `combine`, ...) owns its subscriptions on its
+//! parents, and parents only hold it weakly. Dropping the last handle to a
+//! derived behavior removes its registrations from every parent. A `Scope`
+//! can also tear down everything created inside it at once.
 
-use crate::geometric::{FromGeometric, IntoGeometric, GA3};
-use std::cell::RefCell;
+use crate::dataflow::record;
+use crate::event::Event;
+use crate::geometric::{
+    FromGeometric, FromGeometricBlob, GeometricBlob, IntoGeometric, IntoGeometricBlob, GA3,
+};
+use crate::propagation::{self, DerivedNode, Rank, WeakDerivedNode};
+use crate::scope;
+use std::cell::{Cell, OnceCell, RefCell};
 use std::rc::Rc;
 
 /// A subscription handle that can be used to unsubscribe
+///
+/// Dropping the handle unsubscribes. Use `Scope::adopt` to keep a
+/// subscription alive for the lifetime of a scope instead.
 #[allow(clippy::type_complexity)]
+#[must_use = "dropping a Subscription unsubscribes immediately"]
 pub struct Subscription {
-    #[allow(dead_code)] // Reserved for debugging
     id: usize,
     unsubscribe: Rc<RefCell<Option<Box<dyn Fn()>>>>,
 }
 
 impl Subscription {
+    /// Create a handle that runs `unsubscribe` once, when dropped or disposed
+    pub(crate) fn new<F>(id: usize, unsubscribe: F) -> Self
+    where
+        F: Fn() + 'static,
+    {
+        Self {
+            id,
+            unsubscribe: Rc::new(RefCell::new(Some(Box::new(unsubscribe)))),
+        }
+    }
+
     /// Unsubscribe from updates
     pub fn unsubscribe(self) {
-        if let Some(unsub) = self.unsubscribe.borrow_mut().take() {
+        // Dropping performs the unsubscribe
+    }
+
+    /// Check whether this subscription is still registered
+    pub fn is_active(&self) -> bool {
+        self.unsubscribe.borrow().is_some()
+    }
+
+    /// Also unsubscribe when the current scope is disposed
+    pub(crate) fn scoped(self) -> Self {
+        scope::on_dispose(&self.unsubscribe, |unsubscribe| {
+            let unsub = unsubscribe.borrow_mut().take();
+            if let Some(unsub) = unsub {
+                unsub();
+            }
+        });
+        self
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let unsub = self.unsubscribe.borrow_mut().take();
+        if let Some(unsub) = unsub {
             unsub();
         }
     }
 }
 
+/// Type alias for subscriber callbacks to avoid clippy::type_complexity warning
+type SubscriberList<T> = Rc<RefCell<Vec<(usize, Box<dyn Fn(&T)>)>>>;
+
 /// A time-varying value backed by geometric algebra
 ///
 /// `Behavior<T>` wraps any value type `T` and stores it internally as a
@@ -35,6 +88,8 @@ impl Subscription {
 ///
 /// - Reactive updates with subscriber notification
 /// - Derived behaviors via `map` and `combine`
+/// - Glitch-free propagation: derived behaviors recompute once per update,
+///   in topological (rank) order
 /// - Geometric transformations under the hood
 ///
 /// # Example
@@ -48,28 +103,73 @@ impl Subscription {
 /// count.update(|n| n + 1);
 /// assert_eq!(count.sample(), 1);
 /// ```
-#[allow(clippy::type_complexity)]
 pub struct Behavior<T> {
+    inner: Rc<BehaviorInner<T>>,
+}
+
+/// Shared state behind every clone of a `Behavior`
+struct BehaviorInner<T> {
     /// Internal geometric state
-    state: Rc<RefCell<GA3>>,
+    state: RefCell<GA3>,
 
     /// Cached value (for types that can't be reconstructed from GA3)
-    cache: Rc<RefCell<T>>,
+    cache: RefCell<T>,
 
     /// Subscribers to notify on update
-    subscribers: Rc<RefCell<Vec<(usize, Box<dyn Fn(&T)>)>>>,
+    subscribers: SubscriberList<T>,
 
     /// Next subscriber ID
-    next_id: Rc<RefCell<usize>>,
+    next_id: Cell<usize>,
+
+    /// Propagation rank (0 for sources, parent rank + 1 for derived)
+    rank: Rc<Rank>,
+
+    /// How a derived behavior is recomputed; `None` for sources and
+    /// for derived behaviors whose scope has been disposed
+    derivation: RefCell<Option<Derivation>>,
+
+    /// Optional suppression of updates that don't change the value
+    change_filter: Option<ChangeFilter<T>>,
+}
+
+/// How a behavior decides whether an update is a real change
+///
+/// Updates that are not changes are dropped: the value is not stored and
+/// nothing is notified, so downstream behaviors never recompute.
+enum ChangeFilter<T> {
+    /// Compare values with `PartialEq`
+    Equal(fn(&T, &T) -> bool),
+    /// Compare GA3 states; updates within this multivector distance are ignored
+    Epsilon(f64),
+}
+
+/// The recomputation of a derived behavior and its registrations on its parents
+///
+/// Dropping a `Derivation` unsubscribes from every parent.
+struct Derivation {
+    _node: DerivedNode,
+    _subscriptions: Vec<Subscription>,
+}
+
+/// A behavior that derived behaviors can depend on, independent of its value type
+pub(crate) trait Dependency {
+    /// Propagation rank of the dependency
+    fn rank(&self) -> usize;
+
+    /// Schedule `node` for recomputation whenever the dependency changes
+    fn schedule_on_change(&self, node: &DerivedNode) -> Subscription;
+
+    /// Identity shared by all clones of the dependency
+    fn identity(&self) -> usize;
+
+    /// Name of the dependency's value type
+    fn type_name(&self) -> &'static str;
 }
 
 impl<T> Clone for Behavior<T> {
     fn clone(&self) -> Self {
         Self {
-            state: Rc::clone(&self.state),
-            cache: Rc::clone(&self.cache),
-            subscribers: Rc::clone(&self.subscribers),
-            next_id: Rc::clone(&self.next_id),
+            inner: Rc::clone(&self.inner),
         }
     }
 }
@@ -77,18 +177,182 @@ impl<T> Clone for Behavior<T> {
 impl<T: IntoGeometric + FromGeometric + Clone + 'static> Behavior<T> {
     /// Create a new behavior with an initial value
     pub fn new(initial: T) -> Self {
+        let behavior = Self::with_rank(initial, 0, None);
+        record::source::<T>(behavior.identity());
+        behavior
+    }
+
+    fn with_rank(initial: T, rank: usize, change_filter: Option<ChangeFilter<T>>) -> Self {
         let mv = initial.clone().into_geometric();
         Self {
-            state: Rc::new(RefCell::new(mv)),
-            cache: Rc::new(RefCell::new(initial)),
-            subscribers: Rc::new(RefCell::new(Vec::new())),
-            next_id: Rc::new(RefCell::new(0)),
+            inner: Rc::new(BehaviorInner {
+                state: RefCell::new(mv),
+                cache: RefCell::new(initial),
+                subscribers: Rc::new(RefCell::new(Vec::new())),
+                next_id: Cell::new(0),
+                rank: Rank::new(rank),
+                derivation: RefCell::new(None),
+                change_filter,
+            }),
         }
     }
 
+    /// Create a derived behavior that recomputes `compute` whenever one of
+    /// `parents` changes
+    ///
+    /// `operation` names the derivation in recorded graphs (see
+    /// `GraphRecorder`). `compute` should capture the parents it samples; the derived behavior
+    /// keeps them alive, while the parents only reference it weakly. When
+    /// created inside a `Scope`, disposing the scope detaches it.
+    pub(crate) fn derive<F>(operation: &str, parents: &[&dyn Dependency], compute: F) -> Self
+    where
+        F: Fn() -> T + 'static,
+    {
+        Self::derive_filtered(operation, parents, None, compute)
+    }
+
+    fn derive_filtered<F>(
+        operation: &str,
+        parents: &[&dyn Dependency],
+        change_filter: Option<ChangeFilter<T>>,
+        compute: F,
+    ) -> Self
+    where
+        F: Fn() -> T + 'static,
+    {
+        let rank = parents.iter().map(|p| p.rank()).max().map_or(0, |r| r + 1);
+        let derived = Self::with_rank(compute(), rank, change_filter);
+        record::derived::<T>(operation, derived.identity(), parents);
+
+        let target = Rc::downgrade(&derived.inner);
+        let node = DerivedNode::new(Rc::clone(&derived.inner.rank), move || {
+            if let Some(inner) = target.upgrade() {
+                Behavior { inner }.set(compute());
+            }
+        });
+        let subscriptions = parents
+            .iter()
+            .map(|parent| parent.schedule_on_change(&node))
+            .collect();
+
+        derived.attach(node, subscriptions);
+        derived
+    }
+
+    /// Install the derivation of a derived behavior
+    ///
+    /// When created inside a `Scope`, disposing the scope drops it again.
+    fn attach(&self, node: DerivedNode, subscriptions: Vec<Subscription>) {
+        *self.inner.derivation.borrow_mut() = Some(Derivation {
+            _node: node,
+            _subscriptions: subscriptions,
+        });
+
+        scope::on_dispose(&self.inner, |inner| {
+            let derivation = inner.derivation.borrow_mut().take();
+            drop(derivation);
+        });
+    }
+
+    /// Follow whichever behavior `outer` currently holds
+    ///
+    /// The result always has the value of the current inner behavior. When
+    /// `outer` changes, the subscription on the previous inner behavior is
+    /// dropped and the new one is followed instead. Following an inner
+    /// behavior ranked at or above the result raises the result's rank (and
+    /// its dependents'), so propagation stays glitch-free.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cliffy_core::{behavior, Behavior};
+    ///
+    /// let draft = behavior("draft".to_string());
+    /// let published = behavior("published".to_string());
+    /// let selected = behavior(draft.clone());
+    /// let title = Behavior::switch(&selected);
+    ///
+    /// selected.set(published.clone());
+    /// assert_eq!(title.sample(), "published");
+    ///
+    /// draft.set("ignored".to_string()); // no longer followed
+    /// published.set("updated".to_string());
+    /// assert_eq!(title.sample(), "updated");
+    /// ```
+    pub fn switch(outer: &Behavior<Behavior<T>>) -> Behavior<T> {
+        let first = outer.sample();
+        let rank = outer.rank().max(first.rank()) + 1;
+        let switched = Self::with_rank(first.sample(), rank, None);
+        record::derived::<T>("switch", switched.identity(), &[outer]);
+
+        // The node recomputes when `outer` or the followed inner behavior
+        // changes; it needs its own handle to follow a new inner behavior
+        let this: Rc<OnceCell<WeakDerivedNode>> = Rc::new(OnceCell::new());
+        let following: Rc<RefCell<Option<(usize, Subscription)>>> = Rc::default();
+
+        let source = outer.clone();
+        let target = Rc::downgrade(&switched.inner);
+        let (this_clone, following_clone) = (Rc::clone(&this), Rc::clone(&following));
+        let node = DerivedNode::new(Rc::clone(&switched.inner.rank), move || {
+            let Some(inner) = target.upgrade() else {
+                return;
+            };
+            let current = source.sample();
+            let followed = following_clone.borrow().as_ref().map(|(id, _)| *id);
+            if followed != Some(current.identity()) {
+                inner.rank.raise(current.rank() + 1);
+                let node = this_clone.get().unwrap().clone();
+                // Replacing the subscription drops (and unsubscribes) the old one
+                *following_clone.borrow_mut() =
+                    Some((current.identity(), current.follow(node, &inner.rank)));
+            }
+            Behavior { inner }.set(current.sample());
+        });
+        let _ = this.set(node.downgrade());
+        *following.borrow_mut() = Some((
+            first.identity(),
+            first.follow(node.downgrade(), node.rank()),
+        ));
+        let subscriptions = vec![outer.schedule_on_change(&node)];
+
+        switched.attach(node, subscriptions);
+        switched
+    }
+
+    /// Map each value to a behavior and follow the most recent one
+    ///
+    /// Equivalent to `Behavior::switch(&self.map(f))`.
+    pub fn flat_map<U, F>(&self, f: F) -> Behavior<U>
+    where
+        U: IntoGeometric + FromGeometric + Clone + 'static,
+        F: Fn(T) -> Behavior<U> + 'static,
+    {
+        Behavior::switch(&self.map(f))
+    }
+
+    /// Get the propagation rank of this behavior
+    ///
+    /// Source behaviors have rank 0; a derived behavior is ranked one above
+    /// its highest-ranked parent. Dependents are recomputed in rank order.
+    /// A `switch` (and everything downstream of it) is re-ranked when it
+    /// follows a higher-ranked inner behavior.
+    pub fn rank(&self) -> usize {
+        self.inner.rank.get()
+    }
+
+    /// Check whether this behavior is derived and still attached to its parents
+    pub fn is_derived(&self) -> bool {
+        self.inner.derivation.borrow().is_some()
+    }
+
+    /// Get the number of active subscribers (including derived behaviors)
+    pub fn subscriber_count(&self) -> usize {
+        self.inner.subscribers.borrow().len()
+    }
+
     /// Sample the current value
     pub fn sample(&self) -> T {
-        self.cache.borrow().clone()
+        self.inner.cache.borrow().clone()
     }
 
     /// Update the value using a transformation function
@@ -99,18 +363,18 @@ impl<T: IntoGeometric + FromGeometric + Clone + 'static> Behavior<T> {
     where
         F: FnOnce(T) -> T,
     {
-        let current = self.cache.borrow().clone();
+        let current = self.sample();
         let new_value = f(current);
         let new_mv = new_value.clone().into_geometric();
 
-        *self.state.borrow_mut() = new_mv;
-        *self.cache.borrow_mut() = new_value;
-
-        // Notify subscribers
-        let cache = self.cache.borrow();
-        for (_, callback) in self.subscribers.borrow().iter() {
-            callback(&cache);
+        if !self.is_change(&new_value, &new_mv) {
+            return;
         }
+
+        *self.inner.state.borrow_mut() = new_mv;
+        *self.inner.cache.borrow_mut() = new_value;
+
+        self.notify_subscribers();
     }
 
     /// Set the value directly
@@ -120,26 +384,47 @@ impl<T: IntoGeometric + FromGeometric + Clone + 'static> Behavior<T> {
 
     /// Subscribe to value changes
     ///
-    /// Returns a `Subscription` that can be used to unsubscribe.
+    /// Returns a `Subscription` that unsubscribes when dropped. When called
+    /// inside a `Scope`, the subscription is also removed when the scope is
+    /// disposed.
     pub fn subscribe<F>(&self, callback: F) -> Subscription
     where
         F: Fn(&T) + 'static,
     {
-        let id = {
-            let mut next = self.next_id.borrow_mut();
-            let id = *next;
-            *next += 1;
-            id
-        };
+        let subscription = self.subscribe_unscoped(callback).scoped();
+        record::subscriber::<T>(self.identity());
+        subscription
+    }
 
-        self.subscribers.borrow_mut().push((id, Box::new(callback)));
+    fn subscribe_unscoped<F>(&self, callback: F) -> Subscription
+    where
+        F: Fn(&T) + 'static,
+    {
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id + 1);
 
-        let subscribers = Rc::clone(&self.subscribers);
-        let unsubscribe = Rc::new(RefCell::new(Some(Box::new(move || {
-            subscribers.borrow_mut().retain(|(i, _)| *i != id);
-        }) as Box<dyn Fn()>)));
+        self.inner
+            .subscribers
+            .borrow_mut()
+            .push((id, Box::new(callback)));
+
+        let subscribers = Rc::downgrade(&self.inner.subscribers);
+        let rank = Rc::downgrade(&self.inner.rank);
+        Subscription::new(id, move || {
+            if let Some(subscribers) = subscribers.upgrade() {
+                subscribers.borrow_mut().retain(|(i, _)| *i != id);
+            }
+            if let Some(rank) = rank.upgrade() {
+                rank.remove_dependent(id);
+            }
+        })
+    }
 
-        Subscription { id, unsubscribe }
+    /// Schedule `node`, ranked by `rank`, whenever this behavior changes
+    fn follow(&self, node: WeakDerivedNode, rank: &Rc<Rank>) -> Subscription {
+        let subscription = self.subscribe_unscoped(move |_| node.schedule());
+        self.inner.rank.add_dependent(subscription.id, rank);
+        subscription
     }
 
     /// Create a derived behavior by mapping a function over this behavior
@@ -150,52 +435,125 @@ impl<T: IntoGeometric + FromGeometric + Clone + 'static> Behavior<T> {
         U: IntoGeometric + FromGeometric + Clone + 'static,
         F: Fn(T) -> U + 'static,
     {
-        let initial = f(self.sample());
-        let derived = Behavior::new(initial);
-
-        // Subscribe to changes and update derived
-        let derived_clone = derived.clone();
-        self.subscribe(move |value| {
-            let new_value = f(value.clone());
-            derived_clone.set(new_value);
-        });
-
-        derived
+        let source = self.clone();
+        Behavior::derive("map", &[self], move || f(source.sample()))
     }
 
     /// Combine two behaviors into a new behavior
+    ///
+    /// The combined behavior is recomputed once per update even when both
+    /// inputs change as a result of the same source update.
     pub fn combine<U, V, F>(&self, other: &Behavior<U>, f: F) -> Behavior<V>
     where
         U: IntoGeometric + FromGeometric + Clone + 'static,
         V: IntoGeometric + FromGeometric + Clone + 'static,
         F: Fn(T, U) -> V + Clone + 'static,
     {
-        let initial = f(self.sample(), other.sample());
-        let combined = Behavior::new(initial);
-
-        // Subscribe to self
-        let combined_clone = combined.clone();
-        let other_clone = other.clone();
-        let f_clone = f.clone();
-        self.subscribe(move |a| {
-            let b = other_clone.sample();
-            combined_clone.set(f_clone(a.clone(), b));
-        });
+        let (a, b) = (self.clone(), other.clone());
+        Behavior::derive("combine", &[self, other], move || f(a.sample(), b.sample()))
+    }
 
-        // Subscribe to other
-        let combined_clone = combined.clone();
-        let self_clone = self.clone();
-        other.subscribe(move |b| {
-            let a = self_clone.sample();
-            combined_clone.set(f(a, b.clone()));
-        });
+    /// Create a derived behavior that only propagates actual changes
+    ///
+    /// Updates that produce a value equal (`PartialEq`) to the current one
+    /// are suppressed: subscribers are not notified and behaviors derived
+    /// from the result are not recomputed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cliffy_core::behavior;
+    ///
+    /// let count = behavior(0i32);
+    /// let parity = count.map(|n| n % 2).distinct();
+    ///
+    /// let label = parity.map(|p| if p == 0 { "even".to_string() } else { "odd".to_string() });
+    /// count.set(2); // parity stays 0, `label` is not recomputed
+    /// assert_eq!(label.sample(), "even");
+    /// ```
+    pub fn distinct(&self) -> Behavior<T>
+    where
+        T: PartialEq,
+    {
+        let source = self.clone();
+        Behavior::derive_filtered(
+            "distinct",
+            &[self],
+            Some(ChangeFilter::Equal(T::eq)),
+            move || source.sample(),
+        )
+    }
 
-        combined
+    /// Create a derived behavior that ignores changes smaller than `epsilon`
+    ///
+    /// Changes are measured as the magnitude of the difference between the
+    /// old and new GA3 states, so this works for geometric values (positions,
+    /// rotors, colors) where floating-point jitter shouldn't trigger updates.
+    /// Suppressed updates leave the previous value in place.
+    pub fn distinct_within(&self, epsilon: f64) -> Behavior<T> {
+        let source = self.clone();
+        Behavior::derive_filtered(
+            "distinct_within",
+            &[self],
+            Some(ChangeFilter::Epsilon(epsilon)),
+            move || source.sample(),
+        )
+    }
+
+    /// Map a function over this behavior, propagating only when the result changes
+    ///
+    /// Equivalent to `self.map(f).distinct()` without the intermediate behavior.
+    pub fn memo<U, F>(&self, f: F) -> Behavior<U>
+    where
+        U: IntoGeometric + FromGeometric + Clone + PartialEq + 'static,
+        F: Fn(T) -> U + 'static,
+    {
+        let source = self.clone();
+        Behavior::derive_filtered(
+            "memo",
+            &[self],
+            Some(ChangeFilter::Equal(U::eq)),
+            move || f(source.sample()),
+        )
+    }
+
+    /// Sample this behavior whenever `trigger` fires
+    ///
+    /// The resulting event carries the behavior's value at the moment of
+    /// each occurrence.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cliffy_core::{behavior, event};
+    ///
+    /// let position = behavior(0i32);
+    /// let save = event::<()>();
+    /// let total = position.sample_on(&save).fold(0, |sum, p| sum + p);
+    ///
+    /// position.set(3);
+    /// save.emit(());
+    /// position.set(7);
+    /// save.emit(());
+    /// assert_eq!(total.sample(), 10);
+    /// ```
+    pub fn sample_on<U: Clone + 'static>(&self, trigger: &Event<U>) -> Event<T> {
+        let sampled = trigger.on_same_clock::<T>();
+
+        let sampled_clone = sampled.clone();
+        let source = self.clone();
+        trigger.subscribe(move |_| sampled_clone.emit(source.sample()));
+
+        sampled
     }
 
     /// Get the internal geometric state (for advanced users)
+    ///
+    /// This is a single multivector, so it only fully describes scalar-like
+    /// values; a `String`, for example, is reduced to a hash. Use
+    /// `geometric_blob` when the value must be recovered exactly.
     pub fn geometric_state(&self) -> GA3 {
-        self.state.borrow().clone()
+        self.inner.state.borrow().clone()
     }
 
     /// Apply a geometric transformation directly (for advanced users)
@@ -203,16 +561,151 @@ impl<T: IntoGeometric + FromGeometric + Clone + 'static> Behavior<T> {
     where
         F: FnOnce(&GA3) -> GA3,
     {
-        let current = self.state.borrow().clone();
+        let current = self.geometric_state();
         let new_mv = transform(&current);
-        *self.state.borrow_mut() = new_mv.clone();
-        *self.cache.borrow_mut() = T::from_geometric(&new_mv);
+        let new_value = T::from_geometric(&new_mv);
 
-        // Notify subscribers
-        let cache = self.cache.borrow();
-        for (_, callback) in self.subscribers.borrow().iter() {
-            callback(&cache);
+        if !self.is_change(&new_value, &new_mv) {
+            return;
         }
+
+        *self.inner.state.borrow_mut() = new_mv;
+        *self.inner.cache.borrow_mut() = new_value;
+
+        self.notify_subscribers();
+    }
+
+    /// Check whether an update to `value` (encoded as `mv`) is a change
+    fn is_change(&self, value: &T, mv: &GA3) -> bool {
+        match &self.inner.change_filter {
+            None => true,
+            Some(ChangeFilter::Equal(eq)) => !eq(&self.inner.cache.borrow(), value),
+            Some(ChangeFilter::Epsilon(epsilon)) => {
+                (&*self.inner.state.borrow() - mv).magnitude() > *epsilon
+            }
+        }
+    }
+
+    /// Notify all subscribers of the current value
+    ///
+    /// Inside a `transaction` the notification is deferred until the
+    /// transaction ends. Dependents queued by the subscribers are recomputed
+    /// once every subscriber has run.
+    fn notify_subscribers(&self) {
+        if propagation::in_transaction() {
+            let this = self.clone();
+            propagation::defer(self.identity(), move || this.notify_now());
+        } else {
+            self.notify_now();
+        }
+    }
+
+    fn notify_now(&self) {
+        let value = self.sample();
+        propagation::hold(|| {
+            for (_, callback) in self.inner.subscribers.borrow().iter() {
+                callback(&value);
+            }
+        });
+    }
+
+    /// A setter that doesn't keep this behavior alive
+    ///
+    /// It returns `false`, doing nothing, once the behavior has been dropped.
+    pub(crate) fn weak_setter(&self) -> impl Fn(T) -> bool + 'static {
+        let weak = Rc::downgrade(&self.inner);
+        move |value| match weak.upgrade() {
+            Some(inner) => {
+                Behavior { inner }.set(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Identity shared by all clones of this behavior
+    ///
+    /// Unlike the address of the shared state, an identity is never
+    /// reused once the behavior is dropped.
+    pub(crate) fn identity(&self) -> usize {
+        self.inner.rank.id()
+    }
+}
+
+impl<T> Behavior<T>
+where
+    T: IntoGeometric + FromGeometric + IntoGeometricBlob + FromGeometricBlob + Clone + 'static,
+{
+    /// Get a lossless encoding of the current value
+    ///
+    /// Unlike `geometric_state`, which is a single multivector, the blob
+    /// can be decoded back to the exact value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cliffy_core::behavior;
+    ///
+    /// let name = behavior("Alice".to_string());
+    /// let copy = behavior(String::new());
+    ///
+    /// assert!(copy.set_geometric_blob(&name.geometric_blob()));
+    /// assert_eq!(copy.sample(), "Alice");
+    /// ```
+    pub fn geometric_blob(&self) -> GeometricBlob {
+        self.sample().into_geometric_blob()
+    }
+
+    /// Set the value from a lossless encoding
+    ///
+    /// Returns `false`, leaving the behavior untouched, if the blob doesn't
+    /// decode to a `T`.
+    pub fn set_geometric_blob(&self, blob: &GeometricBlob) -> bool {
+        match T::from_geometric_blob(blob) {
+            Some(value) => {
+                self.set(value);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<T: IntoGeometric + FromGeometric + Clone + 'static> Dependency for Behavior<T> {
+    fn rank(&self) -> usize {
+        self.inner.rank.get()
+    }
+
+    fn schedule_on_change(&self, node: &DerivedNode) -> Subscription {
+        self.follow(node.downgrade(), node.rank())
+    }
+
+    fn identity(&self) -> usize {
+        Behavior::identity(self)
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+}
+
+/// The geometric form of a behavior is its current state, so behaviors can
+/// themselves be held in a `Behavior` (see `Behavior::switch`)
+impl<T: IntoGeometric + FromGeometric + Clone + 'static> IntoGeometric for Behavior<T> {
+    fn into_geometric(self) -> GA3 {
+        self.geometric_state()
+    }
+}
+
+/// Reconstructs a new, detached behavior holding the decoded value
+///
+/// The result is a fresh source: it is not connected to the behavior that
+/// was encoded, and only what `T::from_geometric` recovers survives. This
+/// exists so `Behavior<Behavior<T>>` meets the `Behavior` bounds; `switch`
+/// and `flat_map` never decode inner behaviors this way.
+impl<T: IntoGeometric + FromGeometric + Clone + 'static> FromGeometric for Behavior<T> {
+    fn from_geometric(mv: &GA3) -> Self {
+        Behavior::new(T::from_geometric(mv))
     }
 }
 
@@ -347,4 +840,335 @@ mod tests {
         a.update(|n| n + 1);
         assert_eq!(b.sample(), 1);
     }
+
+    #[test]
+    fn test_behavior_rank() {
+        let a = behavior(1i32);
+        let b = a.map(|n| n + 1);
+        let c = a.map(|n| n * 2);
+        let d = b.combine(&c, |x, y| x + y);
+
+        assert_eq!(a.rank(), 0);
+        assert_eq!(b.rank(), 1);
+        assert_eq!(c.rank(), 1);
+        assert_eq!(d.rank(), 2);
+    }
+
+    #[test]
+    fn test_diamond_recomputes_once() {
+        let a = behavior(1i32);
+        let b = a.map(|n| n + 1);
+        let c = a.map(|n| n * 2);
+        let d = b.combine(&c, |x, y| (x, y));
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = Rc::clone(&seen);
+        let _sub = d.subscribe(move |pair| seen_clone.borrow_mut().push(*pair));
+
+        a.set(10);
+
+        // One notification, with both sides already updated
+        assert_eq!(*seen.borrow(), vec![(11, 20)]);
+        assert_eq!(d.sample(), (11, 20));
+    }
+
+    #[test]
+    fn test_uneven_diamond_is_glitch_free() {
+        let a = behavior(1i32);
+        let b = a.map(|n| n + 1);
+        let c = b.map(|n| n * 10);
+        let d = a.combine(&c, |x, y| (x, y));
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = Rc::clone(&seen);
+        let _sub = d.subscribe(move |pair| seen_clone.borrow_mut().push(*pair));
+
+        a.set(2);
+
+        assert_eq!(d.rank(), 3);
+        assert_eq!(*seen.borrow(), vec![(2, 30)]);
+    }
+
+    #[test]
+    fn test_transaction_notifies_once() {
+        let a = behavior(0i32);
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let calls_clone = Rc::clone(&calls);
+        let _sub = a.subscribe(move |n| calls_clone.borrow_mut().push(*n));
+
+        crate::transaction(|| {
+            a.set(1);
+            a.set(2);
+            a.update(|n| n + 1);
+            // New value is visible immediately inside the transaction
+            assert_eq!(a.sample(), 3);
+            assert!(calls.borrow().is_empty());
+        });
+
+        assert_eq!(*calls.borrow(), vec![3]);
+    }
+
+    #[test]
+    fn test_transaction_across_behaviors() {
+        let x = behavior(1i32);
+        let y = behavior(2i32);
+        let sum = x.combine(&y, |a, b| a + b);
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = Rc::clone(&seen);
+        let _sub = sum.subscribe(move |n| seen_clone.borrow_mut().push(*n));
+
+        crate::transaction(|| {
+            x.set(10);
+            y.set(20);
+        });
+
+        // No half-applied state (12 or 21) is ever observed
+        assert_eq!(*seen.borrow(), vec![30]);
+    }
+
+    #[test]
+    fn test_subscription_drop_unsubscribes() {
+        let b = behavior(0i32);
+        let called = Rc::new(Cell::new(0));
+        let called_clone = Rc::clone(&called);
+
+        {
+            let _sub = b.subscribe(move |_| called_clone.set(called_clone.get() + 1));
+            b.set(1);
+            assert_eq!(b.subscriber_count(), 1);
+        }
+
+        b.set(2);
+        assert_eq!(called.get(), 1);
+        assert_eq!(b.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_dropping_derived_releases_parent() {
+        let source = behavior(0i32);
+        let derived = source.map(|n| n + 1).map(|n| n * 2);
+        assert_eq!(source.subscriber_count(), 1);
+
+        // Intermediate behaviors stay alive through the derived chain
+        source.set(1);
+        assert_eq!(derived.sample(), 4);
+
+        drop(derived);
+        assert_eq!(source.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_many_short_lived_derivations_do_not_leak() {
+        let source = behavior(0i32);
+        for i in 0..100 {
+            let derived = source.map(move |n| n + i);
+            assert_eq!(derived.sample(), i);
+        }
+        assert_eq!(source.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_distinct_suppresses_equal_values() {
+        let source = behavior(1i32);
+        let distinct = source.distinct();
+
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = Rc::clone(&calls);
+        let _sub = distinct.subscribe(move |_| calls_clone.set(calls_clone.get() + 1));
+
+        source.set(1);
+        assert_eq!(calls.get(), 0);
+
+        source.set(2);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(distinct.sample(), 2);
+    }
+
+    #[test]
+    fn test_memo_stops_downstream_recomputation() {
+        let count = behavior(0i32);
+        let recomputes = Rc::new(Cell::new(0));
+
+        let parity = count.memo(|n| n % 2 == 0);
+        let recomputes_clone = Rc::clone(&recomputes);
+        let label = parity.map(move |even| {
+            recomputes_clone.set(recomputes_clone.get() + 1);
+            if even {
+                "even".to_string()
+            } else {
+                "odd".to_string()
+            }
+        });
+        assert_eq!(recomputes.get(), 1);
+
+        count.set(2);
+        count.set(4);
+        assert_eq!(recomputes.get(), 1);
+
+        count.set(5);
+        assert_eq!(recomputes.get(), 2);
+        assert_eq!(label.sample(), "odd");
+    }
+
+    #[test]
+    fn test_distinct_within_epsilon() {
+        let position = behavior(1.0f64);
+        let stable = position.distinct_within(1e-3);
+
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = Rc::clone(&calls);
+        let _sub = stable.subscribe(move |_| calls_clone.set(calls_clone.get() + 1));
+
+        position.set(1.0001);
+        assert_eq!(calls.get(), 0);
+        assert_eq!(stable.sample(), 1.0);
+
+        position.set(1.5);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(stable.sample(), 1.5);
+    }
+
+    #[test]
+    fn test_distinct_within_on_multivector() {
+        let state = behavior(GA3::scalar(0.0));
+        let stable = state.distinct_within(0.01);
+
+        state.apply_geometric(|mv| mv + &GA3::scalar(0.001));
+        assert_eq!(stable.geometric_state().get(0), 0.0);
+
+        state.apply_geometric(|mv| mv + &GA3::scalar(1.0));
+        assert!((stable.geometric_state().get(0) - 1.001).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_switch_follows_current_inner() {
+        let a = behavior(1i32);
+        let b = behavior(10i32);
+        let selected = behavior(a.clone());
+        let switched = Behavior::switch(&selected);
+        assert_eq!(switched.sample(), 1);
+
+        a.set(2);
+        assert_eq!(switched.sample(), 2);
+
+        selected.set(b.clone());
+        assert_eq!(switched.sample(), 10);
+
+        a.set(3);
+        assert_eq!(switched.sample(), 10);
+
+        b.set(11);
+        assert_eq!(switched.sample(), 11);
+    }
+
+    #[test]
+    fn test_switch_disposes_previous_inner_subscription() {
+        let a = behavior(0i32);
+        let b = behavior(0i32);
+        let selected = behavior(a.clone());
+        let switched = Behavior::switch(&selected);
+        assert_eq!(a.subscriber_count(), 1);
+
+        selected.set(b.clone());
+        assert_eq!(a.subscriber_count(), 0);
+        assert_eq!(b.subscriber_count(), 1);
+
+        drop(switched);
+        assert_eq!(b.subscriber_count(), 0);
+        assert_eq!(selected.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_switch_disposed_with_scope() {
+        let a = behavior(0i32);
+        let selected = behavior(a.clone());
+        let scope = crate::Scope::new();
+        let _switched = scope.run(|| Behavior::switch(&selected));
+
+        drop(scope);
+        assert_eq!(selected.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_flat_map() {
+        let documents = [behavior("intro".to_string()), behavior("notes".to_string())];
+        let index = behavior(0usize);
+        let docs = documents.clone();
+        let title = index.flat_map(move |i| docs[i].clone());
+
+        index.set(1);
+        assert_eq!(title.sample(), "notes");
+
+        documents[1].set("renamed".to_string());
+        assert_eq!(title.sample(), "renamed");
+
+        documents[0].set("ignored".to_string());
+        assert_eq!(title.sample(), "renamed");
+    }
+
+    #[test]
+    fn test_switch_to_deeper_chain_is_glitch_free() {
+        let a = behavior(1i32);
+        let deep = a.map(|n| n + 1).map(|n| n * 10).map(|n| n - 1);
+        let selected = behavior(a.clone());
+        let switched = Behavior::switch(&selected);
+        let sum = switched.combine(&a, |x, y| x + y);
+        assert!(sum.rank() < deep.rank());
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_clone = Rc::clone(&seen);
+        let _sub = sum.subscribe(move |v| seen_clone.borrow_mut().push(*v));
+
+        selected.set(deep.clone());
+        assert!(switched.rank() > deep.rank());
+        assert!(sum.rank() > switched.rank());
+        assert_eq!(sum.sample(), 20);
+
+        seen.borrow_mut().clear();
+        a.set(2);
+        // (2 + 1) * 10 - 1 + 2, computed once, never from a stale `deep`
+        assert_eq!(*seen.borrow(), vec![31]);
+    }
+
+    #[test]
+    fn test_switch_downstream_is_glitch_free() {
+        let a = behavior(1i32);
+        let selected = behavior(a.clone());
+        let switched = Behavior::switch(&selected);
+        let sum = switched.combine(&a, |x, y| x + y);
+
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = Rc::clone(&calls);
+        let _sub = sum.subscribe(move |_| calls_clone.set(calls_clone.get() + 1));
+
+        a.set(5);
+        assert_eq!(sum.sample(), 10);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_geometric_blob_roundtrip() {
+        let title = behavior("Notes on the Analytical Engine".to_string());
+        let replica = behavior(String::new());
+
+        let notified = Rc::new(Cell::new(0));
+        let notified_clone = Rc::clone(&notified);
+        let _sub = replica.subscribe(move |_| notified_clone.set(notified_clone.get() + 1));
+
+        // The single-multivector state only carries a hash of the string
+        assert_ne!(
+            String::from_geometric(&title.geometric_state()),
+            title.sample()
+        );
+
+        assert!(replica.set_geometric_blob(&title.geometric_blob()));
+        assert_eq!(replica.sample(), "Notes on the Analytical Engine");
+        assert_eq!(notified.get(), 1);
+
+        let invalid = GeometricBlob::from_bytes(&[0xff, 0xfe]);
+        assert!(!replica.set_geometric_blob(&invalid));
+        assert_eq!(replica.sample(), "Notes on the Analytical Engine");
+        assert_eq!(notified.get(), 1);
+    }
 }
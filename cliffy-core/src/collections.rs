@@ -0,0 +1,984 @@
+//! Collections - Reactive lists and maps with fine-grained diffs
+//!
+//! A vector's geometric encoding (length plus hash) can't be decoded, let
+//! alone say *what* changed, so there is no `Behavior<Vec<T>>`.
+//! `BehaviorVec<T>` and `BehaviorMap<K, V>` instead describe every change
+//! as a diff (`Insert`, `Remove`, `Move`, `Update`), so a view can patch a
+//! single row instead of re-rendering the list.
+//!
+//! Derived collections (`map_each`, `filter`, `sort_by`) consume those
+//! diffs and maintain their own contents incrementally: inserting one todo
+//! into a filtered, sorted list runs the predicate and comparator for that
+//! one item only.
+//!
+//! Derived collections follow the same ownership rules as derived
+//! behaviors: they keep their source alive, the source only references
+//! them weakly, and dropping the last handle (or the `Scope` they were
+//! created in) removes their subscription.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::collections::{BehaviorVec, VecDiff};
+//!
+//! let todos = BehaviorVec::from_vec(vec!["write docs".to_string()]);
+//! let open = todos.filter(|t| !t.starts_with('✓'));
+//! let lengths = open.map_each(|t| t.len());
+//!
+//! let _sub = lengths.subscribe(|diff| {
+//!     if let VecDiff::Insert { index, value } = diff {
+//!         println!("new row {} with length {}", index, value);
+//!     }
+//! });
+//!
+//! todos.push("ship it".to_string());
+//! todos.set(0, "✓ write docs".to_string());
+//! assert_eq!(lengths.to_vec(), vec![7]);
+//! ```
+
+use crate::behavior::Subscription;
+use crate::propagation;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// A single change to a `BehaviorVec`
+#[derive(Debug, Clone, PartialEq)]
+pub enum VecDiff<T> {
+    /// `value` was inserted at `index`
+    Insert { index: usize, value: T },
+    /// The item at `index` was removed
+    Remove { index: usize },
+    /// The item at `from` was moved so that it now sits at `to`
+    Move { from: usize, to: usize },
+    /// The item at `index` was replaced by `value`
+    Update { index: usize, value: T },
+}
+
+impl<T: Clone> VecDiff<T> {
+    /// Apply this change to a plain vector
+    ///
+    /// Useful for keeping a mirror (e.g. a list of DOM nodes) in sync.
+    pub fn apply(&self, items: &mut Vec<T>) {
+        match self {
+            VecDiff::Insert { index, value } => items.insert(*index, value.clone()),
+            VecDiff::Remove { index } => {
+                items.remove(*index);
+            }
+            VecDiff::Move { from, to } => {
+                let item = items.remove(*from);
+                items.insert(*to, item);
+            }
+            VecDiff::Update { index, value } => items[*index] = value.clone(),
+        }
+    }
+}
+
+/// A single change to a `BehaviorMap`
+#[derive(Debug, Clone, PartialEq)]
+pub enum MapDiff<K, V> {
+    /// `key` was added with `value`
+    Insert { key: K, value: V },
+    /// `key` was removed
+    Remove { key: K },
+    /// The value of an existing `key` was replaced by `value`
+    Update { key: K, value: V },
+}
+
+impl<K: Ord + Clone, V: Clone> MapDiff<K, V> {
+    /// Apply this change to a plain map
+    pub fn apply(&self, map: &mut BTreeMap<K, V>) {
+        match self {
+            MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+                map.insert(key.clone(), value.clone());
+            }
+            MapDiff::Remove { key } => {
+                map.remove(key);
+            }
+        }
+    }
+}
+
+/// Type alias for diff subscribers to avoid clippy::type_complexity warning
+type DiffSubscriberList<D> = Rc<RefCell<Vec<(usize, Rc<dyn Fn(&D)>)>>>;
+
+/// Subscribers to a stream of diffs
+struct DiffChannel<D> {
+    subscribers: DiffSubscriberList<D>,
+    next_id: Cell<usize>,
+}
+
+impl<D: 'static> DiffChannel<D> {
+    fn new() -> Self {
+        Self {
+            subscribers: Rc::new(RefCell::new(Vec::new())),
+            next_id: Cell::new(0),
+        }
+    }
+
+    fn subscribe(&self, callback: Rc<dyn Fn(&D)>) -> Subscription {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.subscribers.borrow_mut().push((id, callback));
+
+        let subscribers = Rc::downgrade(&self.subscribers);
+        Subscription::new(id, move || {
+            if let Some(subscribers) = subscribers.upgrade() {
+                subscribers.borrow_mut().retain(|(i, _)| *i != id);
+            }
+        })
+        .scoped()
+    }
+
+    fn len(&self) -> usize {
+        self.subscribers.borrow().len()
+    }
+
+    /// Deliver `diff` to every subscriber
+    ///
+    /// Callbacks are collected first, so a subscriber may subscribe or
+    /// mutate the collection that notified it.
+    fn emit(&self, diff: &D) {
+        let callbacks: Vec<_> = self
+            .subscribers
+            .borrow()
+            .iter()
+            .map(|(_, callback)| Rc::clone(callback))
+            .collect();
+        propagation::hold(|| {
+            for callback in callbacks {
+                callback(diff);
+            }
+        });
+    }
+}
+
+/// A derived collection's registration on its source
+///
+/// Dropping it unsubscribes and releases the source.
+struct Upstream {
+    _subscription: Subscription,
+    _source: Rc<dyn Any>,
+}
+
+/// A reactive list that reports every change as a `VecDiff`
+///
+/// Clones share the same list.
+pub struct BehaviorVec<T> {
+    inner: Rc<VecInner<T>>,
+}
+
+struct VecInner<T> {
+    /// Current items
+    items: RefCell<Vec<T>>,
+    /// Subscribers to changes
+    diffs: DiffChannel<VecDiff<T>>,
+    /// Registration on the source (for derived collections)
+    upstream: RefCell<Option<Upstream>>,
+}
+
+impl<T> Clone for BehaviorVec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Clone + 'static> BehaviorVec<T> {
+    /// Create an empty list
+    pub fn new() -> Self {
+        Self::from_vec(Vec::new())
+    }
+
+    /// Create a list with initial items
+    pub fn from_vec(items: Vec<T>) -> Self {
+        Self {
+            inner: Rc::new(VecInner {
+                items: RefCell::new(items),
+                diffs: DiffChannel::new(),
+                upstream: RefCell::new(None),
+            }),
+        }
+    }
+
+    /// Number of items
+    pub fn len(&self) -> usize {
+        self.inner.items.borrow().len()
+    }
+
+    /// Check whether the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.inner.items.borrow().is_empty()
+    }
+
+    /// Get a copy of the item at `index`
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.inner.items.borrow().get(index).cloned()
+    }
+
+    /// Get a copy of all items
+    pub fn to_vec(&self) -> Vec<T> {
+        self.inner.items.borrow().clone()
+    }
+
+    /// Read the items without copying them
+    pub fn with_items<R>(&self, f: impl FnOnce(&[T]) -> R) -> R {
+        f(&self.inner.items.borrow())
+    }
+
+    /// Apply a change and notify subscribers
+    ///
+    /// All other mutators go through here. Panics if an index is out of
+    /// bounds, like the corresponding `Vec` operation.
+    pub fn apply(&self, diff: VecDiff<T>) {
+        diff.apply(&mut self.inner.items.borrow_mut());
+        self.inner.diffs.emit(&diff);
+    }
+
+    /// Append an item
+    pub fn push(&self, value: T) {
+        let index = self.len();
+        self.apply(VecDiff::Insert { index, value });
+    }
+
+    /// Insert an item at `index`
+    pub fn insert(&self, index: usize, value: T) {
+        self.apply(VecDiff::Insert { index, value });
+    }
+
+    /// Remove and return the item at `index`
+    pub fn remove(&self, index: usize) -> T {
+        let removed = self.inner.items.borrow()[index].clone();
+        self.apply(VecDiff::Remove { index });
+        removed
+    }
+
+    /// Replace the item at `index`
+    pub fn set(&self, index: usize, value: T) {
+        self.apply(VecDiff::Update { index, value });
+    }
+
+    /// Replace the item at `index` with `f` applied to it
+    pub fn update(&self, index: usize, f: impl FnOnce(T) -> T) {
+        let current = self.inner.items.borrow()[index].clone();
+        self.set(index, f(current));
+    }
+
+    /// Move the item at `from` so that it sits at `to`
+    pub fn move_item(&self, from: usize, to: usize) {
+        if from != to {
+            self.apply(VecDiff::Move { from, to });
+        }
+    }
+
+    /// Remove every item, last first
+    pub fn clear(&self) {
+        for index in (0..self.len()).rev() {
+            self.apply(VecDiff::Remove { index });
+        }
+    }
+
+    /// Subscribe to changes
+    ///
+    /// Returns a `Subscription` that unsubscribes when dropped.
+    pub fn subscribe<F>(&self, callback: F) -> Subscription
+    where
+        F: Fn(&VecDiff<T>) + 'static,
+    {
+        self.inner.diffs.subscribe(Rc::new(callback))
+    }
+
+    /// Get the number of active subscribers (including derived collections)
+    pub fn subscriber_count(&self) -> usize {
+        self.inner.diffs.len()
+    }
+
+    /// Check whether this collection is derived and still attached to its source
+    pub fn is_derived(&self) -> bool {
+        self.inner.upstream.borrow().is_some()
+    }
+
+    /// Create a derived list starting at `initial` and updated by `on_diff`
+    fn derive<U, F>(&self, initial: Vec<U>, on_diff: F) -> BehaviorVec<U>
+    where
+        U: Clone + 'static,
+        F: Fn(&BehaviorVec<U>, &VecDiff<T>) + 'static,
+    {
+        let derived = BehaviorVec::from_vec(initial);
+        let target = Rc::downgrade(&derived.inner);
+        let subscription = self.subscribe(move |diff| {
+            if let Some(inner) = target.upgrade() {
+                on_diff(&BehaviorVec { inner }, diff);
+            }
+        });
+        *derived.inner.upstream.borrow_mut() = Some(Upstream {
+            _subscription: subscription,
+            _source: Rc::clone(&self.inner) as Rc<dyn Any>,
+        });
+        derived
+    }
+
+    /// Map every item, updating only the items that change
+    ///
+    /// `f` runs once per inserted or updated item; removals and moves are
+    /// mirrored without calling it.
+    pub fn map_each<U, F>(&self, f: F) -> BehaviorVec<U>
+    where
+        U: Clone + 'static,
+        F: Fn(&T) -> U + 'static,
+    {
+        let initial = self.with_items(|items| items.iter().map(&f).collect());
+        self.derive(initial, move |out, diff| match diff {
+            VecDiff::Insert { index, value } => out.insert(*index, f(value)),
+            VecDiff::Remove { index } => {
+                out.remove(*index);
+            }
+            VecDiff::Move { from, to } => out.move_item(*from, *to),
+            VecDiff::Update { index, value } => out.set(*index, f(value)),
+        })
+    }
+
+    /// Keep the items matching `predicate`, in source order
+    ///
+    /// An update that changes whether an item matches becomes an insert or
+    /// a removal in the filtered list.
+    pub fn filter<F>(&self, predicate: F) -> BehaviorVec<T>
+    where
+        F: Fn(&T) -> bool + 'static,
+    {
+        let (mask, initial): (Vec<bool>, Vec<T>) = self.with_items(|items| {
+            let mask: Vec<bool> = items.iter().map(&predicate).collect();
+            let kept = items
+                .iter()
+                .zip(&mask)
+                .filter(|(_, keep)| **keep)
+                .map(|(item, _)| item.clone())
+                .collect();
+            (mask, kept)
+        });
+        let mask = RefCell::new(mask);
+        // Position in the filtered list of the source item at `index`
+        let position = |mask: &[bool], index: usize| mask[..index].iter().filter(|k| **k).count();
+
+        self.derive(initial, move |out, diff| {
+            let mut mask = mask.borrow_mut();
+            match diff {
+                VecDiff::Insert { index, value } => {
+                    let keep = predicate(value);
+                    mask.insert(*index, keep);
+                    if keep {
+                        out.insert(position(&mask, *index), value.clone());
+                    }
+                }
+                VecDiff::Remove { index } => {
+                    if mask.remove(*index) {
+                        out.remove(position(&mask, *index));
+                    }
+                }
+                VecDiff::Move { from, to } => {
+                    let out_from = position(&mask, *from);
+                    let keep = mask.remove(*from);
+                    mask.insert(*to, keep);
+                    if keep {
+                        out.move_item(out_from, position(&mask, *to));
+                    }
+                }
+                VecDiff::Update { index, value } => {
+                    let (was, now) = (mask[*index], predicate(value));
+                    mask[*index] = now;
+                    let at = position(&mask, *index);
+                    match (was, now) {
+                        (true, true) => out.set(at, value.clone()),
+                        (true, false) => {
+                            out.remove(at);
+                        }
+                        (false, true) => out.insert(at, value.clone()),
+                        (false, false) => {}
+                    }
+                }
+            }
+        })
+    }
+
+    /// Keep the items ordered by `compare`
+    ///
+    /// Each change is placed with a binary search rather than a re-sort.
+    /// Items that compare equal keep their insertion order. Moves in the
+    /// source don't affect the sorted list.
+    pub fn sort_by<F>(&self, compare: F) -> BehaviorVec<T>
+    where
+        F: Fn(&T, &T) -> Ordering + 'static,
+    {
+        let (order, initial): (Vec<usize>, Vec<T>) = self.with_items(|items| {
+            let mut order: Vec<usize> = (0..items.len()).collect();
+            order.sort_by(|&a, &b| compare(&items[a], &items[b]));
+            let sorted = order.iter().map(|&i| items[i].clone()).collect();
+            (order, sorted)
+        });
+        // `order[p]` is the source index of the sorted item at position `p`
+        let order = RefCell::new(order);
+
+        self.derive(initial, move |out, diff| {
+            let mut order = order.borrow_mut();
+            let position_of = |order: &[usize], index: usize| {
+                order
+                    .iter()
+                    .position(|&i| i == index)
+                    .expect("sorted list tracks every source item")
+            };
+            // Sorted position for `value`, ignoring the item at `skip`
+            let insertion_point = |value: &T, skip: Option<usize>| {
+                out.with_items(|items| {
+                    let at =
+                        items.partition_point(|item| compare(item, value) != Ordering::Greater);
+                    match skip {
+                        Some(skip) if skip < at => at - 1,
+                        _ => at,
+                    }
+                })
+            };
+
+            match diff {
+                VecDiff::Insert { index, value } => {
+                    for i in order.iter_mut().filter(|i| **i >= *index) {
+                        *i += 1;
+                    }
+                    let at = insertion_point(value, None);
+                    order.insert(at, *index);
+                    out.insert(at, value.clone());
+                }
+                VecDiff::Remove { index } => {
+                    let at = position_of(&order, *index);
+                    order.remove(at);
+                    for i in order.iter_mut().filter(|i| **i > *index) {
+                        *i -= 1;
+                    }
+                    out.remove(at);
+                }
+                VecDiff::Move { from, to } => {
+                    for i in order.iter_mut() {
+                        *i = match *i {
+                            i if i == *from => *to,
+                            i if from < to && i > *from && i <= *to => i - 1,
+                            i if to < from && i >= *to && i < *from => i + 1,
+                            i => i,
+                        };
+                    }
+                }
+                VecDiff::Update { index, value } => {
+                    let at = position_of(&order, *index);
+                    let new_at = insertion_point(value, Some(at));
+                    order.remove(at);
+                    order.insert(new_at, *index);
+                    out.move_item(at, new_at);
+                    out.set(new_at, value.clone());
+                }
+            }
+        })
+    }
+}
+
+impl<T: Clone + 'static> Default for BehaviorVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A reactive, key-ordered map that reports every change as a `MapDiff`
+///
+/// Clones share the same map.
+pub struct BehaviorMap<K, V> {
+    inner: Rc<MapInner<K, V>>,
+}
+
+struct MapInner<K, V> {
+    /// Current entries
+    entries: RefCell<BTreeMap<K, V>>,
+    /// Subscribers to changes
+    diffs: DiffChannel<MapDiff<K, V>>,
+    /// Registration on the source (for derived collections)
+    upstream: RefCell<Option<Upstream>>,
+}
+
+impl<K, V> Clone for BehaviorMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<K, V> BehaviorMap<K, V>
+where
+    K: Ord + Clone + 'static,
+    V: Clone + 'static,
+{
+    /// Create an empty map
+    pub fn new() -> Self {
+        Self::from_map(BTreeMap::new())
+    }
+
+    /// Create a map with initial entries
+    pub fn from_map(entries: BTreeMap<K, V>) -> Self {
+        Self {
+            inner: Rc::new(MapInner {
+                entries: RefCell::new(entries),
+                diffs: DiffChannel::new(),
+                upstream: RefCell::new(None),
+            }),
+        }
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.inner.entries.borrow().len()
+    }
+
+    /// Check whether the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.inner.entries.borrow().is_empty()
+    }
+
+    /// Get a copy of the value for `key`
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.inner.entries.borrow().get(key).cloned()
+    }
+
+    /// Check whether `key` is present
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.inner.entries.borrow().contains_key(key)
+    }
+
+    /// Get the keys in order
+    pub fn keys(&self) -> Vec<K> {
+        self.inner.entries.borrow().keys().cloned().collect()
+    }
+
+    /// Get a copy of all entries
+    pub fn to_map(&self) -> BTreeMap<K, V> {
+        self.inner.entries.borrow().clone()
+    }
+
+    /// Apply a change and notify subscribers
+    pub fn apply(&self, diff: MapDiff<K, V>) {
+        diff.apply(&mut self.inner.entries.borrow_mut());
+        self.inner.diffs.emit(&diff);
+    }
+
+    /// Insert or replace the value for `key`, returning the previous value
+    ///
+    /// Emits `Insert` for a new key and `Update` for an existing one.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let previous = self.get(&key);
+        if previous.is_some() {
+            self.apply(MapDiff::Update { key, value });
+        } else {
+            self.apply(MapDiff::Insert { key, value });
+        }
+        previous
+    }
+
+    /// Remove `key`, returning its value if it was present
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let previous = self.get(key);
+        if previous.is_some() {
+            self.apply(MapDiff::Remove { key: key.clone() });
+        }
+        previous
+    }
+
+    /// Replace the value for `key` with `f` applied to it
+    ///
+    /// Returns `false` (and does nothing) if `key` is absent.
+    pub fn update(&self, key: &K, f: impl FnOnce(V) -> V) -> bool {
+        match self.get(key) {
+            Some(value) => {
+                let value = f(value);
+                self.apply(MapDiff::Update {
+                    key: key.clone(),
+                    value,
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove every entry
+    pub fn clear(&self) {
+        for key in self.keys() {
+            self.apply(MapDiff::Remove { key });
+        }
+    }
+
+    /// Subscribe to changes
+    ///
+    /// Returns a `Subscription` that unsubscribes when dropped.
+    pub fn subscribe<F>(&self, callback: F) -> Subscription
+    where
+        F: Fn(&MapDiff<K, V>) + 'static,
+    {
+        self.inner.diffs.subscribe(Rc::new(callback))
+    }
+
+    /// Get the number of active subscribers (including derived collections)
+    pub fn subscriber_count(&self) -> usize {
+        self.inner.diffs.len()
+    }
+
+    /// Check whether this collection is derived and still attached to its source
+    pub fn is_derived(&self) -> bool {
+        self.inner.upstream.borrow().is_some()
+    }
+
+    /// Create a derived map starting at `initial` and updated by `on_diff`
+    fn derive<U, F>(&self, initial: BTreeMap<K, U>, on_diff: F) -> BehaviorMap<K, U>
+    where
+        U: Clone + 'static,
+        F: Fn(&BehaviorMap<K, U>, &MapDiff<K, V>) + 'static,
+    {
+        let derived = BehaviorMap::from_map(initial);
+        let target = Rc::downgrade(&derived.inner);
+        let subscription = self.subscribe(move |diff| {
+            if let Some(inner) = target.upgrade() {
+                on_diff(&BehaviorMap { inner }, diff);
+            }
+        });
+        *derived.inner.upstream.borrow_mut() = Some(Upstream {
+            _subscription: subscription,
+            _source: Rc::clone(&self.inner) as Rc<dyn Any>,
+        });
+        derived
+    }
+
+    /// Map every value, updating only the entries that change
+    pub fn map_each<U, F>(&self, f: F) -> BehaviorMap<K, U>
+    where
+        U: Clone + 'static,
+        F: Fn(&K, &V) -> U + 'static,
+    {
+        let initial = self
+            .inner
+            .entries
+            .borrow()
+            .iter()
+            .map(|(k, v)| (k.clone(), f(k, v)))
+            .collect();
+        self.derive(initial, move |out, diff| match diff {
+            MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+                out.insert(key.clone(), f(key, value));
+            }
+            MapDiff::Remove { key } => {
+                out.remove(key);
+            }
+        })
+    }
+
+    /// Keep the entries matching `predicate`
+    pub fn filter<F>(&self, predicate: F) -> BehaviorMap<K, V>
+    where
+        F: Fn(&K, &V) -> bool + 'static,
+    {
+        let initial = self
+            .inner
+            .entries
+            .borrow()
+            .iter()
+            .filter(|(k, v)| predicate(k, v))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        self.derive(initial, move |out, diff| match diff {
+            MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+                if predicate(key, value) {
+                    out.insert(key.clone(), value.clone());
+                } else {
+                    out.remove(key);
+                }
+            }
+            MapDiff::Remove { key } => {
+                out.remove(key);
+            }
+        })
+    }
+
+    /// View the entries as a list in key order
+    pub fn entries(&self) -> BehaviorVec<(K, V)> {
+        let initial: Vec<(K, V)> = self
+            .inner
+            .entries
+            .borrow()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        let list = BehaviorVec::from_vec(initial);
+        let target = Rc::downgrade(&list.inner);
+        let subscription = self.subscribe(move |diff| {
+            let Some(inner) = target.upgrade() else {
+                return;
+            };
+            let out = BehaviorVec { inner };
+            let position =
+                |key: &K| out.with_items(|items| items.partition_point(|(k, _)| k < key));
+            match diff {
+                MapDiff::Insert { key, value } => {
+                    out.insert(position(key), (key.clone(), value.clone()));
+                }
+                MapDiff::Update { key, value } => {
+                    out.set(position(key), (key.clone(), value.clone()));
+                }
+                MapDiff::Remove { key } => {
+                    out.remove(position(key));
+                }
+            }
+        });
+        *list.inner.upstream.borrow_mut() = Some(Upstream {
+            _subscription: subscription,
+            _source: Rc::clone(&self.inner) as Rc<dyn Any>,
+        });
+        list
+    }
+
+    /// View the entries as a list ordered by value
+    ///
+    /// Equivalent to `self.entries().sort_by(..)` comparing values.
+    pub fn sort_by<F>(&self, compare: F) -> BehaviorVec<(K, V)>
+    where
+        F: Fn(&V, &V) -> Ordering + 'static,
+    {
+        self.entries().sort_by(move |a, b| compare(&a.1, &b.1))
+    }
+}
+
+impl<K, V> Default for BehaviorMap<K, V>
+where
+    K: Ord + Clone + 'static,
+    V: Clone + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record<T: Clone + 'static>(
+        list: &BehaviorVec<T>,
+    ) -> (Rc<RefCell<Vec<VecDiff<T>>>>, Subscription) {
+        let diffs = Rc::new(RefCell::new(Vec::new()));
+        let diffs_clone = Rc::clone(&diffs);
+        let sub = list.subscribe(move |diff| diffs_clone.borrow_mut().push(diff.clone()));
+        (diffs, sub)
+    }
+
+    #[test]
+    fn test_vec_emits_diffs() {
+        let list = BehaviorVec::from_vec(vec![1, 2, 3]);
+        let (diffs, _sub) = record(&list);
+
+        list.push(4);
+        list.remove(0);
+        list.move_item(0, 2);
+        list.set(1, 30);
+
+        assert_eq!(list.to_vec(), vec![3, 30, 2]);
+        assert_eq!(
+            *diffs.borrow(),
+            vec![
+                VecDiff::Insert { index: 3, value: 4 },
+                VecDiff::Remove { index: 0 },
+                VecDiff::Move { from: 0, to: 2 },
+                VecDiff::Update {
+                    index: 1,
+                    value: 30
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diffs_replay_onto_mirror() {
+        let list = BehaviorVec::from_vec(vec!['a', 'b']);
+        let mirror = Rc::new(RefCell::new(list.to_vec()));
+        let mirror_clone = Rc::clone(&mirror);
+        let _sub = list.subscribe(move |diff| diff.apply(&mut mirror_clone.borrow_mut()));
+
+        list.insert(1, 'x');
+        list.move_item(2, 0);
+        list.update(1, |c| c.to_ascii_uppercase());
+        list.clear();
+        list.push('z');
+
+        assert_eq!(*mirror.borrow(), list.to_vec());
+    }
+
+    #[test]
+    fn test_map_each_is_incremental() {
+        let list = BehaviorVec::from_vec(vec![1, 2, 3]);
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = Rc::clone(&calls);
+        let doubled = list.map_each(move |n| {
+            calls_clone.set(calls_clone.get() + 1);
+            n * 2
+        });
+        assert_eq!(calls.get(), 3);
+
+        list.push(4);
+        list.remove(0);
+        list.move_item(0, 1);
+        assert_eq!(calls.get(), 4);
+        assert_eq!(doubled.to_vec(), vec![6, 4, 8]);
+    }
+
+    #[test]
+    fn test_filter_tracks_predicate_changes() {
+        let list = BehaviorVec::from_vec(vec![1, 2, 3, 4]);
+        let evens = list.filter(|n| n % 2 == 0);
+        let (diffs, _sub) = record(&evens);
+
+        list.set(0, 10);
+        list.set(1, 5);
+        list.insert(2, 6);
+        list.move_item(3, 0);
+        list.remove(4);
+
+        let expected: Vec<i32> = list.to_vec().into_iter().filter(|n| n % 2 == 0).collect();
+        assert_eq!(evens.to_vec(), expected);
+        assert_eq!(
+            diffs.borrow()[0],
+            VecDiff::Insert {
+                index: 0,
+                value: 10
+            }
+        );
+        assert_eq!(diffs.borrow()[1], VecDiff::Remove { index: 1 });
+    }
+
+    #[test]
+    fn test_sort_by_places_changes() {
+        let list = BehaviorVec::from_vec(vec![5, 1, 4]);
+        let sorted = list.sort_by(|a, b| a.cmp(b));
+        assert_eq!(sorted.to_vec(), vec![1, 4, 5]);
+
+        list.push(3);
+        assert_eq!(sorted.to_vec(), vec![1, 3, 4, 5]);
+
+        list.set(1, 9);
+        assert_eq!(sorted.to_vec(), vec![3, 4, 5, 9]);
+
+        list.move_item(0, 3);
+        list.remove(0);
+        assert_eq!(list.to_vec(), vec![4, 3, 5]);
+        assert_eq!(sorted.to_vec(), vec![3, 4, 5]);
+
+        list.set(2, 0);
+        assert_eq!(sorted.to_vec(), vec![0, 3, 4]);
+    }
+
+    #[test]
+    fn test_sort_by_keeps_ties_in_insertion_order() {
+        let list = BehaviorVec::from_vec(vec![(2, 'a'), (1, 'b')]);
+        let sorted = list.sort_by(|a, b| a.0.cmp(&b.0));
+
+        list.push((2, 'c'));
+        list.push((1, 'd'));
+        assert_eq!(
+            sorted.to_vec(),
+            vec![(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]
+        );
+
+        list.set(1, (2, 'e'));
+        assert_eq!(
+            sorted.to_vec(),
+            vec![(1, 'd'), (2, 'a'), (2, 'c'), (2, 'e')]
+        );
+    }
+
+    #[test]
+    fn test_dropping_derived_vec_releases_source() {
+        let list = BehaviorVec::from_vec(vec![1, 2, 3]);
+        let derived = list.filter(|n| *n > 1).map_each(|n| n + 1);
+        assert_eq!(list.subscriber_count(), 1);
+
+        list.push(4);
+        assert_eq!(derived.to_vec(), vec![3, 4, 5]);
+
+        drop(derived);
+        assert_eq!(list.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_derived_vec_disposed_with_scope() {
+        let list = BehaviorVec::from_vec(vec![1]);
+        let scope = crate::Scope::new();
+        let mapped = scope.run(|| list.map_each(|n| n * 10));
+
+        drop(scope);
+        list.push(2);
+        assert_eq!(mapped.to_vec(), vec![10]);
+        assert_eq!(list.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_map_emits_insert_update_remove() {
+        let map = BehaviorMap::new();
+        let diffs = Rc::new(RefCell::new(Vec::new()));
+        let diffs_clone = Rc::clone(&diffs);
+        let _sub = map.subscribe(move |diff| diffs_clone.borrow_mut().push(diff.clone()));
+
+        assert_eq!(map.insert("a", 1), None);
+        assert_eq!(map.insert("a", 2), Some(1));
+        assert!(map.update(&"a", |v| v * 10));
+        assert_eq!(map.remove(&"a"), Some(20));
+        assert_eq!(map.remove(&"a"), None);
+
+        assert_eq!(
+            *diffs.borrow(),
+            vec![
+                MapDiff::Insert { key: "a", value: 1 },
+                MapDiff::Update { key: "a", value: 2 },
+                MapDiff::Update {
+                    key: "a",
+                    value: 20
+                },
+                MapDiff::Remove { key: "a" },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_map_map_each_and_filter() {
+        let stock = BehaviorMap::new();
+        stock.insert("apples", 3);
+        let available = stock
+            .filter(|_, n| *n > 0)
+            .map_each(|name, n| format!("{}: {}", name, n));
+
+        stock.insert("pears", 0);
+        stock.insert("plums", 2);
+        stock.update(&"apples", |_| 0);
+
+        assert_eq!(available.keys(), vec!["plums"]);
+        assert_eq!(available.get(&"plums"), Some("plums: 2".to_string()));
+    }
+
+    #[test]
+    fn test_map_sort_by_value() {
+        let scores = BehaviorMap::new();
+        scores.insert("ada", 3);
+        scores.insert("grace", 5);
+        let ranking = scores.sort_by(|a, b| b.cmp(a));
+
+        scores.insert("alan", 4);
+        scores.update(&"ada", |_| 9);
+        scores.remove(&"grace");
+
+        assert_eq!(ranking.to_vec(), vec![("ada", 9), ("alan", 4)]);
+        assert_eq!(scores.entries().to_vec(), vec![("ada", 9), ("alan", 4)]);
+    }
+}
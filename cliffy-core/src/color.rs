@@ -0,0 +1,446 @@
+//! Perceptual color spaces
+//!
+//! `ColorProjection` reads RGB straight out of the vector components, so
+//! blending two colors with `GeometricState::lerp` interpolates in sRGB and
+//! the midpoints come out muddy and dark. The types here store colors as
+//! [OKLab](https://bottosson.github.io/posts/oklab/) coordinates instead
+//! (lightness in the scalar, a in e1, b in e2), where straight lines are
+//! perceptually even:
+//!
+//! - `Oklab`: lightness L (0 to 1) and the opponent axes a and b
+//! - `Oklch`: OKLab in polar form, with chroma C and hue h in degrees
+//! - `Hsl`: the familiar sRGB hue, saturation and lightness
+//!
+//! All three encode as OKLab, so whichever type a state was built from,
+//! `lerp` blends perceptually. Not every OKLab coordinate is a displayable
+//! sRGB color; `to_rgb8` gamut maps by reducing chroma at constant
+//! lightness and hue (as CSS Color 4 does) rather than clipping channels.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::color::Oklab;
+//! use cliffy_core::{FromGeometric, GeometricState, IntoGeometric};
+//!
+//! let red = GeometricState::new(Oklab::from_rgb8(255, 0, 0).into_geometric());
+//! let blue = GeometricState::new(Oklab::from_rgb8(0, 0, 255).into_geometric());
+//!
+//! let middle = Oklab::from_geometric(&red.lerp(&blue, 0.5).multivector());
+//! let (r, g, b) = middle.to_rgb8();
+//!
+//! // A lighter purple than the sRGB midpoint (128, 0, 128)
+//! assert!(r > 128 && g > 64 && b > 128);
+//! ```
+
+use crate::geometric::{FromGeometric, IntoGeometric, GA3};
+
+/// How far outside [0, 1] a channel may stray and still count as in gamut
+const GAMUT_EPSILON: f64 = 1e-6;
+
+/// Bisection steps when reducing chroma, enough for 8-bit output
+const GAMUT_STEPS: usize = 24;
+
+/// A color in OKLab
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Oklab {
+    /// Perceived lightness, 0 (black) to 1 (white)
+    pub l: f64,
+    /// Green (negative) to red (positive)
+    pub a: f64,
+    /// Blue (negative) to yellow (positive)
+    pub b: f64,
+}
+
+/// A color in OKLCH, the polar form of OKLab
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Oklch {
+    /// Perceived lightness, 0 (black) to 1 (white)
+    pub l: f64,
+    /// Chroma, 0 for grays and around 0.4 at most for sRGB colors
+    pub c: f64,
+    /// Hue in degrees
+    pub h: f64,
+}
+
+/// A color in HSL over sRGB
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Hsl {
+    /// Hue in degrees
+    pub h: f64,
+    /// Saturation, 0 to 1
+    pub s: f64,
+    /// Lightness, 0 to 1
+    pub l: f64,
+}
+
+// ============================================================================
+// sRGB
+// ============================================================================
+
+/// sRGB transfer function, gamma-encoded to linear (sign preserving, so
+/// out-of-gamut values survive the round trip)
+fn srgb_to_linear(c: f64) -> f64 {
+    let magnitude = c.abs();
+    let linear = if magnitude <= 0.04045 {
+        magnitude / 12.92
+    } else {
+        ((magnitude + 0.055) / 1.055).powf(2.4)
+    };
+    linear.copysign(c)
+}
+
+/// Inverse of `srgb_to_linear`
+fn linear_to_srgb(c: f64) -> f64 {
+    let magnitude = c.abs();
+    let encoded = if magnitude <= 0.0031308 {
+        magnitude * 12.92
+    } else {
+        1.055 * magnitude.powf(1.0 / 2.4) - 0.055
+    };
+    encoded.copysign(c)
+}
+
+fn to_byte(c: f64) -> u8 {
+    (c.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+// ============================================================================
+// OKLab
+// ============================================================================
+
+impl Oklab {
+    /// Create a color from its coordinates
+    pub fn new(l: f64, a: f64, b: f64) -> Self {
+        Self { l, a, b }
+    }
+
+    /// Convert from gamma-encoded sRGB channels in 0 to 1
+    pub fn from_srgb(r: f64, g: f64, b: f64) -> Self {
+        let (r, g, b) = (srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b));
+
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        Self {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        }
+    }
+
+    /// Convert to gamma-encoded sRGB channels
+    ///
+    /// Out-of-gamut colors give channels outside 0 to 1; use `gamut_map`
+    /// first to keep them in range.
+    pub fn to_srgb(&self) -> (f64, f64, f64) {
+        let l = self.l + 0.3963377774 * self.a + 0.2158037573 * self.b;
+        let m = self.l - 0.1055613458 * self.a - 0.0638541728 * self.b;
+        let s = self.l - 0.0894841775 * self.a - 1.2914855480 * self.b;
+        let (l, m, s) = (l * l * l, m * m * m, s * s * s);
+
+        (
+            linear_to_srgb(4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s),
+            linear_to_srgb(-1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s),
+            linear_to_srgb(-0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s),
+        )
+    }
+
+    /// Convert from 8-bit sRGB
+    pub fn from_rgb8(r: u8, g: u8, b: u8) -> Self {
+        Self::from_srgb(r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0)
+    }
+
+    /// Convert to 8-bit sRGB, gamut mapping first
+    pub fn to_rgb8(&self) -> (u8, u8, u8) {
+        let (r, g, b) = self.gamut_map().to_srgb();
+        (to_byte(r), to_byte(g), to_byte(b))
+    }
+
+    /// Whether the color is displayable in sRGB
+    pub fn in_gamut(&self) -> bool {
+        let (r, g, b) = self.to_srgb();
+        [r, g, b]
+            .iter()
+            .all(|c| (-GAMUT_EPSILON..=1.0 + GAMUT_EPSILON).contains(c))
+    }
+
+    /// The closest displayable color with the same lightness and hue
+    ///
+    /// Chroma is reduced by bisection until the color fits in sRGB.
+    /// Lightness at or beyond the ends of the range maps to black or white.
+    pub fn gamut_map(&self) -> Oklab {
+        if self.in_gamut() {
+            return *self;
+        }
+        if self.l >= 1.0 {
+            return Oklab::new(1.0, 0.0, 0.0);
+        }
+        if self.l <= 0.0 {
+            return Oklab::new(0.0, 0.0, 0.0);
+        }
+
+        let lch = Oklch::from(*self);
+        let (mut low, mut high) = (0.0, lch.c);
+        for _ in 0..GAMUT_STEPS {
+            let c = (low + high) / 2.0;
+            if Oklab::from(Oklch { c, ..lch }).in_gamut() {
+                low = c;
+            } else {
+                high = c;
+            }
+        }
+        Oklch { c: low, ..lch }.into()
+    }
+}
+
+// ============================================================================
+// OKLCH
+// ============================================================================
+
+impl Oklch {
+    /// Create a color from lightness, chroma and hue (degrees)
+    pub fn new(l: f64, c: f64, h: f64) -> Self {
+        Self { l, c, h }
+    }
+
+    /// Convert from 8-bit sRGB
+    pub fn from_rgb8(r: u8, g: u8, b: u8) -> Self {
+        Oklab::from_rgb8(r, g, b).into()
+    }
+
+    /// Convert to 8-bit sRGB, gamut mapping first
+    pub fn to_rgb8(&self) -> (u8, u8, u8) {
+        Oklab::from(*self).to_rgb8()
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(lab: Oklab) -> Self {
+        Self {
+            l: lab.l,
+            c: lab.a.hypot(lab.b),
+            h: lab.b.atan2(lab.a).to_degrees().rem_euclid(360.0),
+        }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(lch: Oklch) -> Self {
+        let h = lch.h.to_radians();
+        Self {
+            l: lch.l,
+            a: lch.c * h.cos(),
+            b: lch.c * h.sin(),
+        }
+    }
+}
+
+// ============================================================================
+// HSL
+// ============================================================================
+
+impl Hsl {
+    /// Create a color from hue (degrees), saturation and lightness
+    pub fn new(h: f64, s: f64, l: f64) -> Self {
+        Self { h, s, l }
+    }
+
+    /// Convert from gamma-encoded sRGB channels in 0 to 1
+    pub fn from_srgb(r: f64, g: f64, b: f64) -> Self {
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+        if delta <= f64::EPSILON {
+            return Self { h: 0.0, s: 0.0, l };
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            (g - b) / delta
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
+        Self {
+            h: (h * 60.0).rem_euclid(360.0),
+            s,
+            l,
+        }
+    }
+
+    /// Convert to gamma-encoded sRGB channels in 0 to 1
+    pub fn to_srgb(&self) -> (f64, f64, f64) {
+        let s = self.s.clamp(0.0, 1.0);
+        let l = self.l.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h = self.h.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = l - chroma / 2.0;
+        (r + m, g + m, b + m)
+    }
+
+    /// Convert from 8-bit sRGB
+    pub fn from_rgb8(r: u8, g: u8, b: u8) -> Self {
+        Self::from_srgb(r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0)
+    }
+
+    /// Convert to 8-bit sRGB
+    pub fn to_rgb8(&self) -> (u8, u8, u8) {
+        let (r, g, b) = self.to_srgb();
+        (to_byte(r), to_byte(g), to_byte(b))
+    }
+}
+
+impl From<Hsl> for Oklab {
+    fn from(hsl: Hsl) -> Self {
+        let (r, g, b) = hsl.to_srgb();
+        Oklab::from_srgb(r, g, b)
+    }
+}
+
+/// Gamut maps first, since HSL only covers sRGB
+impl From<Oklab> for Hsl {
+    fn from(lab: Oklab) -> Self {
+        let (r, g, b) = lab.gamut_map().to_srgb();
+        Hsl::from_srgb(r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0))
+    }
+}
+
+// ============================================================================
+// Geometric encodings - OKLab coordinates in the scalar, e1 and e2
+// ============================================================================
+
+impl IntoGeometric for Oklab {
+    fn into_geometric(self) -> GA3 {
+        GA3::from_coefficients(vec![self.l, self.a, self.b, 0.0, 0.0, 0.0, 0.0, 0.0])
+    }
+}
+
+impl FromGeometric for Oklab {
+    fn from_geometric(mv: &GA3) -> Self {
+        Self::new(mv.get(0), mv.get(1), mv.get(2))
+    }
+}
+
+impl IntoGeometric for Oklch {
+    fn into_geometric(self) -> GA3 {
+        Oklab::from(self).into_geometric()
+    }
+}
+
+impl FromGeometric for Oklch {
+    fn from_geometric(mv: &GA3) -> Self {
+        Oklab::from_geometric(mv).into()
+    }
+}
+
+impl IntoGeometric for Hsl {
+    fn into_geometric(self) -> GA3 {
+        Oklab::from(self).into_geometric()
+    }
+}
+
+impl FromGeometric for Hsl {
+    fn from_geometric(mv: &GA3) -> Self {
+        Oklab::from_geometric(mv).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() < tolerance,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_oklab_reference_values() {
+        // Reference values from the OKLab definition
+        let red = Oklab::from_rgb8(255, 0, 0);
+        assert_close(red.l, 0.627955, 1e-5);
+        assert_close(red.a, 0.224863, 1e-5);
+        assert_close(red.b, 0.125846, 1e-5);
+
+        let white = Oklab::from_rgb8(255, 255, 255);
+        assert_close(white.l, 1.0, 1e-6);
+        assert_close(white.a, 0.0, 1e-6);
+        assert_close(white.b, 0.0, 1e-6);
+    }
+
+    #[test]
+    fn test_rgb8_roundtrip() {
+        for rgb in [
+            (255, 0, 0),
+            (12, 200, 97),
+            (0, 0, 0),
+            (255, 255, 255),
+            (128, 64, 192),
+        ] {
+            assert_eq!(Oklab::from_rgb8(rgb.0, rgb.1, rgb.2).to_rgb8(), rgb);
+            assert_eq!(Oklch::from_rgb8(rgb.0, rgb.1, rgb.2).to_rgb8(), rgb);
+            assert_eq!(Hsl::from_rgb8(rgb.0, rgb.1, rgb.2).to_rgb8(), rgb);
+        }
+    }
+
+    #[test]
+    fn test_hsl_values() {
+        let orange = Hsl::from_rgb8(255, 128, 0);
+        assert_close(orange.h, 30.1, 0.1);
+        assert_close(orange.s, 1.0, 1e-9);
+        assert_close(orange.l, 0.5, 1e-9);
+        assert_eq!(Hsl::new(240.0, 1.0, 0.5).to_rgb8(), (0, 0, 255));
+    }
+
+    #[test]
+    fn test_gamut_mapping_keeps_lightness_and_hue() {
+        let vivid = Oklch::new(0.7, 0.4, 150.0);
+        assert!(!Oklab::from(vivid).in_gamut());
+
+        let mapped = Oklch::from(Oklab::from(vivid).gamut_map());
+        assert!(Oklab::from(mapped).in_gamut());
+        assert_close(mapped.l, 0.7, 1e-9);
+        assert_close(mapped.h, 150.0, 1e-6);
+        assert!(mapped.c < 0.4 && mapped.c > 0.1);
+
+        assert_eq!(Oklab::new(1.5, 0.1, 0.0).to_rgb8(), (255, 255, 255));
+        assert_eq!(Oklab::new(-0.2, 0.1, 0.0).to_rgb8(), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_lerp_is_perceptual() {
+        // Lightness of the OKLab midpoint of black and white is halfway,
+        // while the sRGB midpoint is darker
+        let black = Oklab::from_rgb8(0, 0, 0).into_geometric();
+        let white = Hsl::new(0.0, 0.0, 1.0).into_geometric();
+        let middle = &(&black + &white) * 0.5;
+        assert_close(Oklab::from_geometric(&middle).l, 0.5, 1e-6);
+        assert_close(Oklab::from_rgb8(128, 128, 128).l, 0.6, 0.01);
+    }
+
+    #[test]
+    fn test_encodings_agree() {
+        let lch = Oklch::new(0.6, 0.1, 200.0);
+        let lab = Oklab::from_geometric(&lch.into_geometric());
+        let back = Oklch::from_geometric(&lab.into_geometric());
+        assert_close(back.l, 0.6, 1e-12);
+        assert_close(back.c, 0.1, 1e-12);
+        assert_close(back.h, 200.0, 1e-9);
+    }
+}
@@ -30,24 +30,7 @@ where
     T: IntoGeometric + FromGeometric + Clone + Default + 'static,
     F: Fn() -> T + 'static,
 {
-    let initial = if condition.sample() {
-        Some(then_value())
-    } else {
-        None
-    };
-
-    let result = behavior(initial);
-    let result_clone = result.clone();
-
-    condition.subscribe(move |&cond| {
-        if cond {
-            result_clone.set(Some(then_value()));
-        } else {
-            result_clone.set(None);
-        }
-    });
-
-    result
+    condition.map(move |cond| if cond { Some(then_value()) } else { None })
 }
 
 /// Combine two behaviors into one
@@ -103,8 +86,10 @@ where
     D: IntoGeometric + FromGeometric + Clone + 'static,
     F: Fn(A, B, C) -> D + Clone + 'static,
 {
-    let ab = a.combine(b, |x, y| (x, y));
-    ab.combine(c, move |(x, y), z| f(x, y, z))
+    let (sa, sb, sc) = (a.clone(), b.clone(), c.clone());
+    Behavior::derive("combine3", &[a, b, c], move || {
+        f(sa.sample(), sb.sample(), sc.sample())
+    })
 }
 
 /// If-then-else combinator
@@ -135,24 +120,7 @@ where
     TF: Fn() -> T + 'static,
     EF: Fn() -> T + 'static,
 {
-    let initial = if condition.sample() {
-        then_value()
-    } else {
-        else_value()
-    };
-
-    let result = behavior(initial);
-    let result_clone = result.clone();
-
-    condition.subscribe(move |&cond| {
-        if cond {
-            result_clone.set(then_value());
-        } else {
-            result_clone.set(else_value());
-        }
-    });
-
-    result
+    condition.map(move |cond| if cond { then_value() } else { else_value() })
 }
 
 /// Constant behavior that never changes
@@ -237,6 +205,28 @@ mod tests {
         let sum = combine3(&a, &b, &c, |x, y, z| x + y + z);
 
         assert_eq!(sum.sample(), 6);
+
+        c.set(10);
+        assert_eq!(sum.sample(), 13);
+    }
+
+    #[test]
+    fn test_combine3_shared_source_fires_once() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let a = behavior(1i32);
+        let b = a.map(|n| n * 10);
+        let c = a.map(|n| n * 100);
+        let sum = combine3(&a, &b, &c, |x, y, z| x + y + z);
+
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = Rc::clone(&calls);
+        let _sub = sum.subscribe(move |_| calls_clone.set(calls_clone.get() + 1));
+
+        a.set(2);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(sum.sample(), 222);
     }
 
     #[test]
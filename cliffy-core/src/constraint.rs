@@ -0,0 +1,736 @@
+//! Geometric constraint solving on `GeometricState`
+//!
+//! A `ConstraintSolver` holds a set of point states and constraints between
+//! them ("keep this point on that line", "keep these two distances equal")
+//! and iteratively moves the points until every constraint is satisfied.
+//! Solved positions are written back with `GeometricState::set`, so
+//! subscribers see one update per state that moved.
+//!
+//! Each constraint is a residual function of the points it relates, zero
+//! when it is satisfied, together with its gradient. Two methods are
+//! available:
+//!
+//! - `SolverMethod::GaussSeidel`: visit the constraints in turn and move
+//!   each one's points straight onto it (position-based projection). Cheap
+//!   and robust; conflicting constraints settle on a compromise.
+//! - `SolverMethod::Newton`: a Gauss-Newton step on all constraints at
+//!   once, taking the smallest move that satisfies their linearisation.
+//!   Converges in a few iterations when the constraints are consistent.
+//!
+//! States work in any algebra that embeds Euclidean points (GA3, PGA3,
+//! CGA3); constraints see plain (x, y, z) coordinates.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::constraint::{ConstraintSolver, Distance, PointOnLine};
+//! use cliffy_core::GeometricState;
+//!
+//! let start = GeometricState::from_vector(0.0, 0.0, 0.0);
+//! let end = GeometricState::from_vector(1.0, 0.0, 0.0);
+//! let handle = GeometricState::from_vector(0.5, 2.0, 0.0);
+//!
+//! let mut solver = ConstraintSolver::new();
+//! let a = solver.add_fixed_state(&start);
+//! let b = solver.add_fixed_state(&end);
+//! let h = solver.add_state(&handle);
+//!
+//! // Keep the handle on the X axis, 3 away from the start
+//! solver.add_constraint(PointOnLine::new(h, a, b));
+//! solver.add_constraint(Distance::new(a, h, 3.0));
+//!
+//! assert!(solver.solve().converged);
+//! let (x, y, _) = handle.as_vector();
+//! assert!((x - 3.0).abs() < 1e-6 && y.abs() < 1e-6);
+//! ```
+
+use crate::algebra::EuclideanEmbedding;
+use crate::state::GeometricState;
+use amari_core::Multivector;
+
+/// Index of a state registered with a `ConstraintSolver`
+pub type StateId = usize;
+
+/// Type alias for per-state gradients to avoid clippy::type_complexity warning
+type StateGradients = Vec<(StateId, (f64, f64, f64))>;
+
+/// A constraint between point states
+///
+/// `residual` is zero exactly when the constraint holds; the solver drives
+/// it to zero along `gradient`. Points are passed in the order of `states`.
+pub trait Constraint: Send + Sync {
+    /// The states this constraint relates
+    fn states(&self) -> &[StateId];
+
+    /// Signed violation of the constraint
+    fn residual(&self, points: &[(f64, f64, f64)]) -> f64;
+
+    /// Gradient of the residual with respect to each point
+    ///
+    /// Defaults to central differences, which is adequate for smooth
+    /// residuals; implement it analytically where the residual has a kink
+    /// at zero (such as an unsigned distance).
+    fn gradient(&self, points: &[(f64, f64, f64)]) -> Vec<(f64, f64, f64)> {
+        const STEP: f64 = 1e-6;
+        let mut probe = points.to_vec();
+        let mut partial = |i: usize, axis: usize| {
+            let original = probe[i];
+            let mut at = |delta: f64| {
+                let mut p = original;
+                match axis {
+                    0 => p.0 += delta,
+                    1 => p.1 += delta,
+                    _ => p.2 += delta,
+                }
+                probe[i] = p;
+                self.residual(&probe)
+            };
+            let slope = (at(STEP) - at(-STEP)) / (2.0 * STEP);
+            probe[i] = original;
+            slope
+        };
+        (0..points.len())
+            .map(|i| (partial(i, 0), partial(i, 1), partial(i, 2)))
+            .collect()
+    }
+
+    /// Description of this constraint
+    fn description(&self) -> &str;
+}
+
+/// How the solver steps towards a solution
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SolverMethod {
+    /// Project onto one constraint at a time
+    #[default]
+    GaussSeidel,
+    /// Gauss-Newton step on all constraints together
+    Newton,
+}
+
+/// Outcome of `ConstraintSolver::solve`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SolveReport {
+    /// Iterations performed (sweeps for Gauss-Seidel)
+    pub iterations: usize,
+    /// Largest remaining |residual|
+    pub residual: f64,
+    /// Whether the residual fell below the tolerance
+    pub converged: bool,
+}
+
+/// Iterative solver moving point states onto a set of constraints
+///
+/// States are shared handles: the solver reads their current positions
+/// when `solve` is called and writes the solution back into them.
+pub struct ConstraintSolver<const P: usize = 3, const Q: usize = 0, const R: usize = 0> {
+    states: Vec<GeometricState<P, Q, R>>,
+    /// Inverse mass: 1 for free states, 0 for fixed ones
+    weights: Vec<f64>,
+    constraints: Vec<Box<dyn Constraint>>,
+    method: SolverMethod,
+    tolerance: f64,
+    max_iterations: usize,
+}
+
+impl<const P: usize, const Q: usize, const R: usize> ConstraintSolver<P, Q, R>
+where
+    Multivector<P, Q, R>: EuclideanEmbedding,
+{
+    /// Create an empty solver (Gauss-Seidel, tolerance 1e-9, 100 iterations)
+    pub fn new() -> Self {
+        Self {
+            states: Vec::new(),
+            weights: Vec::new(),
+            constraints: Vec::new(),
+            method: SolverMethod::default(),
+            tolerance: 1e-9,
+            max_iterations: 100,
+        }
+    }
+
+    /// Set the stepping method
+    pub fn with_method(mut self, method: SolverMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Set the residual below which a constraint counts as satisfied
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the maximum number of iterations per `solve`
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Register a state the solver may move
+    pub fn add_state(&mut self, state: &GeometricState<P, Q, R>) -> StateId {
+        self.states.push(state.clone());
+        self.weights.push(1.0);
+        self.states.len() - 1
+    }
+
+    /// Register a state that constraints refer to but the solver never moves
+    pub fn add_fixed_state(&mut self, state: &GeometricState<P, Q, R>) -> StateId {
+        let id = self.add_state(state);
+        self.weights[id] = 0.0;
+        id
+    }
+
+    /// Fix or free a registered state
+    ///
+    /// Returns false if the state doesn't exist.
+    pub fn set_fixed(&mut self, id: StateId, fixed: bool) -> bool {
+        match self.weights.get_mut(id) {
+            Some(weight) => {
+                *weight = if fixed { 0.0 } else { 1.0 };
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Add a constraint
+    ///
+    /// Returns false (and ignores the constraint) if it refers to a state
+    /// that doesn't exist.
+    pub fn add_constraint(&mut self, constraint: impl Constraint + 'static) -> bool {
+        if constraint
+            .states()
+            .iter()
+            .any(|&id| id >= self.states.len())
+        {
+            return false;
+        }
+        self.constraints.push(Box::new(constraint));
+        true
+    }
+
+    /// Number of registered states
+    pub fn state_count(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Number of constraints
+    pub fn constraint_count(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Largest |residual| over the constraints at the current positions
+    pub fn residual(&self) -> f64 {
+        self.max_residual(&self.positions())
+    }
+
+    /// Move the free states until all constraints hold
+    ///
+    /// Stops when the largest residual is within tolerance or after the
+    /// maximum number of iterations, then sets every state that moved,
+    /// notifying its subscribers once.
+    pub fn solve(&self) -> SolveReport {
+        let initial = self.positions();
+        let mut points = initial.clone();
+
+        let mut iterations = 0;
+        let mut residual = self.max_residual(&points);
+        while residual > self.tolerance && iterations < self.max_iterations {
+            match self.method {
+                SolverMethod::GaussSeidel => self.gauss_seidel_sweep(&mut points),
+                SolverMethod::Newton => {
+                    if !self.newton_step(&mut points) {
+                        break;
+                    }
+                }
+            }
+            iterations += 1;
+            residual = self.max_residual(&points);
+        }
+
+        for ((state, before), after) in self.states.iter().zip(&initial).zip(&points) {
+            if before != after {
+                state.set(Multivector::point(after.0, after.1, after.2));
+            }
+        }
+
+        SolveReport {
+            iterations,
+            residual,
+            converged: residual <= self.tolerance,
+        }
+    }
+
+    fn positions(&self) -> Vec<(f64, f64, f64)> {
+        self.states.iter().map(|state| state.as_point()).collect()
+    }
+
+    fn max_residual(&self, points: &[(f64, f64, f64)]) -> f64 {
+        self.constraints
+            .iter()
+            .map(|c| c.residual(&gather(c.as_ref(), points)).abs())
+            .fold(0.0, f64::max)
+    }
+
+    /// Per-state gradient of one constraint, merging repeated states
+    fn state_gradients(
+        &self,
+        constraint: &dyn Constraint,
+        points: &[(f64, f64, f64)],
+    ) -> (f64, StateGradients) {
+        let local = gather(constraint, points);
+        let residual = constraint.residual(&local);
+        let mut merged: StateGradients = Vec::new();
+        for (&id, g) in constraint.states().iter().zip(constraint.gradient(&local)) {
+            match merged.iter_mut().find(|(existing, _)| *existing == id) {
+                Some((_, total)) => *total = add(*total, g),
+                None => merged.push((id, g)),
+            }
+        }
+        (residual, merged)
+    }
+
+    fn gauss_seidel_sweep(&self, points: &mut [(f64, f64, f64)]) {
+        for constraint in &self.constraints {
+            let (residual, gradients) = self.state_gradients(constraint.as_ref(), points);
+            let denominator: f64 = gradients
+                .iter()
+                .map(|&(id, g)| self.weights[id] * dot(g, g))
+                .sum();
+            if denominator < 1e-12 {
+                continue;
+            }
+
+            // Smallest weighted move that zeroes the linearised residual
+            let lambda = residual / denominator;
+            for (id, g) in gradients {
+                points[id] = add(points[id], scale(g, -lambda * self.weights[id]));
+            }
+        }
+    }
+
+    /// Returns false when the linear system is singular
+    fn newton_step(&self, points: &mut [(f64, f64, f64)]) -> bool {
+        let rows: Vec<_> = self
+            .constraints
+            .iter()
+            .map(|c| self.state_gradients(c.as_ref(), points))
+            .collect();
+
+        // Minimum-norm step: Δ = -W Jᵀ (J W Jᵀ)⁻¹ r
+        let m = rows.len();
+        let mut system = vec![vec![0.0; m + 1]; m];
+        for (i, (residual, gi)) in rows.iter().enumerate() {
+            for (j, (_, gj)) in rows.iter().enumerate() {
+                system[i][j] = gi
+                    .iter()
+                    .flat_map(|&(a, ga)| {
+                        gj.iter()
+                            .filter(move |&&(b, _)| a == b)
+                            .map(move |&(_, gb)| self.weights[a] * dot(ga, gb))
+                    })
+                    .sum();
+            }
+            // Tiny damping keeps redundant constraints solvable
+            system[i][i] += 1e-12;
+            system[i][m] = *residual;
+        }
+
+        let Some(lambdas) = solve_linear(system) else {
+            return false;
+        };
+        for ((_, gradients), lambda) in rows.iter().zip(lambdas) {
+            for &(id, g) in gradients {
+                points[id] = add(points[id], scale(g, -lambda * self.weights[id]));
+            }
+        }
+        true
+    }
+}
+
+impl<const P: usize, const Q: usize, const R: usize> Default for ConstraintSolver<P, Q, R>
+where
+    Multivector<P, Q, R>: EuclideanEmbedding,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The points a constraint refers to, in its order
+fn gather(constraint: &dyn Constraint, points: &[(f64, f64, f64)]) -> Vec<(f64, f64, f64)> {
+    constraint.states().iter().map(|&id| points[id]).collect()
+}
+
+/// Solve an augmented n x (n+1) system by Gaussian elimination with
+/// partial pivoting
+fn solve_linear(mut system: Vec<Vec<f64>>) -> Option<Vec<f64>> {
+    let n = system.len();
+    for col in 0..n {
+        let pivot =
+            (col..n).max_by(|&a, &b| system[a][col].abs().total_cmp(&system[b][col].abs()))?;
+        if system[pivot][col].abs() < 1e-14 {
+            return None;
+        }
+        system.swap(col, pivot);
+        let pivot_row = system[col].clone();
+        for row in system.iter_mut().skip(col + 1) {
+            let factor = row[col] / pivot_row[col];
+            for (value, pivot_value) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+
+    let mut solution = vec![0.0; n];
+    for row in (0..n).rev() {
+        let known: f64 = (row + 1..n).map(|k| system[row][k] * solution[k]).sum();
+        solution[row] = (system[row][n] - known) / system[row][row];
+    }
+    Some(solution)
+}
+
+fn add(a: (f64, f64, f64), b: (f64, f64, f64)) -> (f64, f64, f64) {
+    (a.0 + b.0, a.1 + b.1, a.2 + b.2)
+}
+
+fn sub(a: (f64, f64, f64), b: (f64, f64, f64)) -> (f64, f64, f64) {
+    (a.0 - b.0, a.1 - b.1, a.2 - b.2)
+}
+
+fn scale(a: (f64, f64, f64), factor: f64) -> (f64, f64, f64) {
+    (a.0 * factor, a.1 * factor, a.2 * factor)
+}
+
+fn dot(a: (f64, f64, f64), b: (f64, f64, f64)) -> f64 {
+    a.0 * b.0 + a.1 * b.1 + a.2 * b.2
+}
+
+/// Unit vector along `a`, or zero for a zero vector
+fn direction(a: (f64, f64, f64)) -> (f64, f64, f64) {
+    let length = dot(a, a).sqrt();
+    if length < 1e-12 {
+        (0.0, 0.0, 0.0)
+    } else {
+        scale(a, 1.0 / length)
+    }
+}
+
+// ============================================================================
+// Built-in constraints
+// ============================================================================
+
+/// Constraint: two points are a fixed distance apart
+pub struct Distance {
+    states: [StateId; 2],
+    length: f64,
+}
+
+impl Distance {
+    /// Keep `a` and `b` exactly `length` apart
+    pub fn new(a: StateId, b: StateId, length: f64) -> Self {
+        Self {
+            states: [a, b],
+            length,
+        }
+    }
+}
+
+impl Constraint for Distance {
+    fn states(&self) -> &[StateId] {
+        &self.states
+    }
+
+    fn residual(&self, points: &[(f64, f64, f64)]) -> f64 {
+        let d = sub(points[0], points[1]);
+        dot(d, d).sqrt() - self.length
+    }
+
+    fn gradient(&self, points: &[(f64, f64, f64)]) -> Vec<(f64, f64, f64)> {
+        let u = direction(sub(points[0], points[1]));
+        vec![u, scale(u, -1.0)]
+    }
+
+    fn description(&self) -> &str {
+        "Distance constraint"
+    }
+}
+
+/// Constraint: the distance a-b equals the distance c-d
+pub struct EqualDistance {
+    states: [StateId; 4],
+}
+
+impl EqualDistance {
+    /// Keep |a - b| equal to |c - d|
+    pub fn new(a: StateId, b: StateId, c: StateId, d: StateId) -> Self {
+        Self {
+            states: [a, b, c, d],
+        }
+    }
+}
+
+impl Constraint for EqualDistance {
+    fn states(&self) -> &[StateId] {
+        &self.states
+    }
+
+    fn residual(&self, points: &[(f64, f64, f64)]) -> f64 {
+        let first = sub(points[0], points[1]);
+        let second = sub(points[2], points[3]);
+        dot(first, first).sqrt() - dot(second, second).sqrt()
+    }
+
+    fn gradient(&self, points: &[(f64, f64, f64)]) -> Vec<(f64, f64, f64)> {
+        let u = direction(sub(points[0], points[1]));
+        let v = direction(sub(points[2], points[3]));
+        vec![u, scale(u, -1.0), scale(v, -1.0), v]
+    }
+
+    fn description(&self) -> &str {
+        "Equal distance constraint"
+    }
+}
+
+/// Constraint: a point lies on the line through two others
+pub struct PointOnLine {
+    states: [StateId; 3],
+}
+
+impl PointOnLine {
+    /// Keep `point` on the (infinite) line through `a` and `b`
+    pub fn new(point: StateId, a: StateId, b: StateId) -> Self {
+        Self {
+            states: [point, a, b],
+        }
+    }
+
+    /// Offset from the closest point of the line to the point, and the
+    /// line parameter of that closest point
+    fn offset(points: &[(f64, f64, f64)]) -> ((f64, f64, f64), f64) {
+        let along = sub(points[2], points[1]);
+        let length_squared = dot(along, along);
+        let relative = sub(points[0], points[1]);
+        if length_squared < 1e-24 {
+            // Degenerate line: treat it as the point a
+            return (relative, 0.0);
+        }
+        let t = dot(relative, along) / length_squared;
+        (sub(relative, scale(along, t)), t)
+    }
+}
+
+impl Constraint for PointOnLine {
+    fn states(&self) -> &[StateId] {
+        &self.states
+    }
+
+    fn residual(&self, points: &[(f64, f64, f64)]) -> f64 {
+        let (offset, _) = Self::offset(points);
+        dot(offset, offset).sqrt()
+    }
+
+    fn gradient(&self, points: &[(f64, f64, f64)]) -> Vec<(f64, f64, f64)> {
+        let (offset, t) = Self::offset(points);
+        let n = direction(offset);
+        vec![n, scale(n, t - 1.0), scale(n, -t)]
+    }
+
+    fn description(&self) -> &str {
+        "Point on line constraint"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn distance(a: &GeometricState, b: &GeometricState) -> f64 {
+        let d = sub(a.as_vector(), b.as_vector());
+        dot(d, d).sqrt()
+    }
+
+    #[test]
+    fn test_distance_both_methods() {
+        for method in [SolverMethod::GaussSeidel, SolverMethod::Newton] {
+            let a = GeometricState::from_vector(0.0, 0.0, 0.0);
+            let b = GeometricState::from_vector(1.0, 1.0, 0.0);
+
+            let mut solver = ConstraintSolver::new().with_method(method);
+            let (ia, ib) = (solver.add_state(&a), solver.add_state(&b));
+            assert!(solver.add_constraint(Distance::new(ia, ib, 4.0)));
+
+            let report = solver.solve();
+            assert!(report.converged, "{:?}: {:?}", method, report);
+            assert!((distance(&a, &b) - 4.0).abs() < 1e-9);
+
+            // Both free with equal weight: the midpoint stays put
+            let (ax, ay, _) = a.as_vector();
+            let (bx, by, _) = b.as_vector();
+            assert!(((ax + bx) / 2.0 - 0.5).abs() < 1e-9);
+            assert!(((ay + by) / 2.0 - 0.5).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_fixed_states_do_not_move() {
+        let anchor = GeometricState::from_vector(1.0, 2.0, 3.0);
+        let free = GeometricState::from_vector(1.0, 2.0, 5.0);
+
+        let mut solver = ConstraintSolver::new();
+        let a = solver.add_fixed_state(&anchor);
+        let f = solver.add_state(&free);
+        solver.add_constraint(Distance::new(a, f, 1.0));
+        assert!(solver.solve().converged);
+
+        assert_eq!(anchor.as_vector(), (1.0, 2.0, 3.0));
+        let (x, y, z) = free.as_vector();
+        assert!((x - 1.0).abs() < 1e-9 && (y - 2.0).abs() < 1e-9 && (z - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_equal_distances_and_line() {
+        for method in [SolverMethod::GaussSeidel, SolverMethod::Newton] {
+            let origin = GeometricState::from_vector(0.0, 0.0, 0.0);
+            let axis = GeometricState::from_vector(0.0, 1.0, 0.0);
+            let left = GeometricState::from_vector(-1.0, 0.0, 0.0);
+            let right = GeometricState::from_vector(1.0, 2.0, 0.0);
+            let pivot = GeometricState::from_vector(0.4, 1.0, 0.2);
+
+            let mut solver = ConstraintSolver::new().with_method(method);
+            let o = solver.add_fixed_state(&origin);
+            let ax = solver.add_fixed_state(&axis);
+            let l = solver.add_fixed_state(&left);
+            let r = solver.add_fixed_state(&right);
+            let p = solver.add_state(&pivot);
+
+            // The pivot slides on the Y axis to be equidistant from both ends
+            solver.add_constraint(PointOnLine::new(p, o, ax));
+            solver.add_constraint(EqualDistance::new(p, l, p, r));
+
+            let report = solver.solve();
+            assert!(report.converged, "{:?}: {:?}", method, report);
+            let (x, y, z) = pivot.as_vector();
+            assert!(x.abs() < 1e-6 && (y - 1.0).abs() < 1e-6 && z.abs() < 1e-6);
+            assert!((distance(&pivot, &left) - distance(&pivot, &right)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_movable_line_follows_point() {
+        let point = GeometricState::from_vector(0.0, 1.0, 0.0);
+        let a = GeometricState::from_vector(-1.0, 0.0, 0.0);
+        let b = GeometricState::from_vector(1.0, 0.0, 0.0);
+
+        let mut solver = ConstraintSolver::new();
+        let p = solver.add_fixed_state(&point);
+        let (ia, ib) = (solver.add_state(&a), solver.add_state(&b));
+        solver.add_constraint(PointOnLine::new(p, ia, ib));
+        assert!(solver.solve().converged);
+
+        // The line moved up to meet the fixed point
+        assert!((a.as_vector().1 - 1.0).abs() < 1e-9);
+        assert!((b.as_vector().1 - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_solve_notifies_moved_states_once() {
+        let a = GeometricState::from_vector(0.0, 0.0, 0.0);
+        let b = GeometricState::from_vector(2.0, 0.0, 0.0);
+        let unrelated = GeometricState::from_vector(5.0, 5.0, 5.0);
+
+        let counts: Vec<_> = (0..3).map(|_| Arc::new(AtomicUsize::new(0))).collect();
+        let _subs: Vec<_> = [&a, &b, &unrelated]
+            .iter()
+            .zip(&counts)
+            .map(|(state, count)| {
+                let count = count.clone();
+                state.subscribe(move |_| {
+                    count.fetch_add(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        let mut solver = ConstraintSolver::new();
+        let ia = solver.add_fixed_state(&a);
+        let ib = solver.add_state(&b);
+        solver.add_state(&unrelated);
+        solver.add_constraint(Distance::new(ia, ib, 1.0));
+        solver.solve();
+
+        let counts: Vec<_> = counts.iter().map(|c| c.load(Ordering::SeqCst)).collect();
+        assert_eq!(counts, vec![0, 1, 0]);
+
+        // Already satisfied: nothing moves, nothing is notified
+        let report = solver.solve();
+        assert_eq!(report.iterations, 0);
+        assert!(report.converged);
+    }
+
+    #[test]
+    fn test_custom_constraint_numeric_gradient() {
+        struct OnPlane {
+            states: [StateId; 1],
+        }
+
+        impl Constraint for OnPlane {
+            fn states(&self) -> &[StateId] {
+                &self.states
+            }
+
+            fn residual(&self, points: &[(f64, f64, f64)]) -> f64 {
+                points[0].0 + points[0].1 + points[0].2 - 1.0
+            }
+
+            fn description(&self) -> &str {
+                "x + y + z = 1"
+            }
+        }
+
+        let state = GeometricState::from_vector(1.0, 1.0, 1.0);
+        let mut solver = ConstraintSolver::new().with_tolerance(1e-8);
+        let id = solver.add_state(&state);
+        solver.add_constraint(OnPlane { states: [id] });
+        assert!(solver.solve().converged);
+
+        // Projected along the plane normal
+        let (x, y, z) = state.as_vector();
+        for c in [x, y, z] {
+            assert!((c - 1.0 / 3.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_conflicting_constraints_report_failure() {
+        let a = GeometricState::from_vector(0.0, 0.0, 0.0);
+        let b = GeometricState::from_vector(1.0, 0.0, 0.0);
+
+        let mut solver = ConstraintSolver::new().with_max_iterations(20);
+        let (ia, ib) = (solver.add_state(&a), solver.add_state(&b));
+        solver.add_constraint(Distance::new(ia, ib, 1.0));
+        solver.add_constraint(Distance::new(ia, ib, 3.0));
+
+        let report = solver.solve();
+        assert!(!report.converged);
+        assert_eq!(report.iterations, 20);
+        assert!(!solver.add_constraint(Distance::new(ia, 7, 1.0)));
+    }
+
+    #[test]
+    fn test_pga_states() {
+        let a = GeometricState::<3, 0, 1>::from_point(0.0, 0.0, 0.0);
+        let b = GeometricState::<3, 0, 1>::from_point(0.0, 0.0, 2.0);
+
+        let mut solver = ConstraintSolver::new().with_method(SolverMethod::Newton);
+        let ia = solver.add_fixed_state(&a);
+        let ib = solver.add_state(&b);
+        solver.add_constraint(Distance::new(ia, ib, 5.0));
+        assert!(solver.solve().converged);
+
+        let (x, y, z) = b.as_point();
+        assert!(x.abs() < 1e-9 && y.abs() < 1e-9 && (z - 5.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,357 @@
+//! Continuous - Time-varying behaviors driven by a tick event
+//!
+//! `Behavior a = Time -> a` is approximated by sampling time on a tick
+//! event. Each tick carries `dt`, the seconds elapsed since the previous
+//! tick, so results depend on elapsed time rather than on the frame rate.
+//!
+//! In the browser the ticks usually come from `requestAnimationFrame`:
+//!
+//! ```text
+//! let last = performance.now();
+//! requestAnimationFrame(function frame(t) {
+//!     ticks.emit((t - last) / 1000); last = t;
+//!     requestAnimationFrame(frame);
+//! })
+//! ```
+//!
+//! Values are integrated, differentiated and interpolated in their GA3
+//! representation, so the same operations work for scalars, vectors and
+//! rotors. Animations between two rotors follow the shortest arc
+//! (`GeometricState::slerp`) instead of cutting through the sphere.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::{behavior, event, Behavior};
+//!
+//! let ticks = event::<f64>();
+//! let velocity = behavior(2.0f64);
+//! let position = velocity.integral(&ticks);
+//! let time = Behavior::time(&ticks);
+//!
+//! ticks.emit(0.5);
+//! ticks.emit(0.5);
+//! assert_eq!(time.sample(), 1.0);
+//! assert_eq!(position.sample(), 2.0);
+//! ```
+
+use crate::behavior::Behavior;
+use crate::event::Event;
+use crate::geometric::{FromGeometric, IntoGeometric, GA3};
+use crate::state::GeometricState;
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+
+/// Tolerance used when deciding whether a multivector is a unit rotor
+const ROTOR_EPSILON: f64 = 1e-9;
+
+/// How an animation progresses from start to end
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Easing {
+    /// Constant speed
+    #[default]
+    Linear,
+    /// Quadratic: starts slow
+    EaseIn,
+    /// Quadratic: ends slow
+    EaseOut,
+    /// Quadratic: starts and ends slow
+    EaseInOut,
+    /// Cubic Hermite smoothstep
+    SmoothStep,
+    /// A custom curve mapping progress in `[0, 1]` to `[0, 1]`
+    Custom(fn(f64) -> f64),
+}
+
+impl Easing {
+    /// Map linear progress `t` in `[0, 1]` to eased progress
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+            Easing::SmoothStep => t * t * (3.0 - 2.0 * t),
+            Easing::Custom(f) => f(t),
+        }
+    }
+}
+
+/// Check whether `mv` is a unit even multivector (a rotor, possibly the identity)
+fn is_unit_rotor(mv: &GA3) -> bool {
+    let odd: f64 = [1, 2, 4, 7].iter().map(|&i| mv.get(i).abs()).sum();
+    odd < ROTOR_EPSILON && (mv.magnitude() - 1.0).abs() < ROTOR_EPSILON
+}
+
+/// Check whether `mv` has a rotation plane (a non-zero bivector part)
+fn has_bivector(mv: &GA3) -> bool {
+    [3, 5, 6].iter().any(|&i| mv.get(i).abs() > ROTOR_EPSILON)
+}
+
+/// Interpolate between two multivectors
+///
+/// Two unit rotors, at least one of which actually rotates, use spherical
+/// interpolation; everything else (including plain numbers such as 1.0 and
+/// -1.0) is interpolated linearly.
+pub fn interpolate(from: &GA3, to: &GA3, t: f64) -> GA3 {
+    let (a, b) = (
+        GeometricState::new(from.clone()),
+        GeometricState::new(to.clone()),
+    );
+    let rotors =
+        is_unit_rotor(from) && is_unit_rotor(to) && (has_bivector(from) || has_bivector(to));
+
+    if rotors {
+        a.slerp(&b, t).multivector()
+    } else {
+        a.lerp(&b, t).multivector()
+    }
+}
+
+impl<T: IntoGeometric + FromGeometric + Clone + 'static> Behavior<T> {
+    /// Integrate this behavior over time
+    ///
+    /// On every tick the current value, scaled by `dt`, is added to the
+    /// running total (rectangle rule). The total starts at zero and is
+    /// accumulated in GA3, so no precision is lost for integer types.
+    pub fn integral(&self, dt: &Event<f64>) -> Behavior<T> {
+        let integral = Behavior::new(T::from_geometric(&GA3::zero()));
+
+        let integral_clone = integral.clone();
+        let source = self.clone();
+        dt.subscribe(move |dt| {
+            let step = &source.geometric_state() * *dt;
+            integral_clone.apply_geometric(|total| total + &step);
+        });
+
+        integral
+    }
+
+    /// Rate of change of this behavior, in units per second
+    ///
+    /// On every tick the change since the previous tick is divided by `dt`.
+    /// Ticks with a non-positive `dt` are ignored. Starts at zero.
+    pub fn derivative(&self, dt: &Event<f64>) -> Behavior<T> {
+        let derivative = Behavior::new(T::from_geometric(&GA3::zero()));
+        let previous = RefCell::new(self.geometric_state());
+
+        let derivative_clone = derivative.clone();
+        let source = self.clone();
+        dt.subscribe(move |dt| {
+            if *dt <= 0.0 {
+                return;
+            }
+            let current = source.geometric_state();
+            let rate = &(&current - &*previous.borrow()) * (1.0 / *dt);
+            *previous.borrow_mut() = current;
+            derivative_clone.apply_geometric(|_| rate);
+        });
+
+        derivative
+    }
+
+    /// Animate from `from` to `to` over `duration`
+    ///
+    /// Progress advances by `dt` on every tick and is shaped by `easing`.
+    /// Rotors are interpolated spherically, other values linearly (see
+    /// [`interpolate`]). The behavior holds `to` once the animation ends.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cliffy_core::{event, Behavior, Easing};
+    /// use std::time::Duration;
+    ///
+    /// let ticks = event::<f64>();
+    /// let opacity = Behavior::animate(0.0f64, 1.0, Easing::Linear, Duration::from_secs(2), &ticks);
+    ///
+    /// ticks.emit(0.5);
+    /// assert_eq!(opacity.sample(), 0.25);
+    /// ticks.emit(5.0);
+    /// assert_eq!(opacity.sample(), 1.0);
+    /// ```
+    pub fn animate(
+        from: T,
+        to: T,
+        easing: Easing,
+        duration: Duration,
+        dt: &Event<f64>,
+    ) -> Behavior<T> {
+        let from_mv = from.clone().into_geometric();
+        let to_mv = to.clone().into_geometric();
+        let animated = Behavior::new(from);
+
+        let elapsed = Cell::new(0.0);
+        let finished = Cell::new(false);
+        let duration = duration.as_secs_f64();
+
+        let animated_clone = animated.clone();
+        dt.subscribe(move |dt| {
+            if finished.get() {
+                return;
+            }
+            elapsed.set(elapsed.get() + dt.max(0.0));
+
+            let progress = if duration > 0.0 {
+                (elapsed.get() / duration).min(1.0)
+            } else {
+                1.0
+            };
+            if progress >= 1.0 {
+                finished.set(true);
+                animated_clone.set(to.clone());
+            } else {
+                let mv = interpolate(&from_mv, &to_mv, easing.apply(progress));
+                animated_clone.apply_geometric(|_| mv);
+            }
+        });
+
+        animated
+    }
+}
+
+impl Behavior<f64> {
+    /// Seconds elapsed, accumulated from the `dt` carried by each tick
+    pub fn time(dt: &Event<f64>) -> Behavior<f64> {
+        dt.fold(0.0, |time, dt| time + dt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::behavior::behavior;
+    use crate::event::event;
+    use crate::transforms::Rotor;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_easing_endpoints() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::SmoothStep,
+        ] {
+            assert!(easing.apply(0.0).abs() < 1e-12);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-12);
+        }
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+    }
+
+    #[test]
+    fn test_time_accumulates_dt() {
+        let ticks = event::<f64>();
+        let time = Behavior::time(&ticks);
+
+        ticks.emit(0.25);
+        ticks.emit(0.5);
+        assert!((time.sample() - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_integral_is_frame_rate_independent() {
+        let fast = event::<f64>();
+        let slow = event::<f64>();
+        let velocity = behavior(3.0f64);
+        let fast_position = velocity.integral(&fast);
+        let slow_position = velocity.integral(&slow);
+
+        for _ in 0..60 {
+            fast.emit(1.0 / 60.0);
+        }
+        for _ in 0..10 {
+            slow.emit(0.1);
+        }
+
+        assert!((fast_position.sample() - 3.0).abs() < 1e-9);
+        assert!((slow_position.sample() - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_integral_of_vector() {
+        let ticks = event::<f64>();
+        let velocity = behavior(GeometricState::from_vector(1.0, 2.0, 0.0).multivector());
+        let position = velocity.integral(&ticks);
+
+        ticks.emit(2.0);
+        let p = GeometricState::new(position.sample());
+        assert_eq!(p.as_vector(), (2.0, 4.0, 0.0));
+    }
+
+    #[test]
+    fn test_derivative() {
+        let ticks = event::<f64>();
+        let position = behavior(0.0f64);
+        let velocity = position.derivative(&ticks);
+
+        position.set(1.0);
+        ticks.emit(0.5);
+        assert!((velocity.sample() - 2.0).abs() < 1e-12);
+
+        ticks.emit(0.5);
+        assert!(velocity.sample().abs() < 1e-12);
+
+        ticks.emit(0.0);
+        assert!(velocity.sample().abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_animate_scalar_with_easing() {
+        let ticks = event::<f64>();
+        let value = Behavior::animate(0.0f64, 10.0, Easing::EaseIn, Duration::from_secs(1), &ticks);
+
+        assert_eq!(value.sample(), 0.0);
+        ticks.emit(0.5);
+        assert!((value.sample() - 2.5).abs() < 1e-12);
+        ticks.emit(0.5);
+        assert_eq!(value.sample(), 10.0);
+    }
+
+    #[test]
+    fn test_animate_stops_notifying_when_finished() {
+        let ticks = event::<f64>();
+        let value = Behavior::animate(0.0f64, 1.0, Easing::Linear, Duration::from_secs(1), &ticks);
+        let updates = std::rc::Rc::new(Cell::new(0));
+
+        let updates_clone = std::rc::Rc::clone(&updates);
+        let _sub = value.subscribe(move |_| updates_clone.set(updates_clone.get() + 1));
+
+        ticks.emit(2.0);
+        ticks.emit(1.0);
+        assert_eq!(updates.get(), 1);
+    }
+
+    #[test]
+    fn test_animate_rotor_slerps() {
+        let ticks = event::<f64>();
+        let start = Rotor::identity().as_multivector().clone();
+        let end = Rotor::xy(PI).as_multivector().clone();
+        let rotation =
+            Behavior::animate(start, end, Easing::Linear, Duration::from_secs(2), &ticks);
+
+        ticks.emit(1.0);
+        let halfway = rotation.sample();
+        let expected = Rotor::xy(PI / 2.0);
+
+        assert!((halfway.magnitude() - 1.0).abs() < 1e-9);
+        for i in 0..8 {
+            assert!((halfway.get(i) - expected.as_multivector().get(i)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_interpolate_scalars_linearly() {
+        let mid = interpolate(&GA3::scalar(1.0), &GA3::scalar(-1.0), 0.5);
+        assert!(mid.get(0).abs() < 1e-12);
+    }
+}
@@ -40,15 +40,37 @@
 //! let sink_id = graph.add_node(Node::sink("span_text", "textContent"));
 //! graph.connect(proj_id, sink_id);
 //! ```
+//!
+//! The graph itself is pure data; [`runtime::DataflowRuntime`] executes it
+//! against bound `GeometricState`s, [`format`] reads and writes it in a
+//! versioned serde interchange format, [`optimize`] simplifies it, and
+//! [`visualize`] renders it as DOT or Mermaid. [`record::GraphRecorder`]
+//! builds a graph from live `Behavior` wiring.
 
+use crate::transforms::{Rotor, Transform, Translation};
 use crate::GA3;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 
+pub mod format;
+pub mod optimize;
+pub mod record;
+pub mod runtime;
+pub mod visualize;
+
+pub use format::{FormatError, GraphDocument, GraphLoader, FORMAT_VERSION};
+pub use optimize::{Optimization, OptimizationReport, Optimizer};
+pub use record::GraphRecorder;
+pub use runtime::{DataflowError, DataflowRuntime, DataflowValue};
+
 /// A unique identifier for a node in the dataflow graph.
 pub type NodeId = usize;
 
 /// A dataflow graph representing the transformation pipeline.
-#[derive(Debug, Clone)]
+///
+/// Serializes as a [`GraphDocument`] and is validated when deserialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "GraphDocument", try_from = "GraphDocument")]
 pub struct DataflowGraph {
     /// All nodes in the graph
     nodes: Vec<Node>,
@@ -108,6 +130,24 @@ impl DataflowGraph {
         self.nodes.get_mut(id)
     }
 
+    /// Rename a node, keeping name lookups in sync.
+    ///
+    /// Returns false if the node doesn't exist.
+    pub fn rename(&mut self, id: NodeId, name: impl Into<String>) -> bool {
+        let Some(node) = self.nodes.get_mut(id) else {
+            return false;
+        };
+        if let Some(old) = node.name.take() {
+            if self.node_names.get(&old) == Some(&id) {
+                self.node_names.remove(&old);
+            }
+        }
+        let name = name.into();
+        self.node_names.insert(name.clone(), id);
+        node.name = Some(name);
+        true
+    }
+
     /// Get a node by name.
     pub fn get_node_by_name(&self, name: &str) -> Option<&Node> {
         self.node_names.get(name).and_then(|id| self.nodes.get(*id))
@@ -231,13 +271,19 @@ impl Default for DataflowGraph {
 }
 
 /// A node in the dataflow graph.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Node {
     /// Node name (for debugging and lookup)
+    #[serde(default)]
     pub name: Option<String>,
     /// The kind of computation this node performs
     pub kind: NodeKind,
     /// Optional constant value (for constant folding)
+    #[serde(
+        default,
+        with = "format::ga3_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub constant_value: Option<GA3>,
 }
 
@@ -330,7 +376,7 @@ impl Node {
 }
 
 /// The kind of computation a node performs.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NodeKind {
     /// Input source (geometric state)
     Source,
@@ -349,14 +395,14 @@ pub enum NodeKind {
 }
 
 /// Specification for a projection node.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProjectionSpec {
     /// Type of projection (e.g., "scalar", "vector", "magnitude")
     pub projection_type: String,
 }
 
 /// Type of geometric transformation.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TransformType {
     /// Translation by a vector
     Translation { x: f64, y: f64, z: f64 },
@@ -366,14 +412,70 @@ pub enum TransformType {
     Scale { factor: f64 },
     /// Arbitrary rotor transformation
     Rotor { coefficients: [f64; 8] },
+    /// Rotor followed by a translation (a rigid motion)
+    Transform {
+        rotor: [f64; 8],
+        translation: [f64; 3],
+    },
     /// Linear interpolation
     Lerp { t: f64 },
     /// Custom named transform
     Custom { name: String },
 }
 
+impl TransformType {
+    /// The rigid motion this transform performs, if it is one
+    ///
+    /// Translations, rotations, rotors and transforms are rigid; scale,
+    /// lerp and custom transforms are not.
+    pub fn to_transform(&self) -> Option<Transform> {
+        let rotor = |coefficients: &[f64; 8]| {
+            Rotor::from_multivector(GA3::from_coefficients(coefficients.to_vec()))
+        };
+        Some(match self {
+            TransformType::Translation { x, y, z } => {
+                Transform::translation(Translation::new(*x, *y, *z))
+            }
+            TransformType::Rotation { angle, plane } => Transform::rotation(match plane {
+                RotationPlane::XY => Rotor::xy(*angle),
+                RotationPlane::YZ => Rotor::yz(*angle),
+                // z towards x is the XZ rotation run backwards
+                RotationPlane::ZX => Rotor::xz(-*angle),
+            }),
+            TransformType::Rotor { coefficients } => Transform::rotation(rotor(coefficients)),
+            TransformType::Transform {
+                rotor: coefficients,
+                translation: [x, y, z],
+            } => Transform::new(rotor(coefficients), Translation::new(*x, *y, *z)),
+            TransformType::Scale { .. }
+            | TransformType::Lerp { .. }
+            | TransformType::Custom { .. } => return None,
+        })
+    }
+
+    /// The simplest transform type performing `transform`: a translation
+    /// when there is no rotation, a rotor when there is no translation
+    pub fn from_transform(transform: &Transform) -> Self {
+        let (rotor, translation) = transform.parts();
+        let mv = rotor.as_multivector();
+        let coefficients: [f64; 8] = std::array::from_fn(|i| mv.get(i));
+        let (x, y, z) = translation.components();
+        let identity = coefficients == [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        if identity {
+            TransformType::Translation { x, y, z }
+        } else if (x, y, z) == (0.0, 0.0, 0.0) {
+            TransformType::Rotor { coefficients }
+        } else {
+            TransformType::Transform {
+                rotor: coefficients,
+                translation: [x, y, z],
+            }
+        }
+    }
+}
+
 /// Plane of rotation in 3D.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RotationPlane {
     XY,
     YZ,
@@ -381,14 +483,14 @@ pub enum RotationPlane {
 }
 
 /// Specification for a sink node.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SinkSpec {
     /// DOM property to update (e.g., "textContent", "style.color")
     pub target_property: String,
 }
 
 /// Type of combiner for multiple inputs.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CombinerType {
     /// Add all inputs
     Sum,
@@ -549,6 +651,17 @@ mod tests {
         assert!(node.is_source());
     }
 
+    #[test]
+    fn test_rename() {
+        let mut graph = DataflowGraph::new();
+        let id = graph.add_node(Node::source("old"));
+
+        assert!(graph.rename(id, "new"));
+        assert_eq!(graph.get_id_by_name("new"), Some(id));
+        assert_eq!(graph.get_id_by_name("old"), None);
+        assert!(!graph.rename(7, "missing"));
+    }
+
     #[test]
     fn test_reachable_from() {
         let mut graph = DataflowGraph::new();
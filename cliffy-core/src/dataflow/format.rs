@@ -0,0 +1,414 @@
+//! Versioned interchange format for dataflow graphs
+//!
+//! A graph travels as a `GraphDocument`: a format version, the nodes in id
+//! order and the edges. The document is plain serde data, so it can be
+//! written with any serde format: JSON for editors and debugging, a binary
+//! format such as CBOR or bincode for shipping to clients.
+//!
+//! Loading goes through a `GraphLoader`, which upgrades documents from
+//! older versions with registered migrations and validates the result:
+//! no cycles, no edges to missing nodes, no duplicate names and no
+//! projection types the runtime would not understand. `DataflowGraph`
+//! implements `Serialize` and `Deserialize` directly through the default
+//! loader, which only knows the built-in projections.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::dataflow::format::{GraphDocument, GraphLoader};
+//! use cliffy_core::dataflow::GraphBuilder;
+//!
+//! let graph = GraphBuilder::new()
+//!     .source("count")
+//!     .project("badge", "badge")
+//!     .sink("out", "className")
+//!     .build();
+//!
+//! // What an editor would send, in whichever serde format it uses
+//! let document = graph.to_document();
+//!
+//! let loader = GraphLoader::new().with_projection("badge");
+//! let loaded = loader.load(document).unwrap();
+//! assert_eq!(loaded.node_count(), 3);
+//!
+//! // The default loader doesn't know the custom projection
+//! assert!(GraphLoader::new().load(graph.to_document()).is_err());
+//! ```
+
+use super::runtime::BUILTIN_PROJECTIONS;
+use super::{DataflowGraph, Node, NodeId, NodeKind};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+/// The version written by `to_document`
+pub const FORMAT_VERSION: u32 = 1;
+
+/// A dataflow graph in interchange form
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GraphDocument {
+    /// Format version the document was written with
+    pub version: u32,
+    /// Nodes, where a node's id is its position
+    pub nodes: Vec<Node>,
+    /// Edges as (from, to), in the order each node's inputs are connected
+    pub edges: Vec<(NodeId, NodeId)>,
+}
+
+/// Errors from loading a `GraphDocument`
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormatError {
+    /// The document is newer than this version of the format
+    UnsupportedVersion { version: u32, supported: u32 },
+    /// No migration is registered from this version
+    MissingMigration(u32),
+    /// An edge refers to a node that doesn't exist
+    DanglingEdge { from: NodeId, to: NodeId },
+    /// The edges form a cycle
+    Cycle,
+    /// Two nodes share a name
+    DuplicateName(String),
+    /// A projection node names a projection the loader doesn't know
+    UnknownProjection {
+        node: NodeId,
+        projection_type: String,
+    },
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::UnsupportedVersion { version, supported } => write!(
+                f,
+                "graph format version {} is newer than supported version {}",
+                version, supported
+            ),
+            FormatError::MissingMigration(version) => {
+                write!(f, "no migration from graph format version {}", version)
+            }
+            FormatError::DanglingEdge { from, to } => {
+                write!(f, "edge {} -> {} refers to a missing node", from, to)
+            }
+            FormatError::Cycle => write!(f, "dataflow graph has a cycle"),
+            FormatError::DuplicateName(name) => write!(f, "duplicate node name '{}'", name),
+            FormatError::UnknownProjection {
+                node,
+                projection_type,
+            } => write!(
+                f,
+                "node {} uses unknown projection '{}'",
+                node, projection_type
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+/// Type alias for migration functions to avoid clippy::type_complexity warning
+type Migration = Box<dyn Fn(&mut GraphDocument) + Send + Sync>;
+
+/// Validates documents and turns them into graphs
+pub struct GraphLoader {
+    projections: HashSet<String>,
+    migrations: BTreeMap<u32, Migration>,
+}
+
+impl GraphLoader {
+    /// A loader that knows the built-in projections and has no migrations
+    pub fn new() -> Self {
+        Self {
+            projections: BUILTIN_PROJECTIONS.iter().map(|p| p.to_string()).collect(),
+            migrations: BTreeMap::new(),
+        }
+    }
+
+    /// Accept projection nodes of type `name`, for projections registered
+    /// with `DataflowRuntime::with_projection`
+    pub fn with_projection(mut self, name: impl Into<String>) -> Self {
+        self.projections.insert(name.into());
+        self
+    }
+
+    /// Register the migration from `version` to `version + 1`
+    ///
+    /// Documents older than `FORMAT_VERSION` are passed through each
+    /// migration in turn; the loader updates `version` after each step.
+    pub fn with_migration<F>(mut self, version: u32, migration: F) -> Self
+    where
+        F: Fn(&mut GraphDocument) + Send + Sync + 'static,
+    {
+        self.migrations.insert(version, Box::new(migration));
+        self
+    }
+
+    /// Migrate a document to the current version without validating it
+    pub fn migrate(&self, mut document: GraphDocument) -> Result<GraphDocument, FormatError> {
+        if document.version > FORMAT_VERSION {
+            return Err(FormatError::UnsupportedVersion {
+                version: document.version,
+                supported: FORMAT_VERSION,
+            });
+        }
+        while document.version < FORMAT_VERSION {
+            let migration = self
+                .migrations
+                .get(&document.version)
+                .ok_or(FormatError::MissingMigration(document.version))?;
+            migration(&mut document);
+            document.version += 1;
+        }
+        Ok(document)
+    }
+
+    /// Migrate, validate and build the graph
+    pub fn load(&self, document: GraphDocument) -> Result<DataflowGraph, FormatError> {
+        let document = self.migrate(document)?;
+
+        let mut names = HashSet::new();
+        for (id, node) in document.nodes.iter().enumerate() {
+            if let Some(name) = &node.name {
+                if !names.insert(name) {
+                    return Err(FormatError::DuplicateName(name.clone()));
+                }
+            }
+            if let NodeKind::Projection(spec) = &node.kind {
+                if !self.projections.contains(&spec.projection_type) {
+                    return Err(FormatError::UnknownProjection {
+                        node: id,
+                        projection_type: spec.projection_type.clone(),
+                    });
+                }
+            }
+        }
+
+        let mut graph = DataflowGraph::new();
+        for node in document.nodes {
+            graph.add_node(node);
+        }
+        for (from, to) in document.edges {
+            if !graph.connect(from, to) {
+                return Err(FormatError::DanglingEdge { from, to });
+            }
+        }
+        if graph.has_cycles() {
+            return Err(FormatError::Cycle);
+        }
+        Ok(graph)
+    }
+}
+
+impl Default for GraphLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataflowGraph {
+    /// This graph as a document at the current format version
+    pub fn to_document(&self) -> GraphDocument {
+        // Edges are listed by target so that reconnecting them in order
+        // restores each node's input order, which lerp and conditional
+        // nodes depend on
+        let edges = self
+            .nodes()
+            .flat_map(|(to, _)| self.incoming(to).iter().map(move |&from| (from, to)))
+            .collect();
+        GraphDocument {
+            version: FORMAT_VERSION,
+            nodes: self.nodes().map(|(_, node)| node.clone()).collect(),
+            edges,
+        }
+    }
+}
+
+impl From<DataflowGraph> for GraphDocument {
+    fn from(graph: DataflowGraph) -> Self {
+        graph.to_document()
+    }
+}
+
+impl TryFrom<GraphDocument> for DataflowGraph {
+    type Error = FormatError;
+
+    fn try_from(document: GraphDocument) -> Result<Self, FormatError> {
+        GraphLoader::new().load(document)
+    }
+}
+
+/// Serde helpers for `Option<GA3>` as a list of coefficients
+///
+/// A list of the wrong length is a deserialization error, not a panic.
+pub(crate) mod ga3_option {
+    use crate::geometric::GA3;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(mv: &Option<GA3>, serializer: S) -> Result<S::Ok, S::Error> {
+        mv.as_ref().map(|mv| mv.as_slice()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<GA3>, D::Error> {
+        let Some(coeffs) = Option::<Vec<f64>>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+        if coeffs.len() != 8 {
+            return Err(D::Error::invalid_length(
+                coeffs.len(),
+                &"8 GA3 coefficients",
+            ));
+        }
+        Ok(Some(GA3::from_slice(&coeffs)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataflow::{CombinerType, GraphBuilder, RotationPlane, TransformType};
+    use crate::GA3;
+
+    fn sample_graph() -> DataflowGraph {
+        let mut graph = GraphBuilder::new()
+            .source("position")
+            .transform(
+                "turn",
+                TransformType::Rotation {
+                    angle: 0.5,
+                    plane: RotationPlane::ZX,
+                },
+            )
+            .project("xyz", "vector")
+            .sink("label", "textContent")
+            .build();
+        let target = graph.add_node(Node::constant("target", GA3::scalar(2.0)));
+        let position = graph.get_id_by_name("position").unwrap();
+        // Inputs connected target-first, the reverse of node order
+        let lerp = graph.add_node(Node::transform("blend", TransformType::Lerp { t: 0.5 }));
+        graph.connect(target, lerp);
+        graph.connect(position, lerp);
+        let sum = graph.add_node(Node::combine("sum", CombinerType::Custom("clamp".into())));
+        graph.connect(lerp, sum);
+        graph
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let graph = sample_graph();
+        let json = serde_json::to_string(&graph).unwrap();
+        let loaded: DataflowGraph = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.to_document(), graph.to_document());
+        let blend = loaded.get_id_by_name("blend").unwrap();
+        assert_eq!(
+            loaded.incoming(blend),
+            &[
+                loaded.get_id_by_name("target").unwrap(),
+                loaded.get_id_by_name("position").unwrap()
+            ]
+        );
+        assert!(json.contains("\"version\":1"));
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let document = sample_graph().to_document();
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&document, &mut bytes).unwrap();
+        let decoded: GraphDocument = ciborium::from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, document);
+    }
+
+    #[test]
+    fn test_validation() {
+        let document = sample_graph().to_document();
+
+        let mut dangling = document.clone();
+        dangling.edges.push((0, 42));
+        assert_eq!(
+            GraphLoader::new().load(dangling).err(),
+            Some(FormatError::DanglingEdge { from: 0, to: 42 })
+        );
+
+        let mut cyclic = document.clone();
+        cyclic.edges.push((3, 0));
+        assert_eq!(
+            GraphLoader::new().load(cyclic).err(),
+            Some(FormatError::Cycle)
+        );
+
+        let mut duplicate = document.clone();
+        duplicate.nodes[1].name = Some("position".into());
+        assert_eq!(
+            GraphLoader::new().load(duplicate).err(),
+            Some(FormatError::DuplicateName("position".into()))
+        );
+
+        let mut unknown = document;
+        unknown.nodes[2] = Node::projection("xyz", "hologram");
+        assert_eq!(
+            GraphLoader::new().load(unknown.clone()).err(),
+            Some(FormatError::UnknownProjection {
+                node: 2,
+                projection_type: "hologram".into()
+            })
+        );
+        assert!(GraphLoader::new()
+            .with_projection("hologram")
+            .load(unknown)
+            .is_ok());
+
+        // Deserializing a graph directly validates too
+        let json = r#"{"version":1,"nodes":[],"edges":[[0,1]]}"#;
+        assert!(serde_json::from_str::<DataflowGraph>(json).is_err());
+    }
+
+    #[test]
+    fn test_bad_constant_is_an_error() {
+        let json = serde_json::to_string(&sample_graph()).unwrap();
+        let full = "[2.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0]";
+        assert!(json.contains(full));
+
+        for coeffs in ["[2.0,0.0]", "[2.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0,0.0]"] {
+            let bad = json.replace(full, coeffs);
+            let error = serde_json::from_str::<DataflowGraph>(&bad).unwrap_err();
+            assert!(error.to_string().contains("invalid length"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_versions_and_migrations() {
+        let mut newer = sample_graph().to_document();
+        newer.version = FORMAT_VERSION + 1;
+        assert_eq!(
+            GraphLoader::new().load(newer).err(),
+            Some(FormatError::UnsupportedVersion {
+                version: FORMAT_VERSION + 1,
+                supported: FORMAT_VERSION
+            })
+        );
+
+        // A hypothetical version 0 that called the vector projection "vec3"
+        let mut old = sample_graph().to_document();
+        old.version = 0;
+        old.nodes[2] = Node::projection("xyz", "vec3");
+        assert_eq!(
+            GraphLoader::new().load(old.clone()).err(),
+            Some(FormatError::MissingMigration(0))
+        );
+
+        let loader = GraphLoader::new().with_migration(0, |document| {
+            for node in &mut document.nodes {
+                if let NodeKind::Projection(spec) = &mut node.kind {
+                    if spec.projection_type == "vec3" {
+                        spec.projection_type = "vector".into();
+                    }
+                }
+            }
+        });
+        let graph = loader.load(old).unwrap();
+        assert_eq!(graph.to_document(), sample_graph().to_document());
+    }
+}
@@ -0,0 +1,617 @@
+//! Optimisation passes for dataflow graphs
+//!
+//! Graphs put together with `GraphBuilder` or an editor tend to contain
+//! chains of small transforms and repeated projections. `Optimizer` runs
+//! these passes, each of which can be switched off:
+//!
+//! 1. **Constant folding**: a node whose inputs are all constants is
+//!    evaluated once and replaced by a `Constant`
+//! 2. **Deduplication**: nodes with the same kind and the same inputs are
+//!    merged, so identical subgraphs are computed once
+//! 3. **Transform fusion**: a rigid transform feeding only another rigid
+//!    transform is folded into it with `Transform::then`, and consecutive
+//!    scales multiply
+//! 4. **Dead node elimination**: nodes that reach no sink node are removed
+//!    (sources are kept, so bindings by name stay valid)
+//!
+//! The result is a new graph with compacted ids, and an
+//! `OptimizationReport` listing each change in terms of the original ids.
+//! Kept nodes keep their names; a node that was fused, merged or removed
+//! can no longer be looked up by name.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::dataflow::{GraphBuilder, RotationPlane, TransformType};
+//!
+//! let graph = GraphBuilder::new()
+//!     .source("position")
+//!     .transform("turn", TransformType::Rotation { angle: 0.5, plane: RotationPlane::XY })
+//!     .transform("move", TransformType::Translation { x: 1.0, y: 0.0, z: 0.0 })
+//!     .project("xyz", "vector")
+//!     .sink("label", "textContent")
+//!     .build();
+//!
+//! let (optimized, report) = graph.optimize();
+//! assert_eq!(optimized.node_count(), 4);
+//! assert!(matches!(
+//!     optimized.get_node_by_name("move").unwrap().kind,
+//!     cliffy_core::dataflow::NodeKind::Transform(TransformType::Transform { .. })
+//! ));
+//! assert_eq!(report.nodes_removed(), 1);
+//! assert!(report.to_string().starts_with("fused transforms"));
+//! ```
+
+use super::runtime::combine_builtin;
+use super::{DataflowGraph, Node, NodeId, NodeKind, TransformType};
+use crate::projection::{BoolProjection, Projection};
+use crate::GA3;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// One change made by the optimiser, in terms of the input graph's ids
+#[derive(Clone, Debug, PartialEq)]
+pub enum Optimization {
+    /// A node with only constant inputs was replaced by its value
+    FoldedConstant { node: NodeId },
+    /// A node identical to an earlier one was merged into it
+    MergedDuplicate { node: NodeId, into: NodeId },
+    /// A chain of transforms was fused into its last node
+    FusedTransforms { nodes: Vec<NodeId>, into: NodeId },
+    /// A node that reaches no sink was removed
+    RemovedDeadNode { node: NodeId },
+}
+
+impl fmt::Display for Optimization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Optimization::FoldedConstant { node } => {
+                write!(f, "folded node {} to a constant", node)
+            }
+            Optimization::MergedDuplicate { node, into } => {
+                write!(f, "merged node {} into identical node {}", node, into)
+            }
+            Optimization::FusedTransforms { nodes, into } => {
+                write!(f, "fused transforms {:?} into node {}", nodes, into)
+            }
+            Optimization::RemovedDeadNode { node } => {
+                write!(f, "removed node {}, which reaches no sink", node)
+            }
+        }
+    }
+}
+
+/// What an optimisation run changed
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OptimizationReport {
+    /// The changes, in the order the passes made them
+    pub changes: Vec<Optimization>,
+    /// The new id of each node of the input graph, or None if it is gone
+    pub id_map: Vec<Option<NodeId>>,
+}
+
+impl OptimizationReport {
+    /// Whether the graph came out unchanged
+    pub fn is_unchanged(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Number of nodes the optimised graph has fewer than the input
+    pub fn nodes_removed(&self) -> usize {
+        self.id_map.iter().filter(|id| id.is_none()).count()
+    }
+}
+
+impl fmt::Display for OptimizationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "no changes");
+        }
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        writeln!(f, "{} node(s) removed", self.nodes_removed())
+    }
+}
+
+/// Runs optimisation passes over a `DataflowGraph`
+#[derive(Clone, Debug)]
+pub struct Optimizer {
+    fold_constants: bool,
+    deduplicate: bool,
+    fuse_transforms: bool,
+    eliminate_dead_nodes: bool,
+}
+
+impl Optimizer {
+    /// An optimiser with every pass enabled
+    pub fn new() -> Self {
+        Self {
+            fold_constants: true,
+            deduplicate: true,
+            fuse_transforms: true,
+            eliminate_dead_nodes: true,
+        }
+    }
+
+    /// Enable or disable constant folding
+    pub fn with_constant_folding(mut self, enabled: bool) -> Self {
+        self.fold_constants = enabled;
+        self
+    }
+
+    /// Enable or disable merging of identical nodes
+    pub fn with_deduplication(mut self, enabled: bool) -> Self {
+        self.deduplicate = enabled;
+        self
+    }
+
+    /// Enable or disable fusing of adjacent transforms
+    pub fn with_transform_fusion(mut self, enabled: bool) -> Self {
+        self.fuse_transforms = enabled;
+        self
+    }
+
+    /// Enable or disable removal of nodes that reach no sink
+    pub fn with_dead_node_elimination(mut self, enabled: bool) -> Self {
+        self.eliminate_dead_nodes = enabled;
+        self
+    }
+
+    /// Optimise `graph`, returning the new graph and what changed
+    ///
+    /// A graph with a cycle is returned unchanged.
+    pub fn optimize(&self, graph: &DataflowGraph) -> (DataflowGraph, OptimizationReport) {
+        let Some(order) = graph.topological_sort() else {
+            let report = OptimizationReport {
+                changes: Vec::new(),
+                id_map: (0..graph.node_count()).map(Some).collect(),
+            };
+            return (graph.clone(), report);
+        };
+
+        let mut work = Work {
+            nodes: graph.nodes().map(|(_, node)| Some(node.clone())).collect(),
+            inputs: graph
+                .nodes()
+                .map(|(id, _)| graph.incoming(id).to_vec())
+                .collect(),
+            order,
+            changes: Vec::new(),
+        };
+        if self.fold_constants {
+            work.fold_constants();
+        }
+        if self.deduplicate {
+            work.deduplicate();
+        }
+        if self.fuse_transforms {
+            work.fuse_transforms();
+        }
+        if self.eliminate_dead_nodes {
+            work.eliminate_dead_nodes();
+        }
+        work.finish()
+    }
+}
+
+impl Default for Optimizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataflowGraph {
+    /// Optimise with every pass enabled (see `Optimizer`)
+    pub fn optimize(&self) -> (DataflowGraph, OptimizationReport) {
+        Optimizer::new().optimize(self)
+    }
+}
+
+// ============================================================================
+// Passes
+// ============================================================================
+
+/// The graph being optimised: removed nodes are None, and each node's
+/// inputs are kept in order
+struct Work {
+    nodes: Vec<Option<Node>>,
+    inputs: Vec<Vec<NodeId>>,
+    order: Vec<NodeId>,
+    changes: Vec<Optimization>,
+}
+
+impl Work {
+    fn node(&self, id: NodeId) -> Option<&Node> {
+        self.nodes[id].as_ref()
+    }
+
+    fn consumers(&self, id: NodeId) -> Vec<NodeId> {
+        (0..self.nodes.len())
+            .filter(|&other| self.nodes[other].is_some() && self.inputs[other].contains(&id))
+            .collect()
+    }
+
+    fn constant(&self, id: NodeId) -> Option<GA3> {
+        let node = self.node(id)?;
+        match node.kind {
+            NodeKind::Constant => Some(node.constant_value.clone().unwrap_or_else(GA3::zero)),
+            _ => None,
+        }
+    }
+
+    fn fold_constants(&mut self) {
+        for index in 0..self.order.len() {
+            let id = self.order[index];
+            if self.inputs[id].is_empty() {
+                continue;
+            }
+            let inputs: Option<Vec<GA3>> =
+                self.inputs[id].iter().map(|&i| self.constant(i)).collect();
+            let Some(inputs) = inputs else {
+                continue;
+            };
+            let Some(value) = fold(&self.node(id).unwrap().kind, &inputs) else {
+                continue;
+            };
+
+            let node = self.nodes[id].as_mut().unwrap();
+            node.kind = NodeKind::Constant;
+            node.constant_value = Some(value);
+            self.inputs[id].clear();
+            self.changes.push(Optimization::FoldedConstant { node: id });
+        }
+    }
+
+    fn deduplicate(&mut self) {
+        let mut seen: HashMap<String, NodeId> = HashMap::new();
+        for index in 0..self.order.len() {
+            let id = self.order[index];
+            let Some(node) = self.node(id) else {
+                continue;
+            };
+            // Sources are distinct states and sinks distinct targets
+            if node.is_source() || node.is_sink() {
+                continue;
+            }
+            // Inputs are already canonical, since they come earlier in order
+            let key = format!(
+                "{:?}|{:?}|{:?}",
+                node.kind, node.constant_value, self.inputs[id]
+            );
+            match seen.get(&key) {
+                Some(&first) => {
+                    self.redirect(id, first);
+                    self.changes.push(Optimization::MergedDuplicate {
+                        node: id,
+                        into: first,
+                    });
+                }
+                None => {
+                    seen.insert(key, id);
+                }
+            }
+        }
+    }
+
+    fn fuse_transforms(&mut self) {
+        let mut absorbed: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        let mut fused_order = Vec::new();
+        for index in 0..self.order.len() {
+            let id = self.order[index];
+            let Some(NodeKind::Transform(second)) = self.node(id).map(|n| n.kind.clone()) else {
+                continue;
+            };
+            let [input] = self.inputs[id][..] else {
+                continue;
+            };
+            let Some(NodeKind::Transform(first)) = self.node(input).map(|n| n.kind.clone()) else {
+                continue;
+            };
+            if self.consumers(input) != [id] {
+                continue;
+            }
+            let Some(fused) = fuse(&first, &second) else {
+                continue;
+            };
+
+            self.nodes[id].as_mut().unwrap().kind = NodeKind::Transform(fused);
+            self.inputs[id] = std::mem::take(&mut self.inputs[input]);
+            self.nodes[input] = None;
+
+            let mut chain = absorbed.remove(&input).unwrap_or_default();
+            chain.push(input);
+            fused_order.retain(|&n| n != input);
+            fused_order.push(id);
+            absorbed.insert(id, chain);
+        }
+        for into in fused_order {
+            let nodes = absorbed.remove(&into).unwrap();
+            self.changes
+                .push(Optimization::FusedTransforms { nodes, into });
+        }
+    }
+
+    fn eliminate_dead_nodes(&mut self) {
+        let mut live = HashSet::new();
+        let mut stack: Vec<NodeId> = (0..self.nodes.len())
+            .filter(|&id| self.node(id).is_some_and(|n| n.is_sink()))
+            .collect();
+        while let Some(id) = stack.pop() {
+            if live.insert(id) {
+                stack.extend(self.inputs[id].iter().copied());
+            }
+        }
+
+        for id in 0..self.nodes.len() {
+            let dead = self
+                .node(id)
+                .is_some_and(|n| !n.is_source() && !live.contains(&id));
+            if dead {
+                self.nodes[id] = None;
+                self.inputs[id].clear();
+                self.changes
+                    .push(Optimization::RemovedDeadNode { node: id });
+            }
+        }
+    }
+
+    /// Point the consumers of `from` at `to` and remove `from`
+    fn redirect(&mut self, from: NodeId, to: NodeId) {
+        for inputs in &mut self.inputs {
+            for input in inputs.iter_mut().filter(|input| **input == from) {
+                *input = to;
+            }
+        }
+        self.nodes[from] = None;
+        self.inputs[from].clear();
+    }
+
+    fn finish(self) -> (DataflowGraph, OptimizationReport) {
+        let mut graph = DataflowGraph::new();
+        let mut id_map = vec![None; self.nodes.len()];
+        for (id, node) in self.nodes.into_iter().enumerate() {
+            if let Some(node) = node {
+                id_map[id] = Some(graph.add_node(node));
+            }
+        }
+        for (to, inputs) in self.inputs.iter().enumerate() {
+            let Some(to) = id_map[to] else {
+                continue;
+            };
+            for &from in inputs {
+                graph.connect(id_map[from].unwrap(), to);
+            }
+        }
+        let report = OptimizationReport {
+            changes: self.changes,
+            id_map,
+        };
+        (graph, report)
+    }
+}
+
+/// The value of a node whose inputs are the constants `inputs`, when it
+/// can be computed without a runtime
+fn fold(kind: &NodeKind, inputs: &[GA3]) -> Option<GA3> {
+    match (kind, inputs) {
+        (NodeKind::Transform(TransformType::Lerp { t }), [a, b]) => Some(a + &(&(b - a) * *t)),
+        (NodeKind::Transform(TransformType::Scale { factor }), [mv]) => Some(mv * *factor),
+        (NodeKind::Transform(transform), [mv]) => {
+            transform.to_transform().map(|rigid| rigid.transform(mv))
+        }
+        (NodeKind::Combine(combiner), inputs) => combine_builtin(combiner, inputs),
+        (NodeKind::Conditional, [condition, then, otherwise]) => {
+            if BoolProjection.project(condition) {
+                Some(then.clone())
+            } else {
+                Some(otherwise.clone())
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The single transform doing `first` then `second`, if there is one
+fn fuse(first: &TransformType, second: &TransformType) -> Option<TransformType> {
+    if let (TransformType::Scale { factor: a }, TransformType::Scale { factor: b }) =
+        (first, second)
+    {
+        return Some(TransformType::Scale { factor: a * b });
+    }
+    let first = first.to_transform()?;
+    let second = second.to_transform()?;
+    Some(TransformType::from_transform(&first.then(&second)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataflow::runtime::{DataflowRuntime, DataflowValue};
+    use crate::dataflow::{CombinerType, GraphBuilder, RotationPlane};
+    use crate::GeometricState;
+    use std::f64::consts::PI;
+
+    /// Evaluate `graph` with `source` bound to (1, 2, 3) and read `sink`
+    fn run(graph: &DataflowGraph, sink: &str) -> GA3 {
+        let runtime = DataflowRuntime::new(graph.clone()).unwrap();
+        if graph.get_id_by_name("source").is_some() {
+            runtime
+                .bind("source", &GeometricState::from_vector(1.0, 2.0, 3.0))
+                .unwrap();
+        }
+        runtime.evaluate().unwrap();
+        runtime.value(sink).unwrap().to_multivector()
+    }
+
+    fn assert_same(a: &GA3, b: &GA3) {
+        for i in 0..8 {
+            assert!((a.get(i) - b.get(i)).abs() < 1e-10, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_fuses_rigid_transforms_and_scales() {
+        let graph = GraphBuilder::new()
+            .source("source")
+            .transform(
+                "turn",
+                TransformType::Rotation {
+                    angle: PI / 3.0,
+                    plane: RotationPlane::ZX,
+                },
+            )
+            .transform(
+                "move",
+                TransformType::Translation {
+                    x: 1.0,
+                    y: -1.0,
+                    z: 0.5,
+                },
+            )
+            .transform(
+                "tilt",
+                TransformType::Rotation {
+                    angle: 0.4,
+                    plane: RotationPlane::XY,
+                },
+            )
+            .transform("grow", TransformType::Scale { factor: 2.0 })
+            .transform("grow_more", TransformType::Scale { factor: 3.0 })
+            .sink("out", "value")
+            .build();
+
+        let (optimized, report) = graph.optimize();
+        assert_eq!(optimized.node_count(), 4);
+        assert_eq!(
+            report.changes,
+            vec![
+                Optimization::FusedTransforms {
+                    nodes: vec![1, 2],
+                    into: 3
+                },
+                Optimization::FusedTransforms {
+                    nodes: vec![4],
+                    into: 5
+                },
+            ]
+        );
+        assert_eq!(
+            optimized.get_node_by_name("grow_more").unwrap().kind,
+            NodeKind::Transform(TransformType::Scale { factor: 6.0 })
+        );
+        assert_same(&run(&optimized, "out"), &run(&graph, "out"));
+    }
+
+    #[test]
+    fn test_folds_constants() {
+        let mut graph = DataflowGraph::new();
+        let a = graph.add_node(Node::constant("a", GA3::scalar(2.0)));
+        let b = graph.add_node(Node::constant("b", GA3::scalar(3.0)));
+        let sum = graph.add_node(Node::combine("sum", CombinerType::Sum));
+        let scale = graph.add_node(Node::transform(
+            "scale",
+            TransformType::Scale { factor: 2.0 },
+        ));
+        let source = graph.add_node(Node::source("source"));
+        let product = graph.add_node(Node::combine("product", CombinerType::Product));
+        let out = graph.add_node(Node::sink("out", "value"));
+        graph.connect(a, sum);
+        graph.connect(b, sum);
+        graph.connect(sum, scale);
+        graph.connect(scale, product);
+        graph.connect(source, product);
+        graph.connect(product, out);
+
+        let (optimized, report) = graph.optimize();
+        assert!(report
+            .changes
+            .contains(&Optimization::FoldedConstant { node: scale }));
+        assert!(report
+            .changes
+            .contains(&Optimization::RemovedDeadNode { node: a }));
+        // a, b and sum are gone; scale is now the constant 10
+        assert_eq!(optimized.node_count(), 4);
+        let folded = optimized.get_node_by_name("scale").unwrap();
+        assert_eq!(folded.kind, NodeKind::Constant);
+        assert_eq!(folded.constant_value.as_ref().unwrap().get(0), 10.0);
+        assert_same(&run(&optimized, "out"), &run(&graph, "out"));
+    }
+
+    #[test]
+    fn test_removes_dead_nodes_but_keeps_sources() {
+        let graph = GraphBuilder::new()
+            .source("source")
+            .project("shown", "scalar")
+            .sink("out", "value")
+            .from("source")
+            .project("unused", "magnitude")
+            .source("unbound")
+            .build();
+
+        let (optimized, report) = graph.optimize();
+        assert_eq!(
+            report.changes,
+            vec![Optimization::RemovedDeadNode { node: 3 }]
+        );
+        assert_eq!(
+            report.id_map,
+            vec![Some(0), Some(1), Some(2), None, Some(3)]
+        );
+        assert!(optimized.get_id_by_name("unbound").is_some());
+        assert!(optimized.get_id_by_name("unused").is_none());
+    }
+
+    #[test]
+    fn test_deduplicates_identical_subgraphs() {
+        let graph = GraphBuilder::new()
+            .source("source")
+            .transform("double", TransformType::Scale { factor: 2.0 })
+            .project("text", "to_string")
+            .sink("label", "textContent")
+            .from("source")
+            .transform("double_again", TransformType::Scale { factor: 2.0 })
+            .project("title", "to_string")
+            .sink("tooltip", "title")
+            .build();
+
+        let (optimized, report) = graph.optimize();
+        assert_eq!(
+            report.changes,
+            vec![
+                Optimization::MergedDuplicate { node: 4, into: 1 },
+                Optimization::MergedDuplicate { node: 5, into: 2 },
+            ]
+        );
+        assert_eq!(optimized.node_count(), 5);
+        let text = optimized.get_id_by_name("text").unwrap();
+        assert_eq!(optimized.outgoing(text).len(), 2);
+
+        let runtime = DataflowRuntime::new(optimized).unwrap();
+        runtime
+            .bind("source", &GeometricState::from_scalar(4.0))
+            .unwrap();
+        assert_eq!(
+            runtime.value("tooltip"),
+            Some(DataflowValue::Text("8".into()))
+        );
+    }
+
+    #[test]
+    fn test_passes_can_be_disabled() {
+        let graph = GraphBuilder::new()
+            .source("source")
+            .transform("a", TransformType::Scale { factor: 2.0 })
+            .transform("b", TransformType::Scale { factor: 2.0 })
+            .project("unused", "scalar")
+            .build();
+
+        let (optimized, report) = Optimizer::new()
+            .with_transform_fusion(false)
+            .with_dead_node_elimination(false)
+            .optimize(&graph);
+        assert!(report.is_unchanged());
+        assert_eq!(optimized.to_document(), graph.to_document());
+        assert_eq!(report.to_string(), "no changes\n");
+    }
+}
@@ -0,0 +1,338 @@
+//! Recording `Behavior` wiring as a dataflow graph
+//!
+//! `map`, `combine` and friends build a dependency graph that is otherwise
+//! invisible. While a `GraphRecorder` is running, every behavior created,
+//! every derivation and every subscription is added to a `DataflowGraph`,
+//! which can then be rendered with `to_dot` or `to_mermaid`:
+//!
+//! - `Behavior::new` adds a `Source` node
+//! - a derivation with one parent (`map`, `distinct`, `memo`, `switch`)
+//!   adds a custom `Transform` node named after the operation
+//! - a derivation with several parents (`combine`, `combine3`) adds a
+//!   custom `Combine` node
+//! - `subscribe` adds a `Sink` node
+//!
+//! Nodes are named after what created them and suffixed with their node
+//! ID, such as `map#3: String` or `subscriber#4`, so names stay unique and
+//! a recorded graph can be saved and loaded like any other.
+//!
+//! Behaviors created before recording started are added as sources the
+//! first time something recorded depends on them. Recording is opt-in: no
+//! recorder running means nothing is tracked. A recorder running inside
+//! another records into both.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::{behavior, GraphRecorder};
+//!
+//! let recorder = GraphRecorder::new();
+//! let (first, full) = recorder.record(|| {
+//!     let first = behavior("Ada".to_string());
+//!     let last = behavior("Lovelace".to_string());
+//!     let full = first.combine(&last, |f, l| format!("{} {}", f, l));
+//!     (first, full)
+//! });
+//! recorder.label(&first, "first name");
+//!
+//! let graph = recorder.graph();
+//! assert_eq!(graph.node_count(), 3);
+//! let mermaid = graph.to_mermaid();
+//! assert!(mermaid.contains("first name"));
+//! assert_eq!(mermaid.matches("-->").count(), 2);
+//! ```
+
+use super::{CombinerType, DataflowGraph, Node, NodeId, NodeKind, TransformType};
+use crate::behavior::{Behavior, Dependency};
+use crate::geometric::{FromGeometric, IntoGeometric};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+thread_local! {
+    /// Recorders currently running on this thread, innermost last
+    static RECORDING: RefCell<Vec<Rc<RefCell<Recording>>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Records behaviors, derivations and subscriptions into a `DataflowGraph`
+///
+/// See the [module documentation](self) for details.
+#[derive(Default)]
+pub struct GraphRecorder {
+    recording: Rc<RefCell<Recording>>,
+}
+
+#[derive(Default)]
+struct Recording {
+    graph: DataflowGraph,
+    /// Node of each behavior, by behavior identity
+    nodes: HashMap<usize, NodeId>,
+}
+
+impl Recording {
+    /// The node for a behavior, added as a source if it hasn't been seen
+    fn node(&mut self, identity: usize, type_name: &str) -> NodeId {
+        if let Some(&id) = self.nodes.get(&identity) {
+            return id;
+        }
+        let name = node_name("behavior", self.graph.node_count(), type_name);
+        let id = self.graph.add_node(Node::source(name));
+        self.nodes.insert(identity, id);
+        id
+    }
+}
+
+impl GraphRecorder {
+    /// Create a recorder with an empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f`, recording the behaviors, derivations and subscriptions it
+    /// creates
+    ///
+    /// A recorder can run several times; each run adds to the same graph.
+    pub fn record<R>(&self, f: impl FnOnce() -> R) -> R {
+        RECORDING.with(|recording| recording.borrow_mut().push(Rc::clone(&self.recording)));
+
+        struct Exit;
+        impl Drop for Exit {
+            fn drop(&mut self) {
+                RECORDING.with(|recording| recording.borrow_mut().pop());
+            }
+        }
+
+        let _exit = Exit;
+        f()
+    }
+
+    /// Name the node of `behavior`, adding it as a source if it isn't
+    /// part of the graph yet
+    pub fn label<T>(&self, behavior: &Behavior<T>, name: impl Into<String>)
+    where
+        T: IntoGeometric + FromGeometric + Clone + 'static,
+    {
+        let mut recording = self.recording.borrow_mut();
+        let id = recording.node(behavior.identity(), std::any::type_name::<T>());
+        recording.graph.rename(id, name);
+    }
+
+    /// The graph recorded so far
+    pub fn graph(&self) -> DataflowGraph {
+        self.recording.borrow().graph.clone()
+    }
+}
+
+/// Run `record` against every running recorder
+fn with_recordings(mut record: impl FnMut(&mut Recording)) {
+    RECORDING.with(|recording| {
+        for active in recording.borrow().iter() {
+            record(&mut active.borrow_mut());
+        }
+    });
+}
+
+/// Record a new source behavior
+pub(crate) fn source<T>(identity: usize) {
+    with_recordings(|recording| {
+        let id = recording.graph.node_count();
+        let name = node_name("behavior", id, std::any::type_name::<T>());
+        recording.graph.add_node(Node::source(name));
+        recording.nodes.insert(identity, id);
+    });
+}
+
+/// Record a behavior derived from `parents` by `operation`
+pub(crate) fn derived<T>(operation: &str, identity: usize, parents: &[&dyn Dependency]) {
+    with_recordings(|recording| {
+        // Parents first, so node ids stay in topological order
+        let parents: Vec<NodeId> = parents
+            .iter()
+            .map(|parent| recording.node(parent.identity(), parent.type_name()))
+            .collect();
+        let kind = if parents.len() > 1 {
+            NodeKind::Combine(CombinerType::Custom(operation.to_string()))
+        } else {
+            NodeKind::Transform(TransformType::Custom {
+                name: operation.to_string(),
+            })
+        };
+        let mut node = Node::new(kind);
+        let id = recording.graph.node_count();
+        node.name = Some(node_name(operation, id, std::any::type_name::<T>()));
+        recording.graph.add_node(node);
+        recording.nodes.insert(identity, id);
+        for parent in parents {
+            recording.graph.connect(parent, id);
+        }
+    });
+}
+
+/// Record a subscription to a behavior
+pub(crate) fn subscriber<T>(identity: usize) {
+    with_recordings(|recording| {
+        let behavior = recording.node(identity, std::any::type_name::<T>());
+        let name = format!("subscriber#{}", recording.graph.node_count());
+        let id = recording.graph.add_node(Node::sink(name, "subscribe"));
+        recording.graph.connect(behavior, id);
+    });
+}
+
+/// `operation#id: Type`, with module paths dropped from the type name
+fn node_name(operation: &str, id: NodeId, type_name: &str) -> String {
+    let mut short = String::new();
+    let mut path = String::new();
+    for c in type_name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            path.push(c);
+        } else {
+            short.push_str(path.rsplit("::").next().unwrap());
+            path.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(path.rsplit("::").next().unwrap());
+    format!("{}#{}: {}", operation, id, short)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::behavior::behavior;
+    use crate::combinators::combine3;
+
+    #[test]
+    fn test_records_derivations() {
+        let recorder = GraphRecorder::new();
+        let total = recorder.record(|| {
+            let a = behavior(1i32);
+            let b = behavior(2i32);
+            let sum = a.combine(&b, |a, b| a + b);
+            sum.map(|n| n.to_string()).distinct()
+        });
+
+        let graph = recorder.graph();
+        let names: Vec<_> = graph
+            .nodes()
+            .map(|(_, node)| node.name.clone().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "behavior#0: i32",
+                "behavior#1: i32",
+                "combine#2: i32",
+                "map#3: String",
+                "distinct#4: String"
+            ]
+        );
+        assert_eq!(graph.incoming(2), [0, 1]);
+        assert_eq!(graph.incoming(3), [2]);
+        assert_eq!(
+            graph.get_node(2).unwrap().kind,
+            NodeKind::Combine(CombinerType::Custom("combine".into()))
+        );
+        assert_eq!(graph.topological_sort().unwrap().last(), Some(&4));
+
+        // Recording doesn't change behavior
+        assert_eq!(total.sample(), "3");
+    }
+
+    #[test]
+    fn test_dropped_behaviors_are_not_confused_with_new_ones() {
+        let recorder = GraphRecorder::new();
+        for _ in 0..8 {
+            recorder.record(|| drop(behavior(0i32)));
+            let outside = behavior(1i32);
+            recorder.record(|| outside.map(|n| n + 1));
+        }
+
+        // Each derivation got its own parent, never a dropped source
+        let graph = recorder.graph();
+        assert_eq!(graph.node_count(), 24);
+        for (id, node) in graph.nodes() {
+            if !node.is_source() {
+                assert_eq!(graph.incoming(id), [id - 1]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_records_subscriptions_and_outside_parents() {
+        let outside = behavior(0.5f64);
+        let recorder = GraphRecorder::new();
+        let (derived, _subscription) = recorder.record(|| {
+            let other = behavior(1.0f64);
+            let derived = combine3(&outside, &other, &outside, |a, b, c| a + b + c);
+            let subscription = derived.subscribe(|_| {});
+            (derived, subscription)
+        });
+        recorder.label(&outside, "opacity");
+
+        let graph = recorder.graph();
+        assert_eq!(graph.node_count(), 4);
+        let opacity = graph.get_id_by_name("opacity").unwrap();
+        assert!(graph.get_node(opacity).unwrap().is_source());
+        assert_eq!(graph.outgoing(opacity).len(), 2);
+        let sink = graph.get_id_by_name("subscriber#3").unwrap();
+        assert_eq!(
+            graph.incoming(sink),
+            [graph.get_id_by_name("combine3#2: f64").unwrap()]
+        );
+        assert!(graph.to_dot().contains("opacity\\nsource"));
+
+        // Nothing is recorded outside `record`
+        let _more = derived.map(|x| x * 2.0);
+        assert_eq!(recorder.graph().node_count(), 4);
+    }
+
+    #[test]
+    fn test_recorded_graph_roundtrips_through_serde() {
+        let recorder = GraphRecorder::new();
+        let _subscriptions = recorder.record(|| {
+            let a = behavior(1i32);
+            let b = behavior(2i32);
+            let sum = a.combine(&b, |a, b| a + b);
+            let labels = [sum.map(|n| n.to_string()), sum.map(|n| format!("{:x}", n))];
+            labels.map(|label| label.subscribe(|_| {}))
+        });
+
+        let graph = recorder.graph();
+        let json = serde_json::to_string(&graph).unwrap();
+        let loaded: DataflowGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.node_count(), 7);
+        for (id, node) in graph.nodes() {
+            let name = node.name.as_deref().unwrap();
+            assert_eq!(graph.get_id_by_name(name), Some(id));
+            assert_eq!(loaded.get_id_by_name(name), Some(id));
+            assert_eq!(loaded.incoming(id), graph.incoming(id));
+        }
+    }
+
+    #[test]
+    fn test_nested_recorders() {
+        let outer = GraphRecorder::new();
+        let inner = GraphRecorder::new();
+        outer.record(|| {
+            let a = behavior(1i32);
+            inner.record(|| a.map(|n| n + 1))
+        });
+
+        assert_eq!(outer.graph().node_count(), 2);
+        // `a` was created outside the inner recorder, so it's added on use
+        assert_eq!(inner.graph().node_count(), 2);
+        assert_eq!(inner.graph().incoming(1), [0]);
+    }
+
+    #[test]
+    fn test_node_name_drops_module_paths() {
+        assert_eq!(
+            node_name("map", 3, std::any::type_name::<Option<Vec<String>>>()),
+            "map#3: Option<Vec<String>>"
+        );
+        assert_eq!(
+            node_name("behavior", 0, "(f64, f64)"),
+            "behavior#0: (f64, f64)"
+        );
+    }
+}
@@ -0,0 +1,1026 @@
+//! Executing dataflow graphs
+//!
+//! `DataflowRuntime` binds the source nodes of a `DataflowGraph` to
+//! `GeometricState`s and evaluates the other nodes in topological order,
+//! using the real `Rotor`, `Translation` and `Projection` implementations.
+//! Sink nodes push their values to registered handlers.
+//!
+//! When a bound state changes, only the nodes reachable from its source
+//! are evaluated again; everything else keeps its cached value. Nodes
+//! downstream of an unbound source stay unevaluated until it is bound.
+//! Binding a source again replaces its previous state, and `unbind`
+//! detaches it, keeping the values last computed from it.
+//!
+//! Projection nodes name a built-in projection by its `Projection::name`
+//! (`"scalar"`, `"vector"`, `"color"` and so on, see `BUILTIN_PROJECTIONS`),
+//! `"to_string"` / `"scalar_to_string"` for text, or a projection
+//! registered with `with_projection`. Custom transforms and
+//! combiners are registered the same way.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::dataflow::runtime::{DataflowRuntime, DataflowValue};
+//! use cliffy_core::dataflow::{GraphBuilder, RotationPlane, TransformType};
+//! use cliffy_core::GeometricState;
+//! use std::f64::consts::PI;
+//! use std::sync::{Arc, Mutex};
+//!
+//! let graph = GraphBuilder::new()
+//!     .source("position")
+//!     .transform("turn", TransformType::Rotation { angle: PI / 2.0, plane: RotationPlane::XY })
+//!     .project("xyz", "vector")
+//!     .sink("label", "textContent")
+//!     .build();
+//!
+//! let runtime = DataflowRuntime::new(graph).unwrap();
+//! let seen = Arc::new(Mutex::new(Vec::new()));
+//! let seen_clone = seen.clone();
+//! runtime
+//!     .on_sink("label", move |value| seen_clone.lock().unwrap().push(value.clone()))
+//!     .unwrap();
+//!
+//! let position = GeometricState::from_vector(1.0, 0.0, 0.0);
+//! runtime.bind("position", &position).unwrap();
+//! position.set_vector(0.0, 2.0, 0.0);
+//!
+//! let seen = seen.lock().unwrap();
+//! assert_eq!(seen.len(), 2);
+//! let DataflowValue::Vector(x, _, _) = seen[1] else { panic!() };
+//! assert!((x + 2.0).abs() < 1e-10);
+//! ```
+
+use super::{CombinerType, DataflowGraph, NodeId, NodeKind, TransformType};
+use crate::geometric::GA3;
+use crate::projection::{
+    BivectorProjection, BoolProjection, ColorAlphaProjection, ColorProjection, IntProjection,
+    MagnitudeProjection, OklabColorProjection, PointProjection, Position3DProjection, Projection,
+    RotorAngleProjection, ScalarProjection, VectorProjection,
+};
+use crate::state::{GeometricState, GeometricSubscription};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A value flowing along an edge
+///
+/// Sources, transforms and combiners produce multivectors; projections
+/// produce the user-facing value they extract.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DataflowValue {
+    /// A GA3 multivector
+    Multivector(GA3),
+    /// A real number
+    Scalar(f64),
+    /// An integer
+    Int(i32),
+    /// A boolean
+    Bool(bool),
+    /// Three components, such as a position or a bivector (xy, xz, yz)
+    Vector(f64, f64, f64),
+    /// An RGB color
+    Color(u8, u8, u8),
+    /// An RGBA color
+    ColorAlpha(u8, u8, u8, u8),
+    /// Text
+    Text(String),
+}
+
+impl DataflowValue {
+    /// The value as a multivector, for nodes that follow a projection
+    ///
+    /// Numbers go in the scalar, vectors in grade 1 and colors in the
+    /// layout `ColorProjection` reads. Text has no geometric meaning here
+    /// and becomes zero.
+    pub fn to_multivector(&self) -> GA3 {
+        let coefficients = |values: &[f64]| {
+            let mut coeffs = vec![0.0; 8];
+            for (&index, &value) in [0, 1, 2, 4].iter().zip(values) {
+                coeffs[index] = value;
+            }
+            GA3::from_coefficients(coeffs)
+        };
+        match self {
+            DataflowValue::Multivector(mv) => mv.clone(),
+            DataflowValue::Scalar(v) => GA3::scalar(*v),
+            DataflowValue::Int(v) => GA3::scalar(*v as f64),
+            DataflowValue::Bool(v) => GA3::scalar(if *v { 1.0 } else { 0.0 }),
+            DataflowValue::Vector(x, y, z) => coefficients(&[0.0, *x, *y, *z]),
+            DataflowValue::Color(r, g, b) => coefficients(&[*r as f64, *g as f64, *b as f64]),
+            DataflowValue::ColorAlpha(r, g, b, a) => {
+                coefficients(&[*r as f64, *g as f64, *b as f64, *a as f64])
+            }
+            DataflowValue::Text(_) => GA3::zero(),
+        }
+    }
+}
+
+impl fmt::Display for DataflowValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataflowValue::Multivector(mv) => write!(f, "{:?}", mv),
+            DataflowValue::Scalar(v) => write!(f, "{}", v),
+            DataflowValue::Int(v) => write!(f, "{}", v),
+            DataflowValue::Bool(v) => write!(f, "{}", v),
+            DataflowValue::Vector(x, y, z) => write!(f, "({}, {}, {})", x, y, z),
+            DataflowValue::Color(r, g, b) => write!(f, "rgb({}, {}, {})", r, g, b),
+            DataflowValue::ColorAlpha(r, g, b, a) => {
+                write!(f, "rgba({}, {}, {}, {})", r, g, b, *a as f64 / 255.0)
+            }
+            DataflowValue::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+/// Errors from building or running a `DataflowRuntime`
+#[derive(Clone, Debug, PartialEq)]
+pub enum DataflowError {
+    /// The graph has a cycle, so there is no evaluation order
+    Cycle,
+    /// No node has this name
+    UnknownNode(String),
+    /// The named node is not a source
+    NotASource(String),
+    /// The named node is not a sink
+    NotASink(String),
+    /// A projection node names a projection that is neither built in nor registered
+    UnknownProjection(String),
+    /// A custom transform that was never registered
+    UnknownTransform(String),
+    /// A custom combiner that was never registered
+    UnknownCombiner(String),
+    /// A node has the wrong number of inputs
+    Arity {
+        node: NodeId,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl fmt::Display for DataflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataflowError::Cycle => write!(f, "dataflow graph has a cycle"),
+            DataflowError::UnknownNode(name) => write!(f, "no node named '{}'", name),
+            DataflowError::NotASource(name) => write!(f, "node '{}' is not a source", name),
+            DataflowError::NotASink(name) => write!(f, "node '{}' is not a sink", name),
+            DataflowError::UnknownProjection(name) => write!(f, "unknown projection '{}'", name),
+            DataflowError::UnknownTransform(name) => write!(f, "unknown transform '{}'", name),
+            DataflowError::UnknownCombiner(name) => write!(f, "unknown combiner '{}'", name),
+            DataflowError::Arity {
+                node,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "node {} expects {} input(s), got {}",
+                node, expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DataflowError {}
+
+/// Type alias for custom projection functions to avoid clippy::type_complexity warning
+type ProjectionFn = Arc<dyn Fn(&GA3) -> DataflowValue + Send + Sync>;
+
+/// Type alias for custom transform functions to avoid clippy::type_complexity warning
+type TransformFn = Arc<dyn Fn(&GA3) -> GA3 + Send + Sync>;
+
+/// Type alias for custom combiner functions to avoid clippy::type_complexity warning
+type CombinerFn = Arc<dyn Fn(&[GA3]) -> GA3 + Send + Sync>;
+
+/// Type alias for sink handlers to avoid clippy::type_complexity warning
+type SinkHandler = Arc<dyn Fn(&DataflowValue) + Send + Sync>;
+
+/// Projection types every runtime understands without registration
+pub const BUILTIN_PROJECTIONS: &[&str] = &[
+    "scalar",
+    "int",
+    "bool",
+    "vector",
+    "position3d",
+    "point",
+    "bivector",
+    "magnitude",
+    "rotor_angle",
+    "color",
+    "color_alpha",
+    "oklab_color",
+    "to_string",
+    "scalar_to_string",
+];
+
+/// Evaluates a dataflow graph against bound geometric states
+///
+/// Handles are cheap to clone and share the same graph, bindings and
+/// cached values.
+#[derive(Clone)]
+pub struct DataflowRuntime {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    evaluator: Mutex<Evaluator>,
+    /// Latest values of sources that changed since the last evaluation
+    pending: Mutex<HashMap<NodeId, GA3>>,
+    /// Set while some thread is evaluating pending changes
+    draining: AtomicBool,
+    /// The state each source is currently bound to
+    bindings: Mutex<HashMap<NodeId, Binding>>,
+    /// Next binding ID
+    next_binding: AtomicU64,
+    handlers: Mutex<HashMap<NodeId, Vec<SinkHandler>>>,
+    last_error: Mutex<Option<DataflowError>>,
+}
+
+/// A source's subscription on its state
+///
+/// Dropping a binding unsubscribes from the state. A notification already
+/// running may still call the subscription, so it only forwards changes
+/// while its ID is the source's current binding.
+struct Binding {
+    id: u64,
+    subscription: Option<GeometricSubscription>,
+}
+
+impl Drop for Binding {
+    fn drop(&mut self) {
+        if let Some(subscription) = self.subscription.take() {
+            subscription.unsubscribe();
+        }
+    }
+}
+
+struct Evaluator {
+    graph: DataflowGraph,
+    order: Vec<NodeId>,
+    values: Vec<Option<DataflowValue>>,
+    sources: HashMap<NodeId, GA3>,
+    projections: HashMap<String, ProjectionFn>,
+    transforms: HashMap<String, TransformFn>,
+    combiners: HashMap<String, CombinerFn>,
+}
+
+impl DataflowRuntime {
+    /// Create a runtime for `graph`
+    ///
+    /// Fails if the graph has a cycle. Only constants are evaluated until
+    /// sources are bound or `evaluate` is called.
+    pub fn new(graph: DataflowGraph) -> Result<Self, DataflowError> {
+        let order = graph.topological_sort().ok_or(DataflowError::Cycle)?;
+        // Constants are not reachable from any source, so they are
+        // evaluated once up front
+        let values = graph
+            .nodes()
+            .map(|(_, node)| match node.kind {
+                NodeKind::Constant => Some(DataflowValue::Multivector(
+                    node.constant_value.clone().unwrap_or_else(GA3::zero),
+                )),
+                _ => None,
+            })
+            .collect();
+        Ok(Self {
+            shared: Arc::new(Shared {
+                evaluator: Mutex::new(Evaluator {
+                    graph,
+                    order,
+                    values,
+                    sources: HashMap::new(),
+                    projections: HashMap::new(),
+                    transforms: HashMap::new(),
+                    combiners: HashMap::new(),
+                }),
+                pending: Mutex::new(HashMap::new()),
+                draining: AtomicBool::new(false),
+                bindings: Mutex::new(HashMap::new()),
+                next_binding: AtomicU64::new(0),
+                handlers: Mutex::new(HashMap::new()),
+                last_error: Mutex::new(None),
+            }),
+        })
+    }
+
+    /// Register a projection for projection nodes of type `name`
+    ///
+    /// Registered projections take precedence over the built-in ones.
+    pub fn with_projection<F>(self, name: impl Into<String>, projection: F) -> Self
+    where
+        F: Fn(&GA3) -> DataflowValue + Send + Sync + 'static,
+    {
+        self.evaluator()
+            .projections
+            .insert(name.into(), Arc::new(projection));
+        self
+    }
+
+    /// Register the implementation of `TransformType::Custom { name }`
+    pub fn with_transform<F>(self, name: impl Into<String>, transform: F) -> Self
+    where
+        F: Fn(&GA3) -> GA3 + Send + Sync + 'static,
+    {
+        self.evaluator()
+            .transforms
+            .insert(name.into(), Arc::new(transform));
+        self
+    }
+
+    /// Register the implementation of `CombinerType::Custom(name)`
+    pub fn with_combiner<F>(self, name: impl Into<String>, combiner: F) -> Self
+    where
+        F: Fn(&[GA3]) -> GA3 + Send + Sync + 'static,
+    {
+        self.evaluator()
+            .combiners
+            .insert(name.into(), Arc::new(combiner));
+        self
+    }
+
+    /// Bind a source node to a state
+    ///
+    /// The subgraph reachable from the source is evaluated straight away
+    /// and again whenever the state changes. Binding a source that is
+    /// already bound replaces its state: changes to the previous state are
+    /// ignored from then on. Evaluation runs inside the state's
+    /// notification, so sink handlers must not set that same state.
+    pub fn bind(&self, source: &str, state: &GeometricState) -> Result<(), DataflowError> {
+        let id = self.source_id(source)?;
+        let binding = self.shared.next_binding.fetch_add(1, Ordering::Relaxed);
+
+        let weak = Arc::downgrade(&self.shared);
+        let subscription = state.subscribe(move |mv| {
+            if let Some(shared) = weak.upgrade() {
+                {
+                    // Checked under the bindings lock, so a change can't
+                    // slip in after the source has been rebound
+                    let bindings = shared.bindings.lock().unwrap();
+                    if bindings.get(&id).map(|b| b.id) != Some(binding) {
+                        return;
+                    }
+                    shared.pending.lock().unwrap().insert(id, mv.clone());
+                }
+                if let Err(error) = shared.drain() {
+                    *shared.last_error.lock().unwrap() = Some(error);
+                }
+            }
+        });
+
+        {
+            let mut bindings = self.shared.bindings.lock().unwrap();
+            bindings.insert(
+                id,
+                Binding {
+                    id: binding,
+                    subscription: Some(subscription),
+                },
+            );
+            self.shared
+                .pending
+                .lock()
+                .unwrap()
+                .insert(id, state.multivector());
+        }
+        self.shared.drain()
+    }
+
+    /// Detach a source node from its state
+    ///
+    /// Later changes to the state are ignored. Nodes computed from the
+    /// source keep their values until it is bound again. Returns whether
+    /// the source was bound.
+    pub fn unbind(&self, source: &str) -> Result<bool, DataflowError> {
+        let id = self.source_id(source)?;
+        let mut bindings = self.shared.bindings.lock().unwrap();
+        self.shared.pending.lock().unwrap().remove(&id);
+        Ok(bindings.remove(&id).is_some())
+    }
+
+    /// The ID of the source node named `source`
+    fn source_id(&self, source: &str) -> Result<NodeId, DataflowError> {
+        let evaluator = self.evaluator();
+        let id = evaluator.lookup(source)?;
+        if !evaluator.graph.get_node(id).unwrap().is_source() {
+            return Err(DataflowError::NotASource(source.to_string()));
+        }
+        Ok(id)
+    }
+
+    /// Register a handler for the values pushed to a sink node
+    pub fn on_sink<F>(&self, sink: &str, handler: F) -> Result<(), DataflowError>
+    where
+        F: Fn(&DataflowValue) + Send + Sync + 'static,
+    {
+        let id = {
+            let evaluator = self.evaluator();
+            let id = evaluator.lookup(sink)?;
+            if !evaluator.graph.get_node(id).unwrap().is_sink() {
+                return Err(DataflowError::NotASink(sink.to_string()));
+            }
+            id
+        };
+        self.shared
+            .handlers
+            .lock()
+            .unwrap()
+            .entry(id)
+            .or_default()
+            .push(Arc::new(handler));
+        Ok(())
+    }
+
+    /// Evaluate every node and push every sink
+    pub fn evaluate(&self) -> Result<(), DataflowError> {
+        let outputs = {
+            let mut evaluator = self.evaluator();
+            let all: HashSet<NodeId> = (0..evaluator.graph.node_count()).collect();
+            evaluator.evaluate(&all)?
+        };
+        self.shared.push(outputs);
+        Ok(())
+    }
+
+    /// The cached value of a node, if it has been evaluated
+    pub fn value(&self, name: &str) -> Option<DataflowValue> {
+        let evaluator = self.evaluator();
+        let id = evaluator.graph.get_id_by_name(name)?;
+        evaluator.values[id].clone()
+    }
+
+    /// Take the error from the last failed evaluation triggered by a state
+    /// change, if any
+    pub fn take_error(&self) -> Option<DataflowError> {
+        self.shared.last_error.lock().unwrap().take()
+    }
+
+    fn evaluator(&self) -> std::sync::MutexGuard<'_, Evaluator> {
+        self.shared.evaluator.lock().unwrap()
+    }
+}
+
+impl Shared {
+    /// Evaluate the subgraphs of pending sources until none are left
+    ///
+    /// A change made by a sink handler while evaluating is queued and picked
+    /// up by the loop rather than evaluated re-entrantly.
+    fn drain(&self) -> Result<(), DataflowError> {
+        while !self.pending.lock().unwrap().is_empty() {
+            if self.draining.swap(true, Ordering::AcqRel) {
+                return Ok(());
+            }
+            let result = self.drain_pending();
+            self.draining.store(false, Ordering::Release);
+            result?;
+        }
+        Ok(())
+    }
+
+    fn drain_pending(&self) -> Result<(), DataflowError> {
+        loop {
+            let changed = std::mem::take(&mut *self.pending.lock().unwrap());
+            if changed.is_empty() {
+                return Ok(());
+            }
+            let outputs = {
+                let mut evaluator = self.evaluator.lock().unwrap();
+                let mut affected = HashSet::new();
+                for (id, mv) in changed {
+                    affected.extend(evaluator.graph.reachable_from(id));
+                    evaluator.sources.insert(id, mv);
+                }
+                evaluator.evaluate(&affected)?
+            };
+            self.push(outputs);
+        }
+    }
+
+    /// Call the handlers of each evaluated sink, outside the evaluator lock
+    fn push(&self, outputs: Vec<(NodeId, DataflowValue)>) {
+        for (id, value) in outputs {
+            let handlers = self
+                .handlers
+                .lock()
+                .unwrap()
+                .get(&id)
+                .cloned()
+                .unwrap_or_default();
+            for handler in handlers {
+                handler(&value);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Node evaluation
+// ============================================================================
+
+impl Evaluator {
+    fn lookup(&self, name: &str) -> Result<NodeId, DataflowError> {
+        self.graph
+            .get_id_by_name(name)
+            .ok_or_else(|| DataflowError::UnknownNode(name.to_string()))
+    }
+
+    /// Evaluate the `affected` nodes in topological order, returning the
+    /// values of the sinks among them
+    fn evaluate(
+        &mut self,
+        affected: &HashSet<NodeId>,
+    ) -> Result<Vec<(NodeId, DataflowValue)>, DataflowError> {
+        let mut outputs = Vec::new();
+        for index in 0..self.order.len() {
+            let id = self.order[index];
+            if !affected.contains(&id) {
+                continue;
+            }
+            let value = self.evaluate_node(id)?;
+            if let (Some(value), true) = (&value, self.graph.get_node(id).unwrap().is_sink()) {
+                outputs.push((id, value.clone()));
+            }
+            self.values[id] = value;
+        }
+        Ok(outputs)
+    }
+
+    /// The value of one node, or None while an input is unevaluated
+    fn evaluate_node(&self, id: NodeId) -> Result<Option<DataflowValue>, DataflowError> {
+        let node = self.graph.get_node(id).unwrap();
+        match &node.kind {
+            NodeKind::Source => {
+                return Ok(self
+                    .sources
+                    .get(&id)
+                    .cloned()
+                    .map(DataflowValue::Multivector))
+            }
+            NodeKind::Constant => {
+                let value = node.constant_value.clone().unwrap_or_else(GA3::zero);
+                return Ok(Some(DataflowValue::Multivector(value)));
+            }
+            _ => {}
+        }
+
+        let inputs: Option<Vec<&DataflowValue>> = self
+            .graph
+            .incoming(id)
+            .iter()
+            .map(|&input| self.values[input].as_ref())
+            .collect();
+        let Some(inputs) = inputs else {
+            return Ok(None);
+        };
+        let arity = |expected: usize| {
+            if inputs.len() == expected {
+                Ok(())
+            } else {
+                Err(DataflowError::Arity {
+                    node: id,
+                    expected,
+                    actual: inputs.len(),
+                })
+            }
+        };
+        let multivectors: Vec<GA3> = inputs.iter().map(|v| v.to_multivector()).collect();
+
+        let value = match &node.kind {
+            NodeKind::Source | NodeKind::Constant => unreachable!(),
+            NodeKind::Sink(_) => {
+                arity(1)?;
+                inputs[0].clone()
+            }
+            NodeKind::Projection(spec) => {
+                arity(1)?;
+                self.project(&spec.projection_type, &multivectors[0])?
+            }
+            NodeKind::Transform(TransformType::Lerp { t }) => {
+                arity(2)?;
+                let (a, b) = (&multivectors[0], &multivectors[1]);
+                DataflowValue::Multivector(a + &(&(b - a) * *t))
+            }
+            NodeKind::Transform(transform) => {
+                arity(1)?;
+                DataflowValue::Multivector(self.transform(transform, &multivectors[0])?)
+            }
+            NodeKind::Combine(combiner) => {
+                if multivectors.is_empty() {
+                    return Err(DataflowError::Arity {
+                        node: id,
+                        expected: 1,
+                        actual: 0,
+                    });
+                }
+                DataflowValue::Multivector(self.combine(combiner, &multivectors)?)
+            }
+            NodeKind::Conditional => {
+                // Inputs are condition, then, else
+                arity(3)?;
+                let branch = if BoolProjection.project(&multivectors[0]) {
+                    1
+                } else {
+                    2
+                };
+                inputs[branch].clone()
+            }
+        };
+        Ok(Some(value))
+    }
+
+    fn project(&self, name: &str, mv: &GA3) -> Result<DataflowValue, DataflowError> {
+        if let Some(projection) = self.projections.get(name) {
+            return Ok(projection(mv));
+        }
+        let vector = |(x, y, z): (f64, f64, f64)| DataflowValue::Vector(x, y, z);
+        Ok(match name {
+            "scalar" => DataflowValue::Scalar(ScalarProjection.project(mv)),
+            "int" => DataflowValue::Int(IntProjection.project(mv)),
+            "bool" => DataflowValue::Bool(BoolProjection.project(mv)),
+            "vector" => vector(VectorProjection.project(mv)),
+            "position3d" => vector(Position3DProjection.project(mv)),
+            "point" => vector(PointProjection.project(mv)),
+            "bivector" => vector(BivectorProjection.project(mv)),
+            "magnitude" => DataflowValue::Scalar(MagnitudeProjection.project(mv)),
+            "rotor_angle" => DataflowValue::Scalar(RotorAngleProjection.project(mv)),
+            "color" => {
+                let (r, g, b) = ColorProjection.project(mv);
+                DataflowValue::Color(r, g, b)
+            }
+            "color_alpha" => {
+                let (r, g, b, a) = ColorAlphaProjection.project(mv);
+                DataflowValue::ColorAlpha(r, g, b, a)
+            }
+            "oklab_color" => {
+                let (r, g, b) = OklabColorProjection.project(mv);
+                DataflowValue::Color(r, g, b)
+            }
+            "to_string" | "scalar_to_string" => {
+                DataflowValue::Text(ScalarProjection.project(mv).to_string())
+            }
+            _ => return Err(DataflowError::UnknownProjection(name.to_string())),
+        })
+    }
+
+    fn transform(&self, transform: &TransformType, mv: &GA3) -> Result<GA3, DataflowError> {
+        Ok(match transform {
+            TransformType::Scale { factor } => mv * *factor,
+            TransformType::Custom { name } => {
+                let custom = self
+                    .transforms
+                    .get(name)
+                    .ok_or_else(|| DataflowError::UnknownTransform(name.clone()))?;
+                custom(mv)
+            }
+            TransformType::Lerp { .. } => unreachable!("lerp takes two inputs"),
+            rigid => rigid.to_transform().unwrap().transform(mv),
+        })
+    }
+
+    fn combine(&self, combiner: &CombinerType, inputs: &[GA3]) -> Result<GA3, DataflowError> {
+        if let CombinerType::Custom(name) = combiner {
+            let custom = self
+                .combiners
+                .get(name)
+                .ok_or_else(|| DataflowError::UnknownCombiner(name.clone()))?;
+            return Ok(custom(inputs));
+        }
+        Ok(combine_builtin(combiner, inputs).unwrap())
+    }
+}
+
+/// Combine one or more inputs with a built-in combiner (None for custom
+/// combiners)
+pub(super) fn combine_builtin(combiner: &CombinerType, inputs: &[GA3]) -> Option<GA3> {
+    let componentwise = |pick: fn(f64, f64) -> f64| {
+        let coeffs = (0..8)
+            .map(|i| inputs.iter().map(|mv| mv.get(i)).reduce(pick).unwrap())
+            .collect();
+        GA3::from_coefficients(coeffs)
+    };
+    let sum = || {
+        inputs[1..]
+            .iter()
+            .fold(inputs[0].clone(), |acc, mv| &acc + mv)
+    };
+    Some(match combiner {
+        CombinerType::Sum => sum(),
+        CombinerType::Product => inputs[1..]
+            .iter()
+            .fold(inputs[0].clone(), |acc, mv| acc.geometric_product(mv)),
+        CombinerType::Min => componentwise(f64::min),
+        CombinerType::Max => componentwise(f64::max),
+        CombinerType::Average => &sum() * (1.0 / inputs.len() as f64),
+        CombinerType::Custom(_) => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataflow::{GraphBuilder, Node, RotationPlane};
+    use std::f64::consts::PI;
+    use std::sync::atomic::AtomicUsize;
+
+    fn recorder(runtime: &DataflowRuntime, sink: &str) -> Arc<Mutex<Vec<DataflowValue>>> {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        runtime
+            .on_sink(sink, move |value| {
+                seen_clone.lock().unwrap().push(value.clone())
+            })
+            .unwrap();
+        seen
+    }
+
+    fn assert_vector(value: &DataflowValue, expected: (f64, f64, f64)) {
+        let DataflowValue::Vector(x, y, z) = *value else {
+            panic!("expected a vector, got {:?}", value);
+        };
+        assert!(
+            (x - expected.0).abs() < 1e-10
+                && (y - expected.1).abs() < 1e-10
+                && (z - expected.2).abs() < 1e-10,
+            "expected {:?}, got {:?}",
+            expected,
+            (x, y, z)
+        );
+    }
+
+    #[test]
+    fn test_transforms_use_real_implementations() {
+        let graph = GraphBuilder::new()
+            .source("p")
+            .transform(
+                "rotate",
+                TransformType::Rotation {
+                    angle: PI / 2.0,
+                    plane: RotationPlane::ZX,
+                },
+            )
+            .transform(
+                "move",
+                TransformType::Translation {
+                    x: 0.0,
+                    y: 1.0,
+                    z: 0.0,
+                },
+            )
+            .transform("scale", TransformType::Scale { factor: 2.0 })
+            .project("xyz", "vector")
+            .sink("out", "value")
+            .build();
+        let runtime = DataflowRuntime::new(graph).unwrap();
+        let seen = recorder(&runtime, "out");
+
+        // z rotates onto x in the ZX plane
+        runtime
+            .bind("p", &GeometricState::from_vector(0.0, 0.0, 1.0))
+            .unwrap();
+        assert_vector(&seen.lock().unwrap()[0], (2.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn test_only_reachable_subgraph_is_reevaluated() {
+        let left_runs = Arc::new(AtomicUsize::new(0));
+        let right_runs = Arc::new(AtomicUsize::new(0));
+        let counter = |runs: &Arc<AtomicUsize>| {
+            let runs = runs.clone();
+            move |mv: &GA3| {
+                runs.fetch_add(1, Ordering::SeqCst);
+                mv.clone()
+            }
+        };
+
+        let graph = GraphBuilder::new()
+            .source("a")
+            .transform(
+                "count_a",
+                TransformType::Custom {
+                    name: "left".into(),
+                },
+            )
+            .sink("out_a", "value")
+            .source("b")
+            .transform(
+                "count_b",
+                TransformType::Custom {
+                    name: "right".into(),
+                },
+            )
+            .sink("out_b", "value")
+            .build();
+        let runtime = DataflowRuntime::new(graph)
+            .unwrap()
+            .with_transform("left", counter(&left_runs))
+            .with_transform("right", counter(&right_runs));
+
+        let a = GeometricState::from_scalar(1.0);
+        let b = GeometricState::from_scalar(2.0);
+        runtime.bind("a", &a).unwrap();
+        runtime.bind("b", &b).unwrap();
+        assert_eq!(left_runs.load(Ordering::SeqCst), 1);
+        assert_eq!(right_runs.load(Ordering::SeqCst), 1);
+
+        a.set(GA3::scalar(3.0));
+        a.set(GA3::scalar(4.0));
+        assert_eq!(left_runs.load(Ordering::SeqCst), 3);
+        assert_eq!(right_runs.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            runtime.value("out_a"),
+            Some(DataflowValue::Multivector(GA3::scalar(4.0)))
+        );
+    }
+
+    #[test]
+    fn test_combiners_and_conditional() {
+        let mut graph = DataflowGraph::new();
+        let a = graph.add_node(Node::source("a"));
+        let b = graph.add_node(Node::source("b"));
+        let sum = graph.add_node(Node::combine("sum", CombinerType::Sum));
+        let max = graph.add_node(Node::combine("max", CombinerType::Max));
+        let average = graph.add_node(Node::combine("average", CombinerType::Average));
+        let lerp = graph.add_node(Node::transform("lerp", TransformType::Lerp { t: 0.25 }));
+        let flag = graph.add_node(Node::constant("flag", GA3::scalar(0.0)));
+        let choose = graph.add_node(Node::conditional("choose"));
+        for node in [sum, max, average, lerp] {
+            graph.connect(a, node);
+            graph.connect(b, node);
+        }
+        graph.connect(flag, choose);
+        graph.connect(sum, choose);
+        graph.connect(max, choose);
+
+        let runtime = DataflowRuntime::new(graph).unwrap();
+        runtime
+            .bind("a", &GeometricState::from_vector(1.0, 4.0, 0.0))
+            .unwrap();
+        runtime
+            .bind("b", &GeometricState::from_vector(3.0, 0.0, 0.0))
+            .unwrap();
+
+        let vector =
+            |name: &str| VectorProjection.project(&runtime.value(name).unwrap().to_multivector());
+        assert_eq!(vector("sum"), (4.0, 4.0, 0.0));
+        assert_eq!(vector("max"), (3.0, 4.0, 0.0));
+        assert_eq!(vector("average"), (2.0, 2.0, 0.0));
+        assert_eq!(vector("lerp"), (1.5, 3.0, 0.0));
+        // The flag is false, so the else branch (max) is taken
+        assert_eq!(vector("choose"), (3.0, 4.0, 0.0));
+    }
+
+    #[test]
+    fn test_projections_and_custom_projection() {
+        let graph = GraphBuilder::new()
+            .source("count")
+            .project("text", "scalar_to_string")
+            .sink("label", "textContent")
+            .from("count")
+            .project("badge", "badge")
+            .sink("badge_out", "className")
+            .build();
+        let runtime = DataflowRuntime::new(graph)
+            .unwrap()
+            .with_projection("badge", |mv| {
+                DataflowValue::Text(if mv.get(0) > 9.0 { "many" } else { "few" }.into())
+            });
+        let labels = recorder(&runtime, "label");
+        let badges = recorder(&runtime, "badge_out");
+
+        let count = GeometricState::from_scalar(3.0);
+        runtime.bind("count", &count).unwrap();
+        count.set(GA3::scalar(12.0));
+
+        assert_eq!(
+            *labels.lock().unwrap(),
+            vec![
+                DataflowValue::Text("3".into()),
+                DataflowValue::Text("12".into())
+            ]
+        );
+        assert_eq!(badges.lock().unwrap().last().unwrap().to_string(), "many");
+    }
+
+    #[test]
+    fn test_builtin_projections_are_known() {
+        let runtime = DataflowRuntime::new(DataflowGraph::new()).unwrap();
+        let evaluator = runtime.evaluator();
+        for name in BUILTIN_PROJECTIONS {
+            assert!(
+                evaluator.project(name, &GA3::scalar(1.0)).is_ok(),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut cyclic = DataflowGraph::new();
+        let a = cyclic.add_node(Node::source("a"));
+        let b = cyclic.add_node(Node::transform("b", TransformType::Scale { factor: 2.0 }));
+        cyclic.connect(a, b);
+        cyclic.connect(b, a);
+        assert_eq!(
+            DataflowRuntime::new(cyclic).err(),
+            Some(DataflowError::Cycle)
+        );
+
+        let graph = GraphBuilder::new()
+            .source("s")
+            .project("p", "no_such_projection")
+            .sink("out", "value")
+            .build();
+        let runtime = DataflowRuntime::new(graph).unwrap();
+        let state = GeometricState::from_scalar(1.0);
+        assert_eq!(
+            runtime.bind("p", &state),
+            Err(DataflowError::NotASource("p".into()))
+        );
+        assert_eq!(
+            runtime.on_sink("missing", |_| {}),
+            Err(DataflowError::UnknownNode("missing".into()))
+        );
+        assert_eq!(
+            runtime.bind("s", &state),
+            Err(DataflowError::UnknownProjection(
+                "no_such_projection".into()
+            ))
+        );
+
+        // Errors from evaluations triggered by a state change are kept
+        state.set(GA3::scalar(2.0));
+        assert_eq!(
+            runtime.take_error(),
+            Some(DataflowError::UnknownProjection(
+                "no_such_projection".into()
+            ))
+        );
+        assert_eq!(runtime.take_error(), None);
+    }
+
+    #[test]
+    fn test_rebind_and_unbind() {
+        let graph = GraphBuilder::new()
+            .source("count")
+            .transform("double", TransformType::Scale { factor: 2.0 })
+            .sink("out", "value")
+            .build();
+        let runtime = DataflowRuntime::new(graph).unwrap();
+        let out = || runtime.value("out");
+        let scalar = |v| Some(DataflowValue::Multivector(GA3::scalar(v)));
+
+        let first = GeometricState::from_scalar(1.0);
+        let second = GeometricState::from_scalar(10.0);
+        runtime.bind("count", &first).unwrap();
+        assert_eq!(out(), scalar(2.0));
+
+        runtime.bind("count", &second).unwrap();
+        assert_eq!(out(), scalar(20.0));
+
+        // The first state no longer drives the source
+        first.set(GA3::scalar(3.0));
+        assert_eq!(out(), scalar(20.0));
+        second.set(GA3::scalar(4.0));
+        assert_eq!(out(), scalar(8.0));
+
+        assert_eq!(runtime.unbind("count"), Ok(true));
+        second.set(GA3::scalar(5.0));
+        assert_eq!(out(), scalar(8.0));
+        assert_eq!(runtime.unbind("count"), Ok(false));
+        assert_eq!(
+            runtime.unbind("double"),
+            Err(DataflowError::NotASource("double".into()))
+        );
+
+        runtime.bind("count", &first).unwrap();
+        assert_eq!(out(), scalar(6.0));
+    }
+
+    #[test]
+    fn test_sink_handler_can_update_another_source() {
+        let graph = GraphBuilder::new()
+            .source("input")
+            .sink("echo", "value")
+            .source("mirror")
+            .transform("negate", TransformType::Scale { factor: -1.0 })
+            .sink("mirrored", "value")
+            .build();
+        let runtime = DataflowRuntime::new(graph).unwrap();
+        let mirror = GeometricState::from_scalar(0.0);
+        runtime.bind("mirror", &mirror).unwrap();
+
+        let mirror_clone = mirror.clone();
+        runtime
+            .on_sink("echo", move |value| {
+                mirror_clone.set(value.to_multivector())
+            })
+            .unwrap();
+        let input = GeometricState::from_scalar(5.0);
+        runtime.bind("input", &input).unwrap();
+
+        assert_eq!(
+            runtime.value("mirrored"),
+            Some(DataflowValue::Multivector(GA3::scalar(-5.0)))
+        );
+    }
+}
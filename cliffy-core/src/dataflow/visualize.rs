@@ -0,0 +1,250 @@
+//! Graphviz and Mermaid export for dataflow graphs
+//!
+//! `to_dot` and `to_mermaid` render a `DataflowGraph` for debugging. Each
+//! node is labelled with its name and what it does, and its shape shows
+//! its kind (sources are rounded, projections slanted, conditionals
+//! diamonds). Where the order of a node's inputs matters, edges are
+//! labelled: `from`/`to` for a lerp, `if`/`then`/`else` for a conditional
+//! and the input index for a combiner.
+//!
+//! The same export works for graphs recorded from `Behavior` wiring with
+//! `GraphRecorder`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::dataflow::{GraphBuilder, TransformType};
+//!
+//! let graph = GraphBuilder::new()
+//!     .source("count")
+//!     .transform("double", TransformType::Scale { factor: 2.0 })
+//!     .project("text", "to_string")
+//!     .sink("label", "textContent")
+//!     .build();
+//!
+//! let dot = graph.to_dot(); // render with `dot -Tsvg`
+//! assert!(dot.contains("n0 -> n1;"));
+//!
+//! let mermaid = graph.to_mermaid(); // paste into a ```mermaid block
+//! assert!(mermaid.contains("n0 --> n1"));
+//! ```
+
+use super::{CombinerType, DataflowGraph, Node, NodeId, NodeKind, RotationPlane, TransformType};
+use std::fmt::Write;
+
+impl DataflowGraph {
+    /// Render the graph in Graphviz DOT format
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph dataflow {\n    rankdir=LR;\n");
+        for (id, node) in self.nodes() {
+            let label = escape_dot(&format!(
+                "{}\n{}",
+                node_title(id, node),
+                describe(&node.kind)
+            ));
+            let shape = match node.kind {
+                NodeKind::Source => "shape=ellipse",
+                NodeKind::Projection(_) => "shape=parallelogram",
+                NodeKind::Transform(_) => "shape=box",
+                NodeKind::Sink(_) => "shape=box, peripheries=2",
+                NodeKind::Combine(_) => "shape=invtrapezium",
+                NodeKind::Conditional => "shape=diamond",
+                NodeKind::Constant => "shape=box, style=dashed",
+            };
+            let _ = writeln!(dot, "    n{} [label=\"{}\", {}];", id, label, shape);
+        }
+        for (from, to, label) in self.labelled_edges() {
+            match label {
+                Some(label) => {
+                    let _ = writeln!(dot, "    n{} -> n{} [label=\"{}\"];", from, to, label);
+                }
+                None => {
+                    let _ = writeln!(dot, "    n{} -> n{};", from, to);
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Render the graph as a Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = String::from("flowchart LR\n");
+        for (id, node) in self.nodes() {
+            let label = escape_mermaid(&node_title(id, node))
+                + "<br/>"
+                + &escape_mermaid(&describe(&node.kind));
+            let (open, close) = match node.kind {
+                NodeKind::Source => ("([", "])"),
+                NodeKind::Projection(_) => ("[/", "/]"),
+                NodeKind::Transform(_) => ("[", "]"),
+                NodeKind::Sink(_) => ("[[", "]]"),
+                NodeKind::Combine(_) => ("[\\", "/]"),
+                NodeKind::Conditional => ("{", "}"),
+                NodeKind::Constant => ("((", "))"),
+            };
+            let _ = writeln!(mermaid, "    n{}{}\"{}\"{}", id, open, label, close);
+        }
+        for (from, to, label) in self.labelled_edges() {
+            match label {
+                Some(label) => {
+                    let _ = writeln!(mermaid, "    n{} -->|{}| n{}", from, label, to);
+                }
+                None => {
+                    let _ = writeln!(mermaid, "    n{} --> n{}", from, to);
+                }
+            }
+        }
+        mermaid
+    }
+
+    /// Every edge, with a label where the input's position matters
+    fn labelled_edges(&self) -> Vec<(NodeId, NodeId, Option<String>)> {
+        let mut edges = Vec::new();
+        for (to, node) in self.nodes() {
+            let inputs = self.incoming(to);
+            for (index, &from) in inputs.iter().enumerate() {
+                let label = match &node.kind {
+                    NodeKind::Transform(TransformType::Lerp { .. }) => {
+                        ["from", "to"].get(index).map(|l| l.to_string())
+                    }
+                    NodeKind::Conditional => {
+                        ["if", "then", "else"].get(index).map(|l| l.to_string())
+                    }
+                    NodeKind::Combine(_) if inputs.len() > 1 => Some(index.to_string()),
+                    _ => None,
+                };
+                edges.push((from, to, label));
+            }
+        }
+        edges
+    }
+}
+
+/// The node's name, or its id when it has none
+fn node_title(id: NodeId, node: &Node) -> String {
+    node.name.clone().unwrap_or_else(|| format!("node {}", id))
+}
+
+/// A short description of what a node does
+fn describe(kind: &NodeKind) -> String {
+    match kind {
+        NodeKind::Source => "source".to_string(),
+        NodeKind::Projection(spec) => format!("project {}", spec.projection_type),
+        NodeKind::Transform(transform) => match transform {
+            TransformType::Translation { x, y, z } => format!("translate ({}, {}, {})", x, y, z),
+            TransformType::Rotation { angle, plane } => {
+                let plane = match plane {
+                    RotationPlane::XY => "xy",
+                    RotationPlane::YZ => "yz",
+                    RotationPlane::ZX => "zx",
+                };
+                format!("rotate {} in {}", angle, plane)
+            }
+            TransformType::Rotor { .. } => "rotor".to_string(),
+            TransformType::Transform { translation, .. } => format!(
+                "rotor then translate ({}, {}, {})",
+                translation[0], translation[1], translation[2]
+            ),
+            TransformType::Scale { factor } => format!("scale {}", factor),
+            TransformType::Lerp { t } => format!("lerp {}", t),
+            TransformType::Custom { name } => name.clone(),
+        },
+        NodeKind::Sink(spec) => format!("sink {}", spec.target_property),
+        NodeKind::Combine(combiner) => match combiner {
+            CombinerType::Sum => "sum".to_string(),
+            CombinerType::Product => "product".to_string(),
+            CombinerType::Min => "min".to_string(),
+            CombinerType::Max => "max".to_string(),
+            CombinerType::Average => "average".to_string(),
+            CombinerType::Custom(name) => name.clone(),
+        },
+        NodeKind::Conditional => "conditional".to_string(),
+        NodeKind::Constant => "constant".to_string(),
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Mermaid labels are quoted, so quotes and markup characters become
+/// entity codes
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataflow::GraphBuilder;
+    use crate::GA3;
+
+    fn conditional_graph() -> DataflowGraph {
+        let mut graph = DataflowGraph::new();
+        let flag = graph.add_node(Node::source("flag"));
+        let on = graph.add_node(Node::constant("on", GA3::scalar(1.0)));
+        let off = graph.add_node(Node::new(NodeKind::Constant));
+        let choose = graph.add_node(Node::conditional("choose"));
+        let out = graph.add_node(Node::sink("out", "style.opacity"));
+        for (from, to) in [(flag, choose), (on, choose), (off, choose), (choose, out)] {
+            graph.connect(from, to);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = conditional_graph().to_dot();
+        assert!(dot.starts_with("digraph dataflow {\n"));
+        assert!(dot.contains("    n0 [label=\"flag\\nsource\", shape=ellipse];\n"));
+        assert!(dot.contains("    n2 [label=\"node 2\\nconstant\", shape=box, style=dashed];\n"));
+        assert!(dot.contains("    n3 [label=\"choose\\nconditional\", shape=diamond];\n"));
+        assert!(dot.contains("    n0 -> n3 [label=\"if\"];\n"));
+        assert!(dot.contains("    n2 -> n3 [label=\"else\"];\n"));
+        assert!(dot.contains("    n3 -> n4;\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let graph = GraphBuilder::new()
+            .source("count")
+            .transform("double", TransformType::Scale { factor: 2.0 })
+            .project("text", "to_string")
+            .sink("label", "textContent")
+            .build();
+
+        assert_eq!(
+            graph.to_mermaid(),
+            "flowchart LR\n\
+             \x20   n0([\"count<br/>source\"])\n\
+             \x20   n1[\"double<br/>scale 2\"]\n\
+             \x20   n2[/\"text<br/>project to_string\"/]\n\
+             \x20   n3[[\"label<br/>sink textContent\"]]\n\
+             \x20   n0 --> n1\n\
+             \x20   n1 --> n2\n\
+             \x20   n2 --> n3\n"
+        );
+        assert!(conditional_graph()
+            .to_mermaid()
+            .contains("    n1 -->|then| n3\n"));
+    }
+
+    #[test]
+    fn test_labels_are_escaped() {
+        let mut graph = DataflowGraph::new();
+        graph.add_node(Node::source("say \"hi\" <b>\\"));
+
+        assert!(graph
+            .to_dot()
+            .contains("[label=\"say \\\"hi\\\" <b>\\\\\\nsource\""));
+        assert!(graph
+            .to_mermaid()
+            .contains("n0([\"say #quot;hi#quot; #lt;b#gt;\\<br/>source\"])"));
+    }
+}
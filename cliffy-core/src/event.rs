@@ -6,17 +6,24 @@
 //!
 //! This is inspired by Conal Elliott's classical FRP semantics where
 //! `Event a = [(Time, a)]` - a list of time-value pairs.
+//!
+//! The time of each occurrence comes from the event's `Clock`. Events use
+//! the target's real-time clock by default; `Event::with_clock` lets tests
+//! drive time explicitly. Derived events share their source's clock.
 
+use crate::behavior::Behavior;
 use crate::geometric::{FromGeometric, IntoGeometric, GA3};
-use std::cell::RefCell;
+use crate::time::{Clock, DefaultClock, Scheduler};
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use std::time::Duration;
 
 /// An event occurrence with a value
 #[derive(Debug, Clone)]
 pub struct Occurrence<T> {
     /// The value of this occurrence
     pub value: T,
-    /// Timestamp (relative to behavior creation)
+    /// Timestamp in seconds, read from the event's clock
     pub timestamp: f64,
 }
 
@@ -24,7 +31,8 @@ pub struct Occurrence<T> {
 ///
 /// `Event<T>` allows you to react to discrete happenings like clicks,
 /// keypresses, or timer ticks. Events can be mapped, filtered, and
-/// merged with other events.
+/// merged with other events, and shaped in time (`debounce`, `throttle`,
+/// `delay`) on a pluggable `Scheduler`.
 ///
 /// # Example
 ///
@@ -47,8 +55,8 @@ pub struct Event<T> {
     /// Next subscriber ID
     next_id: Rc<RefCell<usize>>,
 
-    /// Start time for timestamp calculation
-    start_time: std::time::Instant,
+    /// Clock used to timestamp occurrences
+    clock: Rc<dyn Clock>,
 
     /// Optional geometric transform applied to each event
     transform: Option<GA3>,
@@ -59,28 +67,60 @@ impl<T> Clone for Event<T> {
         Self {
             subscribers: Rc::clone(&self.subscribers),
             next_id: Rc::clone(&self.next_id),
-            start_time: self.start_time,
+            clock: Rc::clone(&self.clock),
             transform: self.transform.clone(),
         }
     }
 }
 
 impl<T: Clone + 'static> Event<T> {
-    /// Create a new event stream
+    /// Create a new event stream timestamped by the target's real-time clock
     pub fn new() -> Self {
+        Self::with_clock(DefaultClock::new())
+    }
+
+    /// Create a new event stream timestamped by `clock`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cliffy_core::{Event, ManualClock};
+    /// use std::time::Duration;
+    ///
+    /// let clock = ManualClock::new();
+    /// let ticks = Event::<i32>::with_clock(clock.clone());
+    ///
+    /// clock.advance(Duration::from_secs(2));
+    /// assert_eq!(ticks.now(), 2.0);
+    /// ```
+    pub fn with_clock<C: Clock + 'static>(clock: C) -> Self {
+        Self::with_shared_clock(Rc::new(clock))
+    }
+
+    fn with_shared_clock(clock: Rc<dyn Clock>) -> Self {
         Self {
             subscribers: Rc::new(RefCell::new(Vec::new())),
             next_id: Rc::new(RefCell::new(0)),
-            start_time: std::time::Instant::now(),
+            clock,
             transform: None,
         }
     }
 
+    /// Create an empty event stream on the same clock as this one
+    pub(crate) fn on_same_clock<U: Clone + 'static>(&self) -> Event<U> {
+        Event::with_shared_clock(Rc::clone(&self.clock))
+    }
+
+    /// Current time on this event's clock, in seconds
+    pub fn now(&self) -> f64 {
+        self.clock.now()
+    }
+
     /// Emit a value to all subscribers
     pub fn emit(&self, value: T) {
         let occurrence = Occurrence {
             value,
-            timestamp: self.start_time.elapsed().as_secs_f64(),
+            timestamp: self.clock.now(),
         };
 
         for (_, callback) in self.subscribers.borrow().iter() {
@@ -92,6 +132,14 @@ impl<T: Clone + 'static> Event<T> {
     pub fn subscribe<F>(&self, callback: F) -> EventSubscription
     where
         F: Fn(&T) + 'static,
+    {
+        self.subscribe_occurrences(move |occ| callback(&occ.value))
+    }
+
+    /// Subscribe to this event stream, receiving each value with its timestamp
+    pub fn subscribe_occurrences<F>(&self, callback: F) -> EventSubscription
+    where
+        F: Fn(&Occurrence<T>) + 'static,
     {
         let id = {
             let mut next = self.next_id.borrow_mut();
@@ -100,9 +148,7 @@ impl<T: Clone + 'static> Event<T> {
             id
         };
 
-        self.subscribers
-            .borrow_mut()
-            .push((id, Box::new(move |occ| callback(&occ.value))));
+        self.subscribers.borrow_mut().push((id, Box::new(callback)));
 
         let subscribers = Rc::clone(&self.subscribers);
         EventSubscription {
@@ -119,7 +165,7 @@ impl<T: Clone + 'static> Event<T> {
         U: Clone + 'static,
         F: Fn(T) -> U + 'static,
     {
-        let mapped = Event::<U>::new();
+        let mapped = self.on_same_clock::<U>();
 
         let mapped_clone = mapped.clone();
         self.subscribe(move |value| {
@@ -134,7 +180,7 @@ impl<T: Clone + 'static> Event<T> {
     where
         F: Fn(&T) -> bool + 'static,
     {
-        let filtered = Event::<T>::new();
+        let filtered = self.on_same_clock::<T>();
 
         let filtered_clone = filtered.clone();
         self.subscribe(move |value| {
@@ -148,7 +194,7 @@ impl<T: Clone + 'static> Event<T> {
 
     /// Merge two event streams
     pub fn merge(&self, other: &Event<T>) -> Event<T> {
-        let merged = Event::<T>::new();
+        let merged = self.on_same_clock::<T>();
 
         let merged_clone = merged.clone();
         self.subscribe(move |value| {
@@ -178,6 +224,170 @@ impl<T: Clone + 'static> Event<T> {
 
         behavior
     }
+
+    /// Pair each occurrence with the current value of a behavior
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cliffy_core::{behavior, event};
+    ///
+    /// let clicks = event::<()>();
+    /// let tool = behavior("pen".to_string());
+    /// let used = clicks.snapshot(&tool).fold(String::new(), |_, (_, t)| t);
+    ///
+    /// clicks.emit(());
+    /// assert_eq!(used.sample(), "pen");
+    /// ```
+    pub fn snapshot<B>(&self, behavior: &Behavior<B>) -> Event<(T, B)>
+    where
+        B: IntoGeometric + FromGeometric + Clone + 'static,
+    {
+        let snapshots = self.on_same_clock::<(T, B)>();
+
+        let snapshots_clone = snapshots.clone();
+        let behavior = behavior.clone();
+        self.subscribe(move |value| {
+            snapshots_clone.emit((value.clone(), behavior.sample()));
+        });
+
+        snapshots
+    }
+
+    /// Emit the latest value once no occurrence has arrived for `period`
+    ///
+    /// Each occurrence restarts the quiet period; intermediate values are
+    /// dropped. The debounced event is timestamped by `scheduler`, as are
+    /// those of `throttle` and `delay`.
+    pub fn debounce<S>(&self, period: Duration, scheduler: &S) -> Event<T>
+    where
+        S: Scheduler + Clone + 'static,
+    {
+        let debounced = Event::<T>::with_clock(scheduler.clone());
+        let generation = Rc::new(Cell::new(0u64));
+
+        let debounced_clone = debounced.clone();
+        let scheduler = scheduler.clone();
+        self.subscribe(move |value| {
+            let current = generation.get() + 1;
+            generation.set(current);
+
+            let generation = Rc::clone(&generation);
+            let debounced = debounced_clone.clone();
+            let value = value.clone();
+            scheduler.schedule(
+                period,
+                Box::new(move || {
+                    if generation.get() == current {
+                        debounced.emit(value);
+                    }
+                }),
+            );
+        });
+
+        debounced
+    }
+
+    /// Emit at most one occurrence per `period`
+    ///
+    /// The first occurrence passes through immediately (leading edge);
+    /// occurrences within `period` of the last emitted one are dropped.
+    pub fn throttle<S>(&self, period: Duration, scheduler: &S) -> Event<T>
+    where
+        S: Scheduler + Clone + 'static,
+    {
+        let throttled = Event::<T>::with_clock(scheduler.clone());
+        let last_emit: Rc<Cell<Option<f64>>> = Rc::new(Cell::new(None));
+
+        let throttled_clone = throttled.clone();
+        let scheduler = scheduler.clone();
+        self.subscribe(move |value| {
+            let now = scheduler.now();
+            // Compare whole nanoseconds: in f64 seconds, an occurrence
+            // exactly one period later can land just short (0.3 - 0.2 < 0.1)
+            let open = last_emit
+                .get()
+                .is_none_or(|last| ((now - last) * 1e9).round() >= period.as_nanos() as f64);
+            if open {
+                last_emit.set(Some(now));
+                throttled_clone.emit(value.clone());
+            }
+        });
+
+        throttled
+    }
+
+    /// Re-emit every occurrence after `period`
+    pub fn delay<S>(&self, period: Duration, scheduler: &S) -> Event<T>
+    where
+        S: Scheduler + Clone + 'static,
+    {
+        let delayed = Event::<T>::with_clock(scheduler.clone());
+
+        let delayed_clone = delayed.clone();
+        let scheduler = scheduler.clone();
+        self.subscribe(move |value| {
+            let delayed = delayed_clone.clone();
+            let value = value.clone();
+            scheduler.schedule(period, Box::new(move || delayed.emit(value)));
+        });
+
+        delayed
+    }
+}
+
+impl<T: Clone + 'static> Event<Event<T>> {
+    /// Forward the occurrences of the most recently emitted inner event
+    ///
+    /// Each time this event emits a new inner event, the subscription on the
+    /// previous inner event is unsubscribed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cliffy_core::{event, Event};
+    ///
+    /// let keyboard = event::<i32>();
+    /// let mouse = event::<i32>();
+    /// let active = event::<Event<i32>>();
+    /// let input = active.switch_latest().fold(0, |_, v| v);
+    ///
+    /// active.emit(keyboard.clone());
+    /// keyboard.emit(1);
+    /// active.emit(mouse.clone());
+    /// keyboard.emit(2); // no longer followed
+    /// assert_eq!(input.sample(), 1);
+    /// ```
+    pub fn switch_latest(&self) -> Event<T> {
+        let switched = self.on_same_clock::<T>();
+        let following: RefCell<Option<EventSubscription>> = RefCell::new(None);
+
+        let switched_clone = switched.clone();
+        self.subscribe(move |inner| {
+            if let Some(previous) = following.borrow_mut().take() {
+                previous.unsubscribe();
+            }
+            let switched = switched_clone.clone();
+            let subscription = inner.subscribe(move |value| switched.emit(value.clone()));
+            *following.borrow_mut() = Some(subscription);
+        });
+
+        switched
+    }
+}
+
+impl<T: Clone + 'static> Event<T> {
+    /// Map each occurrence to an event and forward the most recent one
+    ///
+    /// Equivalent to `self.map(f).switch_latest()`: occurrences of an inner
+    /// event stop being forwarded once a later occurrence replaces it.
+    pub fn flat_map<U, F>(&self, f: F) -> Event<U>
+    where
+        U: Clone + 'static,
+        F: Fn(T) -> Event<U> + 'static,
+    {
+        self.map(f).switch_latest()
+    }
 }
 
 impl<T: Clone + 'static> Default for Event<T> {
@@ -221,7 +431,7 @@ pub fn event<T: Clone + 'static>() -> Event<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::cell::Cell;
+    use crate::time::{ManualClock, VirtualScheduler};
 
     #[test]
     fn test_event_new_and_emit() {
@@ -341,4 +551,179 @@ mod tests {
         clicks.emit(());
         assert_eq!(count.sample(), 3);
     }
+
+    #[test]
+    fn test_event_snapshot() {
+        let clicks = event::<i32>();
+        let offset = crate::behavior(100i32);
+        let snapshots = clicks.snapshot(&offset);
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received_clone = Rc::clone(&received);
+        snapshots.subscribe(move |pair| received_clone.borrow_mut().push(*pair));
+
+        clicks.emit(1);
+        offset.set(200);
+        clicks.emit(2);
+
+        assert_eq!(*received.borrow(), vec![(1, 100), (2, 200)]);
+    }
+
+    #[test]
+    fn test_event_timestamps_follow_clock() {
+        let clock = ManualClock::new();
+        let evt = Event::<i32>::with_clock(clock.clone());
+        let doubled = evt.map(|n| n * 2);
+
+        let stamps = Rc::new(RefCell::new(Vec::new()));
+        let stamps_clone = Rc::clone(&stamps);
+        doubled.subscribe_occurrences(move |occ| {
+            stamps_clone.borrow_mut().push((occ.value, occ.timestamp));
+        });
+
+        evt.emit(1);
+        clock.advance(Duration::from_millis(250));
+        evt.emit(2);
+
+        assert_eq!(*stamps.borrow(), vec![(2, 0.0), (4, 0.25)]);
+    }
+
+    #[test]
+    fn test_delayed_occurrence_stamped_by_scheduler() {
+        let scheduler = VirtualScheduler::new();
+        let input = event::<i32>();
+        let delayed = input.delay(Duration::from_millis(500), &scheduler);
+
+        let stamp = Rc::new(Cell::new(-1.0));
+        let stamp_clone = Rc::clone(&stamp);
+        delayed.subscribe_occurrences(move |occ| stamp_clone.set(occ.timestamp));
+
+        scheduler.advance(Duration::from_secs(1));
+        input.emit(1);
+        scheduler.advance(Duration::from_secs(1));
+
+        assert!((stamp.get() - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_event_debounce() {
+        let scheduler = VirtualScheduler::new();
+        let input = event::<i32>();
+        let debounced = input.debounce(Duration::from_millis(100), &scheduler);
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received_clone = Rc::clone(&received);
+        debounced.subscribe(move |v| received_clone.borrow_mut().push(*v));
+
+        input.emit(1);
+        scheduler.advance(Duration::from_millis(50));
+        input.emit(2);
+        scheduler.advance(Duration::from_millis(99));
+        assert!(received.borrow().is_empty());
+
+        scheduler.advance(Duration::from_millis(1));
+        assert_eq!(*received.borrow(), vec![2]);
+
+        input.emit(3);
+        scheduler.advance(Duration::from_millis(100));
+        assert_eq!(*received.borrow(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_event_throttle() {
+        let scheduler = VirtualScheduler::new();
+        let input = event::<i32>();
+        let throttled = input.throttle(Duration::from_millis(100), &scheduler);
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received_clone = Rc::clone(&received);
+        throttled.subscribe(move |v| received_clone.borrow_mut().push(*v));
+
+        input.emit(1);
+        scheduler.advance(Duration::from_millis(40));
+        input.emit(2);
+        scheduler.advance(Duration::from_millis(60));
+        input.emit(3);
+        input.emit(4);
+
+        assert_eq!(*received.borrow(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_event_throttle_on_period_boundary() {
+        let scheduler = VirtualScheduler::new();
+        let input = event::<i32>();
+        let throttled = input.throttle(Duration::from_millis(100), &scheduler);
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received_clone = Rc::clone(&received);
+        throttled.subscribe(move |v| received_clone.borrow_mut().push(*v));
+
+        for n in 0..10 {
+            input.emit(n);
+            scheduler.advance(Duration::from_millis(100));
+        }
+
+        assert_eq!(*received.borrow(), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_event_delay() {
+        let scheduler = VirtualScheduler::new();
+        let input = event::<i32>();
+        let delayed = input.delay(Duration::from_millis(30), &scheduler);
+        let latest = delayed.fold(0i32, |_, v| v);
+
+        input.emit(1);
+        scheduler.advance(Duration::from_millis(10));
+        input.emit(2);
+        assert_eq!(latest.sample(), 0);
+
+        scheduler.advance(Duration::from_millis(20));
+        assert_eq!(latest.sample(), 1);
+
+        scheduler.advance(Duration::from_millis(10));
+        assert_eq!(latest.sample(), 2);
+    }
+
+    #[test]
+    fn test_event_switch_latest() {
+        let a = event::<i32>();
+        let b = event::<i32>();
+        let outer = event::<Event<i32>>();
+        let switched = outer.switch_latest();
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received_clone = Rc::clone(&received);
+        switched.subscribe(move |v| received_clone.borrow_mut().push(*v));
+
+        a.emit(0);
+        outer.emit(a.clone());
+        a.emit(1);
+        outer.emit(b.clone());
+        a.emit(2);
+        b.emit(3);
+
+        assert_eq!(*received.borrow(), vec![1, 3]);
+        assert_eq!(a.subscribers.borrow().len(), 0);
+        assert_eq!(b.subscribers.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_event_flat_map() {
+        let channels = [event::<String>(), event::<String>()];
+        let tuned = event::<usize>();
+        let chans = channels.clone();
+        let messages = tuned.flat_map(move |i| chans[i].clone());
+        let last = messages.fold(String::new(), |_, m| m);
+
+        tuned.emit(0);
+        channels[0].emit("zero".to_string());
+        tuned.emit(1);
+        channels[0].emit("stale".to_string());
+        assert_eq!(last.sample(), "zero");
+
+        channels[1].emit("one".to_string());
+        assert_eq!(last.sample(), "one");
+    }
 }
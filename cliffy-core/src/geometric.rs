@@ -16,6 +16,18 @@ use std::hash::{Hash, Hasher};
 /// It has 8 basis elements: 1, e1, e2, e3, e12, e13, e23, e123
 pub type GA3 = Multivector<3, 0, 0>;
 
+/// 3D projective geometric algebra Cl(3,0,1)
+///
+/// Adds a null basis vector e0 to GA3, so translations are versors and
+/// planes, lines and points meet by outer product. See `crate::algebra`.
+pub type PGA3 = Multivector<3, 0, 1>;
+
+/// 3D conformal geometric algebra Cl(4,1,0)
+///
+/// Adds e+ and e- to GA3, so translations are versors and points are null
+/// vectors. See `crate::algebra`.
+pub type CGA3 = Multivector<4, 1, 0>;
+
 /// Convert a value into its geometric representation
 ///
 /// This trait is implemented for common types, allowing them to be
@@ -143,7 +155,8 @@ impl IntoGeometric for String {
 impl FromGeometric for String {
     fn from_geometric(mv: &GA3) -> Self {
         // We can't reconstruct the string from its hash
-        // Return a placeholder - in practice, the original string is cached
+        // Return a placeholder - in practice, the original string is cached.
+        // Use `GeometricBlob` when the string itself must survive.
         format!("[hash:{:.4}]", mv.get(0))
     }
 }
@@ -184,6 +197,9 @@ impl<T: FromGeometric + Default> FromGeometric for Option<T> {
 
 // ============================================================================
 // Vec<T> - stored as length in scalar, hash in e1
+//
+// There is deliberately no `FromGeometric`: the elements can't be recovered
+// from one multivector. Use `GeometricBlob` for a lossless encoding.
 // ============================================================================
 
 impl<T: Hash> IntoGeometric for Vec<T> {
@@ -210,9 +226,6 @@ impl<T: Hash> IntoGeometric for Vec<T> {
     }
 }
 
-// Note: Vec<T> can't be reconstructed from geometric form without caching
-// The cache is managed by the Behavior type
-
 // ============================================================================
 // Unit type
 // ============================================================================
@@ -240,6 +253,7 @@ impl<A: IntoGeometric, B: IntoGeometric> IntoGeometric for (A, B) {
 
         // Store A's scalar in grade 0, B's scalar in e1 component
         // This is a simplified encoding - works for scalar-like types
+        // (for structured types, use #[derive(Geometric)] instead)
         let mut coeffs = vec![0.0; 8];
         coeffs[0] = a_mv.get(0); // A in scalar
         coeffs[1] = b_mv.get(0); // B in e1
@@ -281,6 +295,332 @@ impl FromGeometric for GA3 {
     }
 }
 
+// ============================================================================
+// GeometricBlob - lossless multi-multivector encoding
+// ============================================================================
+
+/// Bytes packed into each coefficient (48 bits, exact in an f64 mantissa)
+const BYTES_PER_COEFFICIENT: usize = 6;
+
+/// Bytes packed into each payload chunk
+const BYTES_PER_CHUNK: usize = BYTES_PER_COEFFICIENT * 8;
+
+/// Header tag for a byte payload
+const BYTES_TAG: f64 = 1.0;
+
+/// Header tag for a payload of nested element blobs
+const ELEMENTS_TAG: f64 = 2.0;
+
+/// Header tag for a payload of a single multivector
+const MULTIVECTOR_TAG: f64 = 3.0;
+
+/// A lossless encoding of a value as a sequence of GA3 multivectors
+///
+/// A single GA3 holds eight coefficients, which is not enough for values of
+/// arbitrary size such as strings or vectors. A blob is a header chunk
+/// followed by payload chunks:
+///
+/// - header: length in the scalar, payload kind in e1
+/// - bytes: six bytes per coefficient, 48 bytes per chunk
+/// - multivector: the one chunk, for numbers and other values whose
+///   `IntoGeometric` encoding is exact
+/// - elements: for each element, a chunk holding the number of chunks in
+///   its blob, then that blob
+///
+/// Every chunk is an ordinary GA3, so blobs travel through anything that
+/// moves multivectors (CRDT operations, deltas, snapshots).
+///
+/// # Example
+///
+/// ```rust
+/// use cliffy_core::{FromGeometricBlob, IntoGeometricBlob};
+///
+/// let blob = "hello, world".to_string().into_geometric_blob();
+/// assert_eq!(String::from_geometric_blob(&blob).as_deref(), Some("hello, world"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeometricBlob {
+    chunks: Vec<GA3>,
+}
+
+impl GeometricBlob {
+    /// Rebuild a blob from chunks, e.g. after deserialization
+    ///
+    /// The chunks are validated when decoded.
+    pub fn from_chunks(chunks: Vec<GA3>) -> Self {
+        Self { chunks }
+    }
+
+    /// Encode raw bytes
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut chunks = vec![Self::header(bytes.len(), BYTES_TAG)];
+        for chunk in bytes.chunks(BYTES_PER_CHUNK) {
+            let coeffs: Vec<f64> = (0..8)
+                .map(|i| {
+                    let start = (i * BYTES_PER_COEFFICIENT).min(chunk.len());
+                    let end = (start + BYTES_PER_COEFFICIENT).min(chunk.len());
+                    chunk[start..end]
+                        .iter()
+                        .rev()
+                        .fold(0u64, |acc, &b| (acc << 8) | b as u64) as f64
+                })
+                .collect();
+            chunks.push(GA3::from_coefficients(coeffs));
+        }
+        Self { chunks }
+    }
+
+    /// Encode a single multivector
+    pub fn from_multivector(mv: GA3) -> Self {
+        Self {
+            chunks: vec![Self::header(1, MULTIVECTOR_TAG), mv],
+        }
+    }
+
+    /// Encode a sequence of element blobs
+    pub fn from_elements<I>(elements: I) -> Self
+    where
+        I: IntoIterator<Item = GeometricBlob>,
+    {
+        let mut chunks = vec![GA3::zero()];
+        let mut len = 0;
+        for element in elements {
+            chunks.push(GA3::scalar(element.chunks.len() as f64));
+            chunks.extend(element.chunks);
+            len += 1;
+        }
+        chunks[0] = Self::header(len, ELEMENTS_TAG);
+        Self { chunks }
+    }
+
+    /// All chunks, header first
+    pub fn chunks(&self) -> &[GA3] {
+        &self.chunks
+    }
+
+    /// Consume the blob, returning its chunks
+    pub fn into_chunks(self) -> Vec<GA3> {
+        self.chunks
+    }
+
+    /// Decode a byte payload
+    ///
+    /// Returns `None` if the blob is not a well-formed byte payload.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        let len = self.payload_len(BYTES_TAG)?;
+        let payload = &self.chunks[1..];
+        if payload.len() != len.div_ceil(BYTES_PER_CHUNK) {
+            return None;
+        }
+
+        let mut bytes = Vec::with_capacity(payload.len() * BYTES_PER_CHUNK);
+        for chunk in payload {
+            for &coeff in chunk.as_slice() {
+                let packed = Self::exact_integer(coeff, 1u64 << 48)?;
+                bytes.extend((0..BYTES_PER_COEFFICIENT).map(|i| (packed >> (8 * i)) as u8));
+            }
+        }
+        if bytes[len..].iter().any(|&b| b != 0) {
+            return None;
+        }
+        bytes.truncate(len);
+        Some(bytes)
+    }
+
+    /// Decode a multivector payload
+    ///
+    /// Returns `None` if the blob is not a well-formed multivector payload.
+    pub fn to_multivector(&self) -> Option<&GA3> {
+        let len = self.payload_len(MULTIVECTOR_TAG)?;
+        match &self.chunks[1..] {
+            [mv] if len == 1 => Some(mv),
+            _ => None,
+        }
+    }
+
+    /// The element blobs of an element payload
+    ///
+    /// Returns `None` if the blob is not a well-formed element payload.
+    pub fn elements(&self) -> Option<Vec<GeometricBlob>> {
+        let len = self.payload_len(ELEMENTS_TAG)?;
+        let mut rest = &self.chunks[1..];
+        let mut elements = Vec::with_capacity(len.min(rest.len()));
+        for _ in 0..len {
+            let (count, tail) = rest.split_first()?;
+            let count = Self::exact_integer(count.get(0), tail.len() as u64 + 1)? as usize;
+            elements.push(Self::from_chunks(tail[..count].to_vec()));
+            rest = &tail[count..];
+        }
+        rest.is_empty().then_some(elements)
+    }
+
+    fn header(len: usize, tag: f64) -> GA3 {
+        GA3::from_coefficients(vec![len as f64, tag, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0])
+    }
+
+    /// Payload length from the header, if the header carries `tag`
+    fn payload_len(&self, tag: f64) -> Option<usize> {
+        let header = self.chunks.first()?;
+        if header.get(1) != tag {
+            return None;
+        }
+        Self::exact_integer(header.get(0), 1u64 << 53).map(|len| len as usize)
+    }
+
+    /// `value` as an integer below `bound`, if it is exactly one
+    fn exact_integer(value: f64, bound: u64) -> Option<u64> {
+        (value >= 0.0 && value < bound as f64 && value.fract() == 0.0).then_some(value as u64)
+    }
+}
+
+/// Convert a value into a lossless multi-multivector encoding
+///
+/// Use this instead of `IntoGeometric` when the value must be recovered
+/// exactly on the other side of a process boundary.
+pub trait IntoGeometricBlob {
+    /// Encode self as a `GeometricBlob`
+    fn into_geometric_blob(self) -> GeometricBlob;
+}
+
+/// Decode a value from its `GeometricBlob`
+///
+/// This is the inverse of `IntoGeometricBlob`.
+pub trait FromGeometricBlob: Sized {
+    /// Decode a value, or `None` if the blob doesn't encode one
+    fn from_geometric_blob(blob: &GeometricBlob) -> Option<Self>;
+}
+
+/// Strings are encoded as their UTF-8 bytes
+impl IntoGeometricBlob for String {
+    fn into_geometric_blob(self) -> GeometricBlob {
+        GeometricBlob::from_bytes(self.as_bytes())
+    }
+}
+
+impl FromGeometricBlob for String {
+    fn from_geometric_blob(blob: &GeometricBlob) -> Option<Self> {
+        String::from_utf8(blob.to_bytes()?).ok()
+    }
+}
+
+impl IntoGeometricBlob for Vec<u8> {
+    fn into_geometric_blob(self) -> GeometricBlob {
+        GeometricBlob::from_bytes(&self)
+    }
+}
+
+impl FromGeometricBlob for Vec<u8> {
+    fn from_geometric_blob(blob: &GeometricBlob) -> Option<Self> {
+        blob.to_bytes()
+    }
+}
+
+/// Values whose `IntoGeometric` encoding is exact are stored as that one
+/// multivector
+macro_rules! multivector_blob {
+    ($($t:ty),*) => {
+        $(
+            impl IntoGeometricBlob for $t {
+                fn into_geometric_blob(self) -> GeometricBlob {
+                    GeometricBlob::from_multivector(self.into_geometric())
+                }
+            }
+
+            impl FromGeometricBlob for $t {
+                fn from_geometric_blob(blob: &GeometricBlob) -> Option<Self> {
+                    blob.to_multivector().map(<$t>::from_geometric)
+                }
+            }
+        )*
+    };
+}
+
+multivector_blob!(i32, i64, f32, f64, usize, bool, (), GA3);
+
+/// Vectors nest the blob of each element, so they round-trip whenever the
+/// element type does
+impl<T: IntoGeometricBlob> IntoGeometricBlob for Vec<T> {
+    fn into_geometric_blob(self) -> GeometricBlob {
+        GeometricBlob::from_elements(self.into_iter().map(T::into_geometric_blob))
+    }
+}
+
+impl<T: FromGeometricBlob> FromGeometricBlob for Vec<T> {
+    fn from_geometric_blob(blob: &GeometricBlob) -> Option<Self> {
+        blob.elements()?
+            .iter()
+            .map(T::from_geometric_blob)
+            .collect()
+    }
+}
+
+/// Pairs are two nested element blobs
+impl<A: IntoGeometricBlob, B: IntoGeometricBlob> IntoGeometricBlob for (A, B) {
+    fn into_geometric_blob(self) -> GeometricBlob {
+        GeometricBlob::from_elements([self.0.into_geometric_blob(), self.1.into_geometric_blob()])
+    }
+}
+
+impl<A: FromGeometricBlob, B: FromGeometricBlob> FromGeometricBlob for (A, B) {
+    fn from_geometric_blob(blob: &GeometricBlob) -> Option<Self> {
+        match blob.elements()?.as_slice() {
+            [a, b] => Some((A::from_geometric_blob(a)?, B::from_geometric_blob(b)?)),
+            _ => None,
+        }
+    }
+}
+
+impl IntoGeometricBlob for GeometricBlob {
+    fn into_geometric_blob(self) -> GeometricBlob {
+        self
+    }
+}
+
+impl FromGeometricBlob for GeometricBlob {
+    fn from_geometric_blob(blob: &GeometricBlob) -> Option<Self> {
+        Some(blob.clone())
+    }
+}
+
+// ============================================================================
+// Support for #[derive(Geometric)]
+// ============================================================================
+
+/// Helpers called by code generated by `#[derive(Geometric)]`
+///
+/// Each field is placed with a list of `(field component, multivector
+/// component)` index pairs. Not part of the public API.
+#[doc(hidden)]
+pub mod derive_support {
+    use super::GA3;
+
+    /// Copy components of a field's multivector into the combined coefficients
+    pub fn place(coefficients: &mut [f64; 8], field: &GA3, layout: &[(usize, usize)]) {
+        for &(from, to) in layout {
+            coefficients[to] = field.get(from);
+        }
+    }
+
+    /// Recover a field's multivector from the combined multivector
+    pub fn extract(mv: &GA3, layout: &[(usize, usize)]) -> GA3 {
+        let mut coefficients = vec![0.0; 8];
+        for &(from, to) in layout {
+            coefficients[from] = mv.get(to);
+        }
+        GA3::from_coefficients(coefficients)
+    }
+
+    /// Build the combined multivector
+    pub fn finish(coefficients: [f64; 8]) -> GA3 {
+        GA3::from_coefficients(coefficients.to_vec())
+    }
+
+    /// Decode an enum variant index stored in the scalar component
+    pub fn variant_index(mv: &GA3) -> i64 {
+        mv.get(0).round() as i64
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -322,4 +662,67 @@ mod tests {
         let back: Option<i32> = Option::from_geometric(&mv);
         assert_eq!(back, None);
     }
+
+    #[test]
+    fn test_string_blob_roundtrip() {
+        for value in ["", "a", "hello, world", "ünïcødé ✓", &"x".repeat(1000)] {
+            let blob = value.to_string().into_geometric_blob();
+            assert_eq!(String::from_geometric_blob(&blob).as_deref(), Some(value));
+        }
+    }
+
+    #[test]
+    fn test_bytes_blob_roundtrip() {
+        let bytes: Vec<u8> = (0..=255).chain(0..=255).collect();
+        let blob = bytes.clone().into_geometric_blob();
+        assert_eq!(blob.chunks().len(), 1 + bytes.len().div_ceil(48));
+        assert_eq!(Vec::<u8>::from_geometric_blob(&blob), Some(bytes));
+    }
+
+    #[test]
+    fn test_vec_blob_roundtrip() {
+        let values = vec![1.5f64, -2.0, 1e300];
+        let blob = values.clone().into_geometric_blob();
+        assert_eq!(Vec::<f64>::from_geometric_blob(&blob), Some(values));
+
+        let points = vec![(1i32, 2i32), (3, 4)];
+        let blob = points.clone().into_geometric_blob();
+        assert_eq!(Vec::<(i32, i32)>::from_geometric_blob(&blob), Some(points));
+
+        let words = vec!["hello".to_string(), "world".to_string(), "x".repeat(100)];
+        let blob = words.clone().into_geometric_blob();
+        assert_eq!(Vec::<String>::from_geometric_blob(&blob), Some(words));
+
+        let nested = vec![vec![], vec![("a".to_string(), 1.5f64)]];
+        let blob = nested.clone().into_geometric_blob();
+        assert_eq!(
+            Vec::<Vec<(String, f64)>>::from_geometric_blob(&blob),
+            Some(nested)
+        );
+    }
+
+    #[test]
+    fn test_blob_rejects_malformed_input() {
+        let bytes = "text".to_string().into_geometric_blob();
+        assert_eq!(Vec::<i32>::from_geometric_blob(&bytes), None);
+
+        let elements = vec![1i32, 2].into_geometric_blob();
+        assert_eq!(String::from_geometric_blob(&elements), None);
+        assert_eq!(Vec::<String>::from_geometric_blob(&elements), None);
+
+        let mut chunks = elements.into_chunks();
+        chunks.pop();
+        let truncated = GeometricBlob::from_chunks(chunks);
+        assert_eq!(Vec::<i32>::from_geometric_blob(&truncated), None);
+
+        let mut chunks = bytes.into_chunks();
+        chunks.pop();
+        let truncated = GeometricBlob::from_chunks(chunks);
+        assert_eq!(String::from_geometric_blob(&truncated), None);
+
+        assert_eq!(
+            String::from_geometric_blob(&GeometricBlob::from_chunks(vec![])),
+            None
+        );
+    }
 }
@@ -0,0 +1,804 @@
+//! Lenses: projections that can write back
+//!
+//! A `Projection` reads a value out of a multivector. A `Lens` can also put
+//! a value back with `inject`, leaving the rest of the multivector alone,
+//! which is what a two-way binding needs: a slider editing the x component
+//! of a position reads through the lens and writes through it.
+//!
+//! Lenses obey the usual laws (up to rounding and clamping):
+//!
+//! - `project(inject(mv, v)) == v`: you get back what you put in
+//! - `inject(mv, project(mv)) == mv`: putting back what you read changes nothing
+//!
+//! Lenses compose with `zip` (two lenses side by side) and `then` (focus
+//! further into the output), and `GeometricState::focus` turns a lens into
+//! a writable `Focus` on a state. `Focus::to_behavior` makes that a
+//! `Behavior`, so it can be mapped and combined like any other.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::lens::LensExt;
+//! use cliffy_core::{GeometricState, VectorProjection};
+//!
+//! let position = GeometricState::from_vector(1.0, 2.0, 3.0);
+//!
+//! // A slider bound to the x component
+//! let x = position.focus(VectorProjection.then(|v| v.0, |v, x| (x, v.1, v.2)));
+//! assert_eq!(x.sample(), 1.0);
+//!
+//! x.set(5.0);
+//! assert_eq!(position.as_vector(), (5.0, 2.0, 3.0));
+//! ```
+
+use crate::algebra::EuclideanEmbedding;
+use crate::behavior::Behavior;
+use crate::color::{Hsl, Oklab, Oklch};
+use crate::geometric::{FromGeometric, IntoGeometric, GA3};
+use crate::projection::{
+    BivectorProjection, BoolProjection, ColorAlphaProjection, ColorProjection, HslProjection,
+    IntProjection, MagnitudeProjection, OklabColorProjection, OklabProjection, OklchProjection,
+    PointProjection, Position2DProjection, Position3DProjection, Projection, RotorAngleProjection,
+    ScalarProjection, TypedBivectorProjection, TypedVectorProjection, VectorProjection,
+};
+use crate::state::{GeometricState, GeometricSubscription};
+use amari_core::{Bivector, Multivector, Vector};
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread::{self, ThreadId};
+
+/// A projection that can write its output back into a multivector
+pub trait Lens<const P: usize = 3, const Q: usize = 0, const R: usize = 0>:
+    Projection<P, Q, R>
+{
+    /// Return `mv` with the projected part replaced by `value`
+    fn inject(&self, mv: &Multivector<P, Q, R>, value: Self::Output) -> Multivector<P, Q, R>;
+}
+
+/// Copy of `mv` with the given coefficients replaced
+fn with_coefficients<const P: usize, const Q: usize, const R: usize>(
+    mv: &Multivector<P, Q, R>,
+    values: &[(usize, f64)],
+) -> Multivector<P, Q, R> {
+    let mut result = mv.clone();
+    for &(index, value) in values {
+        result.set(index, value);
+    }
+    result
+}
+
+// ============================================================================
+// Built-in projections
+// ============================================================================
+
+impl<const P: usize, const Q: usize, const R: usize> Lens<P, Q, R> for ScalarProjection {
+    fn inject(&self, mv: &Multivector<P, Q, R>, value: f64) -> Multivector<P, Q, R> {
+        with_coefficients(mv, &[(0, value)])
+    }
+}
+
+impl<const P: usize, const Q: usize, const R: usize> Lens<P, Q, R> for IntProjection {
+    fn inject(&self, mv: &Multivector<P, Q, R>, value: i32) -> Multivector<P, Q, R> {
+        with_coefficients(mv, &[(0, value as f64)])
+    }
+}
+
+/// Writes 1.0 for true and 0.0 for false
+impl<const P: usize, const Q: usize, const R: usize> Lens<P, Q, R> for BoolProjection {
+    fn inject(&self, mv: &Multivector<P, Q, R>, value: bool) -> Multivector<P, Q, R> {
+        with_coefficients(mv, &[(0, if value { 1.0 } else { 0.0 })])
+    }
+}
+
+impl Lens for VectorProjection {
+    fn inject(&self, mv: &GA3, (x, y, z): (f64, f64, f64)) -> GA3 {
+        with_coefficients(mv, &[(1, x), (2, y), (4, z)])
+    }
+}
+
+impl Lens for Position2DProjection {
+    fn inject(&self, mv: &GA3, (x, y): (f64, f64)) -> GA3 {
+        with_coefficients(mv, &[(1, x), (2, y)])
+    }
+}
+
+impl Lens for Position3DProjection {
+    fn inject(&self, mv: &GA3, (x, y, z): (f64, f64, f64)) -> GA3 {
+        with_coefficients(mv, &[(1, x), (2, y), (4, z)])
+    }
+}
+
+impl Lens for BivectorProjection {
+    fn inject(&self, mv: &GA3, (xy, xz, yz): (f64, f64, f64)) -> GA3 {
+        with_coefficients(mv, &[(3, xy), (5, xz), (6, yz)])
+    }
+}
+
+impl Lens for TypedVectorProjection {
+    fn inject(&self, mv: &GA3, value: Vector<3, 0, 0>) -> GA3 {
+        let v = value.mv;
+        with_coefficients(mv, &[(1, v.get(1)), (2, v.get(2)), (4, v.get(4))])
+    }
+}
+
+impl Lens for TypedBivectorProjection {
+    fn inject(&self, mv: &GA3, value: Bivector<3, 0, 0>) -> GA3 {
+        with_coefficients(
+            mv,
+            &[(3, value.get(0)), (5, value.get(1)), (6, value.get(2))],
+        )
+    }
+}
+
+impl Lens for ColorProjection {
+    fn inject(&self, mv: &GA3, (r, g, b): (u8, u8, u8)) -> GA3 {
+        with_coefficients(mv, &[(0, r as f64), (1, g as f64), (2, b as f64)])
+    }
+}
+
+impl Lens for ColorAlphaProjection {
+    fn inject(&self, mv: &GA3, (r, g, b, a): (u8, u8, u8, u8)) -> GA3 {
+        with_coefficients(
+            mv,
+            &[(0, r as f64), (1, g as f64), (2, b as f64), (4, a as f64)],
+        )
+    }
+}
+
+impl Lens for OklabProjection {
+    fn inject(&self, mv: &GA3, value: Oklab) -> GA3 {
+        with_coefficients(mv, &[(0, value.l), (1, value.a), (2, value.b)])
+    }
+}
+
+impl Lens for OklchProjection {
+    fn inject(&self, mv: &GA3, value: Oklch) -> GA3 {
+        OklabProjection.inject(mv, value.into())
+    }
+}
+
+impl Lens for HslProjection {
+    fn inject(&self, mv: &GA3, value: Hsl) -> GA3 {
+        OklabProjection.inject(mv, value.into())
+    }
+}
+
+impl Lens for OklabColorProjection {
+    fn inject(&self, mv: &GA3, (r, g, b): (u8, u8, u8)) -> GA3 {
+        OklabProjection.inject(mv, Oklab::from_rgb8(r, g, b))
+    }
+}
+
+/// Moves the point to the new position, so in PGA3 and CGA3 the rest of
+/// the element is translated along with it
+impl<const P: usize, const Q: usize, const R: usize> Lens<P, Q, R> for PointProjection
+where
+    Multivector<P, Q, R>: EuclideanEmbedding,
+{
+    fn inject(
+        &self,
+        mv: &Multivector<P, Q, R>,
+        (x, y, z): (f64, f64, f64),
+    ) -> Multivector<P, Q, R> {
+        let (cx, cy, cz) = mv.to_point();
+        mv.translate(x - cx, y - cy, z - cz)
+    }
+}
+
+/// Rescales the multivector; a zero multivector becomes the scalar `value`
+impl<const P: usize, const Q: usize, const R: usize> Lens<P, Q, R> for MagnitudeProjection {
+    fn inject(&self, mv: &Multivector<P, Q, R>, value: f64) -> Multivector<P, Q, R> {
+        match mv.normalize() {
+            Some(unit) => &unit * value,
+            None => Multivector::scalar(value),
+        }
+    }
+}
+
+/// Keeps the rotation plane and sets the angle, replacing the scalar and
+/// bivector parts and leaving the other grades alone; without a plane (the
+/// identity) there is no rotation to change and the rotor is unchanged
+impl<const P: usize, const Q: usize, const R: usize> Lens<P, Q, R> for RotorAngleProjection {
+    fn inject(&self, mv: &Multivector<P, Q, R>, angle: f64) -> Multivector<P, Q, R> {
+        let bivector = mv.grade_projection(2);
+        let Some(plane) = bivector.normalize() else {
+            return mv.clone();
+        };
+        let half = angle / 2.0;
+        let rest = &(mv - &mv.grade_projection(0)) - &bivector;
+        &(&rest + &Multivector::scalar(half.cos())) + &(&plane * half.sin())
+    }
+}
+
+// ============================================================================
+// Combinators
+// ============================================================================
+
+/// Two lenses side by side
+///
+/// Projects to the pair of outputs; injecting writes the first value, then
+/// the second, so when the lenses overlap the second one wins.
+#[derive(Clone, Debug)]
+pub struct ZipLens<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> ZipLens<A, B> {
+    /// Combine two lenses
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<A, B, const P: usize, const Q: usize, const R: usize> Projection<P, Q, R> for ZipLens<A, B>
+where
+    A: Projection<P, Q, R>,
+    B: Projection<P, Q, R>,
+{
+    type Output = (A::Output, B::Output);
+
+    fn project(&self, mv: &Multivector<P, Q, R>) -> Self::Output {
+        (self.first.project(mv), self.second.project(mv))
+    }
+
+    fn name(&self) -> &str {
+        "zip"
+    }
+}
+
+impl<A, B, const P: usize, const Q: usize, const R: usize> Lens<P, Q, R> for ZipLens<A, B>
+where
+    A: Lens<P, Q, R>,
+    B: Lens<P, Q, R>,
+{
+    fn inject(
+        &self,
+        mv: &Multivector<P, Q, R>,
+        (first, second): Self::Output,
+    ) -> Multivector<P, Q, R> {
+        self.second.inject(&self.first.inject(mv, first), second)
+    }
+}
+
+/// A lens focused further into another lens's output
+///
+/// `get` reads the part of the outer value, `set` returns the outer value
+/// with that part replaced.
+pub struct ThenLens<L, G, S, U> {
+    outer: L,
+    get: G,
+    set: S,
+    _focus: PhantomData<fn() -> U>,
+}
+
+impl<L, G, S, U> ThenLens<L, G, S, U> {
+    /// Focus `outer` through `get` and `set`
+    pub fn new(outer: L, get: G, set: S) -> Self {
+        Self {
+            outer,
+            get,
+            set,
+            _focus: PhantomData,
+        }
+    }
+}
+
+impl<L, G, S, U, const P: usize, const Q: usize, const R: usize> Projection<P, Q, R>
+    for ThenLens<L, G, S, U>
+where
+    L: Projection<P, Q, R>,
+    G: Fn(L::Output) -> U + Send + Sync,
+    S: Send + Sync,
+{
+    type Output = U;
+
+    fn project(&self, mv: &Multivector<P, Q, R>) -> U {
+        (self.get)(self.outer.project(mv))
+    }
+
+    fn name(&self) -> &str {
+        "then"
+    }
+}
+
+impl<L, G, S, U, const P: usize, const Q: usize, const R: usize> Lens<P, Q, R>
+    for ThenLens<L, G, S, U>
+where
+    L: Lens<P, Q, R>,
+    G: Fn(L::Output) -> U + Send + Sync,
+    S: Fn(L::Output, U) -> L::Output + Send + Sync,
+{
+    fn inject(&self, mv: &Multivector<P, Q, R>, value: U) -> Multivector<P, Q, R> {
+        let outer = (self.set)(self.outer.project(mv), value);
+        self.outer.inject(mv, outer)
+    }
+}
+
+/// A custom lens defined by a pair of closures
+pub struct CustomLens<F, G, T>
+where
+    F: Fn(&GA3) -> T + Send + Sync,
+    G: Fn(&GA3, T) -> GA3 + Send + Sync,
+{
+    project_fn: F,
+    inject_fn: G,
+    name: String,
+}
+
+impl<F, G, T> CustomLens<F, G, T>
+where
+    F: Fn(&GA3) -> T + Send + Sync,
+    G: Fn(&GA3, T) -> GA3 + Send + Sync,
+{
+    /// Create a new custom lens
+    pub fn new(project_fn: F, inject_fn: G, name: impl Into<String>) -> Self {
+        Self {
+            project_fn,
+            inject_fn,
+            name: name.into(),
+        }
+    }
+}
+
+impl<F, G, T> Projection for CustomLens<F, G, T>
+where
+    F: Fn(&GA3) -> T + Send + Sync,
+    G: Fn(&GA3, T) -> GA3 + Send + Sync,
+{
+    type Output = T;
+
+    fn project(&self, mv: &GA3) -> T {
+        (self.project_fn)(mv)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<F, G, T> Lens for CustomLens<F, G, T>
+where
+    F: Fn(&GA3) -> T + Send + Sync,
+    G: Fn(&GA3, T) -> GA3 + Send + Sync,
+{
+    fn inject(&self, mv: &GA3, value: T) -> GA3 {
+        (self.inject_fn)(mv, value)
+    }
+}
+
+/// Combinator methods for GA3 lenses
+///
+/// In other algebras, build the combinators with `ZipLens::new` and
+/// `ThenLens::new`.
+pub trait LensExt: Lens + Sized {
+    /// Pair this lens with another (see `ZipLens`)
+    fn zip<B: Lens>(self, other: B) -> ZipLens<Self, B> {
+        ZipLens::new(self, other)
+    }
+
+    /// Focus into part of this lens's output (see `ThenLens`)
+    fn then<U, G, S>(self, get: G, set: S) -> ThenLens<Self, G, S, U>
+    where
+        G: Fn(Self::Output) -> U + Send + Sync,
+        S: Fn(Self::Output, U) -> Self::Output + Send + Sync,
+    {
+        ThenLens::new(self, get, set)
+    }
+}
+
+impl<L: Lens> LensExt for L {}
+
+// ============================================================================
+// Focus
+// ============================================================================
+
+/// A value that is only used on the thread that created it
+///
+/// State subscribers must be `Send + Sync`, while a `Behavior` is
+/// single-threaded. Other threads can't reach the value, and if the last
+/// handle is dropped on another thread the value is leaked, not dropped.
+struct ThreadBound<T> {
+    owner: ThreadId,
+    value: ManuallyDrop<T>,
+}
+
+// SAFETY: the value is only borrowed and dropped on `owner`
+unsafe impl<T> Send for ThreadBound<T> {}
+unsafe impl<T> Sync for ThreadBound<T> {}
+
+impl<T> ThreadBound<T> {
+    fn new(value: T) -> Self {
+        Self {
+            owner: thread::current().id(),
+            value: ManuallyDrop::new(value),
+        }
+    }
+
+    /// The value, if this is the thread that created it
+    fn get(&self) -> Option<&T> {
+        (thread::current().id() == self.owner).then_some(&*self.value)
+    }
+}
+
+impl<T> Drop for ThreadBound<T> {
+    fn drop(&mut self) {
+        if thread::current().id() == self.owner {
+            // SAFETY: the value is dropped once, on the thread that owns it
+            unsafe { ManuallyDrop::drop(&mut self.value) }
+        }
+    }
+}
+
+/// Unsubscribes from a state when dropped
+struct StateLink<const P: usize, const Q: usize, const R: usize>(
+    Option<GeometricSubscription<P, Q, R>>,
+);
+
+impl<const P: usize, const Q: usize, const R: usize> Drop for StateLink<P, Q, R> {
+    fn drop(&mut self) {
+        if let Some(subscription) = self.0.take() {
+            subscription.unsubscribe();
+        }
+    }
+}
+
+/// A writable view of part of a `GeometricState`, created with
+/// `GeometricState::focus`
+///
+/// It behaves like a behavior of the lens output that is backed by the
+/// state: `sample` projects the current state, `set` injects into it, and
+/// every focus on the same state (and the state's own subscribers) sees
+/// the change. Clones share the state and the lens.
+pub struct Focus<L, const P: usize = 3, const Q: usize = 0, const R: usize = 0> {
+    state: GeometricState<P, Q, R>,
+    lens: Arc<L>,
+}
+
+impl<L, const P: usize, const Q: usize, const R: usize> Clone for Focus<L, P, Q, R> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            lens: Arc::clone(&self.lens),
+        }
+    }
+}
+
+impl<L, const P: usize, const Q: usize, const R: usize> Focus<L, P, Q, R>
+where
+    L: Lens<P, Q, R> + 'static,
+{
+    /// Focus `state` through `lens`
+    pub fn new(state: &GeometricState<P, Q, R>, lens: L) -> Self {
+        Self {
+            state: state.clone(),
+            lens: Arc::new(lens),
+        }
+    }
+
+    /// The current value seen through the lens
+    pub fn sample(&self) -> L::Output {
+        self.state.project(self.lens.as_ref())
+    }
+
+    /// Write a value through the lens
+    ///
+    /// This is an explicit set of the state, so it also resets the
+    /// reference magnitude of a renormalisation policy.
+    pub fn set(&self, value: L::Output) {
+        self.state.set_with(|mv| self.lens.inject(mv, value));
+    }
+
+    /// Update the value through the lens
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(L::Output) -> L::Output,
+    {
+        self.state
+            .set_with(|mv| self.lens.inject(mv, f(self.lens.project(mv))));
+    }
+
+    /// Subscribe to the focused value
+    ///
+    /// The callback runs on every change of the state, with the value seen
+    /// through the lens (which may be unchanged).
+    pub fn subscribe<F>(&self, callback: F) -> GeometricSubscription<P, Q, R>
+    where
+        F: Fn(&L::Output) + Send + Sync + 'static,
+    {
+        let lens = Arc::clone(&self.lens);
+        self.state.subscribe(move |mv| callback(&lens.project(mv)))
+    }
+
+    /// A `Behavior` of the focused value, backed by the state
+    ///
+    /// The behavior follows the state, and setting it writes through the
+    /// lens, so it can be mapped, combined and bound like any behavior
+    /// while staying in sync with the state. A `Behavior` is
+    /// single-threaded: it follows changes to the state made on the thread
+    /// that created it. Dropping the behavior, or disposing the scope it
+    /// was created in, detaches it from the state.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cliffy_core::lens::LensExt;
+    /// use cliffy_core::{GeometricState, VectorProjection};
+    ///
+    /// let position = GeometricState::from_vector(1.0, 2.0, 3.0);
+    /// let x = position
+    ///     .focus(VectorProjection.then(|v| v.0, |v, x| (x, v.1, v.2)))
+    ///     .to_behavior();
+    /// let label = x.map(|x| format!("x = {}", x));
+    ///
+    /// x.set(5.0);
+    /// assert_eq!(position.as_vector(), (5.0, 2.0, 3.0));
+    /// assert_eq!(label.sample(), "x = 5");
+    /// ```
+    pub fn to_behavior(&self) -> Behavior<L::Output>
+    where
+        L::Output: IntoGeometric + FromGeometric + Clone + 'static,
+    {
+        let behavior = Behavior::new(self.sample());
+        // Set while one side updates the other, so the change doesn't echo
+        let busy = Rc::new(Cell::new(false));
+
+        // State to behavior, holding the behavior weakly
+        let handle = ThreadBound::new((behavior.weak_setter(), Rc::clone(&busy)));
+        let focus = self.clone();
+        let follow = self.state.subscribe(move |_| {
+            if let Some((set, busy)) = handle.get() {
+                if !busy.replace(true) {
+                    // Sampled, as an earlier subscriber may have written
+                    // the state since this notification was sent
+                    set(focus.sample());
+                    busy.set(false);
+                }
+            }
+        });
+
+        // Behavior to state. The write-back subscriber owns both
+        // subscriptions, so they go when it is dropped with the behavior
+        // or removed by its scope
+        let focus = self.clone();
+        let link = Rc::new(RefCell::new(None));
+        let link_clone = Rc::clone(&link);
+        let write_back = behavior.subscribe(move |value| {
+            let _link = &link_clone;
+            if !busy.replace(true) {
+                focus.set(value.clone());
+                busy.set(false);
+            }
+        });
+        *link.borrow_mut() = Some((write_back, StateLink(Some(follow))));
+
+        behavior
+    }
+
+    /// The state this focus reads and writes
+    pub fn state(&self) -> &GeometricState<P, Q, R> {
+        &self.state
+    }
+
+    /// The lens this focus looks through
+    pub fn lens(&self) -> &L {
+        &self.lens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+    use std::sync::Mutex;
+
+    fn sample_mv() -> GA3 {
+        GA3::from_coefficients(vec![0.5, 1.0, 2.0, 0.1, 3.0, 0.2, 0.3, 0.4])
+    }
+
+    fn assert_laws<L>(lens: &L, value: L::Output)
+    where
+        L: Lens,
+        L::Output: Clone + PartialEq + std::fmt::Debug,
+    {
+        let mv = sample_mv();
+        assert_eq!(lens.project(&lens.inject(&mv, value.clone())), value);
+        let unchanged = lens.inject(&mv, lens.project(&mv));
+        for i in 0..8 {
+            assert!(
+                (unchanged.get(i) - mv.get(i)).abs() < 1e-12,
+                "{}",
+                lens.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_builtin_lens_laws() {
+        assert_laws(&ScalarProjection, 4.0);
+        assert_laws(&VectorProjection, (4.0, 5.0, 6.0));
+        assert_laws(&Position2DProjection, (4.0, 5.0));
+        assert_laws(&Position3DProjection, (4.0, 5.0, 6.0));
+        assert_laws(&BivectorProjection, (0.4, 0.5, 0.6));
+        assert_laws(&PointProjection, (-1.0, 0.0, 7.0));
+
+        // Color and integer lenses read back what they write
+        let mv = sample_mv();
+        assert_eq!(
+            ColorProjection.project(&ColorProjection.inject(&mv, (1, 2, 3))),
+            (1, 2, 3)
+        );
+        assert_eq!(
+            ColorAlphaProjection.project(&ColorAlphaProjection.inject(&mv, (1, 2, 3, 4))),
+            (1, 2, 3, 4)
+        );
+        assert_eq!(IntProjection.project(&IntProjection.inject(&mv, -7)), -7);
+        assert!(BoolProjection.project(&BoolProjection.inject(&mv, true)));
+        assert_eq!(
+            OklabColorProjection.project(&OklabColorProjection.inject(&mv, (12, 200, 97))),
+            (12, 200, 97)
+        );
+        assert_laws(&OklabProjection, Oklab::new(0.5, 0.1, -0.1));
+    }
+
+    #[test]
+    fn test_typed_lenses() {
+        let mv = sample_mv();
+        let v = TypedVectorProjection.inject(&mv, Vector::from_components(7.0, 8.0, 9.0));
+        assert_eq!(VectorProjection.project(&v), (7.0, 8.0, 9.0));
+        assert_eq!(v.get(0), 0.5);
+
+        let b = TypedBivectorProjection.inject(&mv, Bivector::from_components(0.7, 0.8, 0.9));
+        assert_eq!(BivectorProjection.project(&b), (0.7, 0.8, 0.9));
+        assert_eq!(b.get(1), 1.0);
+    }
+
+    #[test]
+    fn test_magnitude_and_angle_lenses() {
+        let v = GA3::from_coefficients(vec![0.0, 3.0, 4.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        let scaled = MagnitudeProjection.inject(&v, 10.0);
+        assert!((MagnitudeProjection.project(&scaled) - 10.0).abs() < 1e-12);
+        assert!((scaled.get(1) - 6.0).abs() < 1e-12);
+
+        let rotor = crate::Rotor::xz(0.3);
+        let turned = RotorAngleProjection.inject(rotor.as_multivector(), PI / 2.0);
+        assert!((RotorAngleProjection.project(&turned) - PI / 2.0).abs() < 1e-12);
+        // Same plane
+        assert_eq!(turned.get(3), 0.0);
+        assert_eq!(turned.get(6), 0.0);
+
+        // Grades other than the rotor's are kept
+        let mixed = sample_mv();
+        let turned = RotorAngleProjection.inject(&mixed, 1.0);
+        assert!((RotorAngleProjection.project(&turned) - 1.0).abs() < 1e-12);
+        for i in [1, 2, 4, 7] {
+            assert_eq!(turned.get(i), mixed.get(i));
+        }
+        let plane = |mv: &GA3| (mv.get(3) / mv.get(6), mv.get(5) / mv.get(6));
+        let (a, b) = (plane(&turned), plane(&mixed));
+        assert!((a.0 - b.0).abs() < 1e-12 && (a.1 - b.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zip_and_then() {
+        let lens = ScalarProjection.zip(VectorProjection.then(|v| v.2, |v, z| (v.0, v.1, z)));
+        assert_laws(&lens, (9.0, -1.0));
+
+        let mv = lens.inject(&sample_mv(), (9.0, -1.0));
+        assert_eq!(VectorProjection.project(&mv), (1.0, 2.0, -1.0));
+
+        // Overlapping lenses: the second write wins
+        let overlap = ScalarProjection.zip(IntProjection);
+        assert_eq!(overlap.inject(&sample_mv(), (1.5, 3)).get(0), 3.0);
+    }
+
+    #[test]
+    fn test_custom_lens() {
+        let celsius = CustomLens::new(
+            |mv: &GA3| mv.get(0) - 273.15,
+            |mv: &GA3, c: f64| with_coefficients(mv, &[(0, c + 273.15)]),
+            "celsius",
+        );
+        let mv = celsius.inject(&GA3::zero(), 20.0);
+        assert!((mv.get(0) - 293.15).abs() < 1e-12);
+        assert_eq!(celsius.name(), "celsius");
+    }
+
+    #[test]
+    fn test_focus_two_way_binding() {
+        let position = GeometricState::from_vector(1.0, 2.0, 3.0);
+        let x = position.focus(VectorProjection.then(|v| v.0, |v, x| (x, v.1, v.2)));
+        let y = position.focus(VectorProjection.then(|v| v.1, |v, y| (v.0, y, v.2)));
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _sub = x.subscribe(move |x| seen_clone.lock().unwrap().push(*x));
+
+        x.set(10.0);
+        y.update(|y| y * 2.0);
+        assert_eq!(position.as_vector(), (10.0, 4.0, 3.0));
+
+        // Writes to the state show through the focus
+        position.set_vector(-1.0, 0.0, 0.0);
+        assert_eq!(x.sample(), -1.0);
+        assert_eq!(*seen.lock().unwrap(), vec![10.0, 10.0, -1.0]);
+    }
+
+    #[test]
+    fn test_focus_to_behavior() {
+        let position = GeometricState::from_vector(1.0, 2.0, 3.0);
+        let x = position
+            .focus(VectorProjection.then(|v| v.0, |v, x| (x, v.1, v.2)))
+            .to_behavior();
+        let doubled = x.map(|x| x * 2.0);
+
+        let calls = Arc::new(Mutex::new(0));
+        let calls_clone = calls.clone();
+        let _sub = x.subscribe(move |_| *calls_clone.lock().unwrap() += 1);
+
+        // Writing through the behavior updates the parent state, once
+        x.set(10.0);
+        assert_eq!(position.as_vector(), (10.0, 2.0, 3.0));
+        assert_eq!(doubled.sample(), 20.0);
+        assert_eq!(*calls.lock().unwrap(), 1);
+
+        // Writes to the state show through the behavior
+        position.set_vector(-1.0, 0.0, 0.0);
+        assert_eq!(x.sample(), -1.0);
+        assert_eq!(doubled.sample(), -2.0);
+        assert_eq!(*calls.lock().unwrap(), 2);
+
+        // A dropped behavior lets go of the state, and with it the lens
+        let marker = Arc::new(());
+        let held = Arc::clone(&marker);
+        let y = position
+            .focus(VectorProjection.then(
+                move |v| {
+                    let _held = &held;
+                    v.1
+                },
+                |v, y| (v.0, y, v.2),
+            ))
+            .to_behavior();
+        assert_eq!(Arc::strong_count(&marker), 2);
+        drop((x, doubled, y));
+        assert_eq!(Arc::strong_count(&marker), 1);
+        position.set_vector(4.0, 0.0, 0.0);
+    }
+
+    #[test]
+    fn test_focus_behaviors_write_each_other() {
+        let position = GeometricState::from_vector(1.0, 2.0, 3.0);
+        let x = position
+            .focus(VectorProjection.then(|v| v.0, |v, x| (x, v.1, v.2)))
+            .to_behavior();
+        let y = position
+            .focus(VectorProjection.then(|v| v.1, |v, y| (v.0, y, v.2)))
+            .to_behavior();
+
+        // y follows x
+        let y_clone = y.clone();
+        let _sub = x.subscribe(move |x| y_clone.set(x + 1.0));
+
+        position.set_vector(2.0, 0.0, 0.0);
+        assert_eq!(position.as_vector(), (2.0, 3.0, 0.0));
+        assert_eq!((x.sample(), y.sample()), (2.0, 3.0));
+
+        x.set(5.0);
+        assert_eq!(position.as_vector(), (5.0, 6.0, 0.0));
+        assert_eq!(y.sample(), 6.0);
+    }
+
+    #[test]
+    fn test_focus_pga_point() {
+        let point = GeometricState::<3, 0, 1>::from_point(1.0, 2.0, 3.0);
+        let focus = point.focus(PointProjection);
+        focus.set((0.0, 0.0, 1.0));
+
+        let (x, y, z) = point.as_point();
+        assert!(x.abs() < 1e-12 && y.abs() < 1e-12 && (z - 1.0).abs() < 1e-12);
+    }
+}
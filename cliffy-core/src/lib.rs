@@ -52,38 +52,84 @@
 //! - **Behavior<T>**: Time-varying values backed by geometric algebra
 //! - **Event<T>**: Discrete occurrences with geometric transformations
 //! - **GeometricState**: Explicit geometric operations (rotations, translations)
+//!   with optional renormalisation against floating-point drift
+//! - **PGA3 / CGA3**: Projective and conformal algebras where translations are versors
+//! - **Splines**: SQUAD, Catmull-Rom and Bezier paths through transform keyframes
+//! - **ConstraintSolver**: Gauss-Seidel / Newton solving of point constraints on states
 //! - **Projection**: Extract user types from geometric state
+//! - **Color**: OKLab, OKLCH and HSL colors that blend perceptually, with gamut mapping
+//! - **DataflowRuntime**: Incremental execution of dataflow graphs against bound states
+//! - **GraphDocument / GraphLoader**: Versioned serde format for shipping dataflow graphs
+//! - **Optimizer**: Constant folding, deduplication, transform fusion and dead node elimination
+//! - **GraphRecorder / to_dot / to_mermaid**: Record Behavior wiring and render dataflow graphs
+//! - **Lens / Focus**: Projections that write back, for two-way bindings
 //! - **Combinators**: `when`, `combine` for composition
+//! - **transaction**: Batch several updates into a single notification pass
+//! - **Scope**: Own subscriptions and derived behaviors; dropping it disposes them
+//! - **SyncBehavior<T> / SyncEvent<T>**: `Send + Sync` variants for multi-threaded use
+//! - **BehaviorVec<T> / BehaviorMap<K, V>**: Collections that emit fine-grained diffs
+//! - **Continuous time**: `time`, `integral`, `derivative` and `animate` driven by ticks
+//! - **Clock / Scheduler**: Pluggable time for event timestamps and time-based combinators
+//! - **GeometricBlob**: Lossless multi-multivector encoding for strings, bytes and vectors
 //!
 //! The geometric algebra foundation can be hidden from users (they work with
 //! familiar types like `i32`, `String`, `Vec<T>`) or exposed explicitly via
 //! `GeometricState` for advanced use cases.
 
+pub mod algebra;
 pub mod behavior;
+pub mod collections;
+pub mod color;
 pub mod combinators;
 pub mod component;
+pub mod constraint;
+pub mod continuous;
 pub mod dataflow;
 pub mod event;
 pub mod geometric;
+pub mod lens;
 pub mod projection;
+mod propagation;
+pub mod scope;
 pub mod state;
+pub mod sync;
+pub mod time;
 pub mod transforms;
 
 // Re-export main types - basic FRP
 pub use behavior::{behavior, Behavior, Subscription};
+pub use collections::{BehaviorMap, BehaviorVec, MapDiff, VecDiff};
 pub use combinators::{combine, when};
+pub use continuous::Easing;
 pub use event::{event, Event};
-pub use geometric::{FromGeometric, IntoGeometric, GA3};
+pub use geometric::{
+    FromGeometric, FromGeometricBlob, GeometricBlob, IntoGeometric, IntoGeometricBlob, CGA3, GA3,
+    PGA3,
+};
+
+// Re-export the derive macro
+#[cfg(feature = "derive")]
+pub use cliffy_derive::Geometric;
+pub use propagation::transaction;
+pub use scope::Scope;
 
 // Re-export geometric state types
+pub use color::{Hsl, Oklab, Oklch};
+pub use constraint::{Constraint, ConstraintSolver, SolveReport, SolverMethod};
+pub use lens::{CustomLens, Focus, Lens, LensExt, ThenLens, ZipLens};
 pub use projection::{
     BivectorProjection, BoolProjection, ColorAlphaProjection, ColorProjection, CustomProjection,
-    IntProjection, MagnitudeProjection, MappedProjection, Position2DProjection,
-    Position3DProjection, Projection, RotorAngleProjection, ScalarProjection,
-    TypedBivectorProjection, TypedVectorProjection, VectorProjection,
+    HslProjection, IntProjection, MagnitudeProjection, MappedProjection, OklabColorProjection,
+    OklabProjection, OklchProjection, PointProjection, Position2DProjection, Position3DProjection,
+    Projection, RotorAngleProjection, ScalarProjection, TypedBivectorProjection,
+    TypedVectorProjection, VectorProjection,
+};
+pub use state::{
+    DriftDiagnostics, DriftSample, GeometricState, GeometricSubscription, RenormalizationPolicy,
 };
-pub use state::{GeometricState, GeometricSubscription};
-pub use transforms::{Rotor, Transform, Translation, Versor};
+pub use sync::{SyncBehavior, SyncEvent, SyncSubscription};
+pub use time::{Clock, DefaultClock, ManualClock, Scheduler, VirtualScheduler};
+pub use transforms::{EulerOrder, Interpolation, Motor, Rotor, Transform, Translation, Versor};
 
 // Re-export component types
 pub use component::{
@@ -93,8 +139,9 @@ pub use component::{
 
 // Re-export dataflow types
 pub use dataflow::{
-    CombinerType, DataflowGraph, GraphBuilder, Node, NodeId, NodeKind, ProjectionSpec,
-    RotationPlane, SinkSpec, TransformType,
+    CombinerType, DataflowError, DataflowGraph, DataflowRuntime, DataflowValue, FormatError,
+    GraphBuilder, GraphDocument, GraphLoader, GraphRecorder, Node, NodeId, NodeKind,
+    ProjectionSpec, RotationPlane, SinkSpec, TransformType,
 };
 
 // Re-export Amari types for advanced users
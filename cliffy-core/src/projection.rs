@@ -10,21 +10,31 @@
 //! - `VectorProjection`: Extract the vector (grade 1) components
 //! - `PositionProjection`: Extract 3D position from vector components
 //! - `ColorProjection`: Interpret components as RGB color
+//! - `OklabProjection`, `OklchProjection`, `HslProjection`: Interpret
+//!   components as OKLab coordinates (see [`crate::color`])
+//! - `PointProjection`: Extract a 3D position in any algebra that embeds points
+//!
+//! `Projection` is generic over the algebra signature and defaults to GA3.
+//! Projections that only read the scalar or the norm work in every algebra.
 
+use crate::algebra::EuclideanEmbedding;
+use crate::color::{Hsl, Oklab, Oklch};
 use crate::geometric::GA3;
-use amari_core::{Bivector, Vector};
+use amari_core::{Bivector, Multivector, Vector};
 
 /// A projection extracts a user-facing type from a multivector.
 ///
 /// Projections are the observer side of geometric state.
 /// While state lives in geometric space, projections define
 /// how that state appears to the user.
-pub trait Projection: Send + Sync {
+pub trait Projection<const P: usize = 3, const Q: usize = 0, const R: usize = 0>:
+    Send + Sync
+{
     /// The output type of this projection
     type Output;
 
     /// Project a multivector to the output type
-    fn project(&self, mv: &GA3) -> Self::Output;
+    fn project(&self, mv: &Multivector<P, Q, R>) -> Self::Output;
 
     /// Name of this projection (for debugging)
     fn name(&self) -> &str;
@@ -34,10 +44,10 @@ pub trait Projection: Send + Sync {
 #[derive(Clone, Debug)]
 pub struct ScalarProjection;
 
-impl Projection for ScalarProjection {
+impl<const P: usize, const Q: usize, const R: usize> Projection<P, Q, R> for ScalarProjection {
     type Output = f64;
 
-    fn project(&self, mv: &GA3) -> f64 {
+    fn project(&self, mv: &Multivector<P, Q, R>) -> f64 {
         mv.get(0)
     }
 
@@ -50,10 +60,10 @@ impl Projection for ScalarProjection {
 #[derive(Clone, Debug)]
 pub struct IntProjection;
 
-impl Projection for IntProjection {
+impl<const P: usize, const Q: usize, const R: usize> Projection<P, Q, R> for IntProjection {
     type Output = i32;
 
-    fn project(&self, mv: &GA3) -> i32 {
+    fn project(&self, mv: &Multivector<P, Q, R>) -> i32 {
         mv.get(0) as i32
     }
 
@@ -66,10 +76,10 @@ impl Projection for IntProjection {
 #[derive(Clone, Debug)]
 pub struct BoolProjection;
 
-impl Projection for BoolProjection {
+impl<const P: usize, const Q: usize, const R: usize> Projection<P, Q, R> for BoolProjection {
     type Output = bool;
 
-    fn project(&self, mv: &GA3) -> bool {
+    fn project(&self, mv: &Multivector<P, Q, R>) -> bool {
         mv.get(0) > 0.5
     }
 
@@ -219,14 +229,105 @@ impl Projection for ColorAlphaProjection {
     }
 }
 
+/// Project components as an OKLab color
+///
+/// Uses scalar for lightness, e1 for a, e2 for b, the encoding of the
+/// `color` types, so states blended with `lerp` stay perceptually even
+#[derive(Clone, Debug)]
+pub struct OklabProjection;
+
+impl Projection for OklabProjection {
+    type Output = Oklab;
+
+    fn project(&self, mv: &GA3) -> Oklab {
+        Oklab::new(mv.get(0), mv.get(1), mv.get(2))
+    }
+
+    fn name(&self) -> &str {
+        "oklab"
+    }
+}
+
+/// Project OKLab components as an OKLCH color
+#[derive(Clone, Debug)]
+pub struct OklchProjection;
+
+impl Projection for OklchProjection {
+    type Output = Oklch;
+
+    fn project(&self, mv: &GA3) -> Oklch {
+        OklabProjection.project(mv).into()
+    }
+
+    fn name(&self) -> &str {
+        "oklch"
+    }
+}
+
+/// Project OKLab components as an HSL color, gamut mapped to sRGB
+#[derive(Clone, Debug)]
+pub struct HslProjection;
+
+impl Projection for HslProjection {
+    type Output = Hsl;
+
+    fn project(&self, mv: &GA3) -> Hsl {
+        OklabProjection.project(mv).into()
+    }
+
+    fn name(&self) -> &str {
+        "hsl"
+    }
+}
+
+/// Project OKLab components as 8-bit RGB, gamut mapped to sRGB
+///
+/// The perceptual counterpart of `ColorProjection`.
+#[derive(Clone, Debug)]
+pub struct OklabColorProjection;
+
+impl Projection for OklabColorProjection {
+    type Output = (u8, u8, u8);
+
+    fn project(&self, mv: &GA3) -> (u8, u8, u8) {
+        OklabProjection.project(mv).to_rgb8()
+    }
+
+    fn name(&self) -> &str {
+        "oklab_color"
+    }
+}
+
+/// Project a point in any algebra that embeds Euclidean positions
+///
+/// In GA3 this is the same as `Position3DProjection`; in PGA3 and CGA3 it
+/// reads the point back from its trivector or null-vector form.
+#[derive(Clone, Debug)]
+pub struct PointProjection;
+
+impl<const P: usize, const Q: usize, const R: usize> Projection<P, Q, R> for PointProjection
+where
+    Multivector<P, Q, R>: EuclideanEmbedding,
+{
+    type Output = (f64, f64, f64);
+
+    fn project(&self, mv: &Multivector<P, Q, R>) -> (f64, f64, f64) {
+        mv.to_point()
+    }
+
+    fn name(&self) -> &str {
+        "point"
+    }
+}
+
 /// Project the magnitude (norm) of the multivector
 #[derive(Clone, Debug)]
 pub struct MagnitudeProjection;
 
-impl Projection for MagnitudeProjection {
+impl<const P: usize, const Q: usize, const R: usize> Projection<P, Q, R> for MagnitudeProjection {
     type Output = f64;
 
-    fn project(&self, mv: &GA3) -> f64 {
+    fn project(&self, mv: &Multivector<P, Q, R>) -> f64 {
         mv.magnitude()
     }
 
@@ -241,10 +342,10 @@ impl Projection for MagnitudeProjection {
 #[derive(Clone, Debug)]
 pub struct RotorAngleProjection;
 
-impl Projection for RotorAngleProjection {
+impl<const P: usize, const Q: usize, const R: usize> Projection<P, Q, R> for RotorAngleProjection {
     type Output = f64;
 
-    fn project(&self, mv: &GA3) -> f64 {
+    fn project(&self, mv: &Multivector<P, Q, R>) -> f64 {
         // For a rotor R = cos(θ/2) + sin(θ/2)*B
         // The scalar part is cos(θ/2)
         let scalar = mv.get(0);
@@ -407,6 +508,26 @@ mod tests {
         assert_eq!(b, 100);
     }
 
+    #[test]
+    fn test_oklab_color_projection() {
+        use crate::geometric::IntoGeometric;
+
+        let mv = Oklch::new(0.7, 0.4, 150.0).into_geometric();
+        let (l, c, h) = {
+            let lch = OklchProjection.project(&mv);
+            (lch.l, lch.c, lch.h)
+        };
+        assert!((l - 0.7).abs() < 1e-12 && (c - 0.4).abs() < 1e-12 && (h - 150.0).abs() < 1e-9);
+
+        // Out of gamut: mapped rather than clipped per channel
+        let (r, g, b) = OklabColorProjection.project(&mv);
+        assert_eq!(Oklab::from_rgb8(r, g, b).to_rgb8(), (r, g, b));
+        assert!(g > r && g > b);
+
+        let hsl = HslProjection.project(&Oklab::from_rgb8(255, 0, 0).into_geometric());
+        assert!(hsl.h.abs() < 0.5 || (hsl.h - 360.0).abs() < 0.5);
+    }
+
     #[test]
     fn test_magnitude_projection() {
         let v = Vector::<3, 0, 0>::from_components(3.0, 4.0, 0.0);
@@ -452,4 +573,20 @@ mod tests {
         let mv = GA3::scalar(21.0);
         assert_eq!(proj.project(&mv), 42);
     }
+
+    #[test]
+    fn test_projections_in_other_algebras() {
+        use crate::algebra::EuclideanEmbedding;
+        use crate::geometric::{CGA3, PGA3};
+
+        let pga = PGA3::point(1.0, 2.0, 3.0);
+        let (x, y, z) = PointProjection.project(&pga);
+        assert!((x - 1.0).abs() < 1e-10 && (y - 2.0).abs() < 1e-10 && (z - 3.0).abs() < 1e-10);
+
+        let cga = CGA3::point(1.0, 2.0, 3.0);
+        let (x, y, z) = PointProjection.project(&cga);
+        assert!((x - 1.0).abs() < 1e-10 && (y - 2.0).abs() < 1e-10 && (z - 3.0).abs() < 1e-10);
+
+        assert!((ScalarProjection.project(&PGA3::scalar(4.0)) - 4.0).abs() < 1e-10);
+    }
 }
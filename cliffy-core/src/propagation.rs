@@ -0,0 +1,475 @@
+//! Glitch-free propagation for derived behaviors
+//!
+//! Derived behaviors (`map`, `combine`, `combine3`, ...) do not recompute
+//! eagerly inside their parents' notification loops. Instead each derived
+//! behavior is a `DerivedNode` with a *rank*: sources have rank 0 and a
+//! derived behavior sits one rank above its highest parent. Ranks are a
+//! topological order of the dependency graph, the same order
+//! `DataflowGraph::topological_sort` produces for an explicit graph.
+//!
+//! When a behavior changes, its dependents are queued by rank and drained
+//! lowest-rank-first once the change has finished notifying. A node queued
+//! through several parents (a diamond) is recomputed exactly once, after
+//! all of its parents are up to date, so observers never see a mix of old
+//! and new values.
+//!
+//! ```text
+//!        a (rank 0)
+//!       / \
+//!      b   c (rank 1)
+//!       \ /
+//!        d (rank 2)  <- recomputed once per update of `a`
+//! ```
+//!
+//! Ranks only ever grow. `Behavior::switch` raises its rank when it starts
+//! following a behavior ranked at or above it, and every dependent is
+//! raised with it, so the order stays topological as the graph changes.
+//!
+//! `transaction` extends this across several updates: notifications of
+//! every behavior changed inside the closure are deferred until it returns,
+//! then each changed behavior notifies exactly once.
+
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::rc::{Rc, Weak};
+
+/// Type alias for the pending queue to avoid clippy::type_complexity warning
+type PendingQueue = RefCell<BTreeMap<(usize, usize), Rc<dyn Fn()>>>;
+
+/// Type alias for deferred notifications, keyed by behavior identity
+type DeferredList = RefCell<Vec<(usize, Box<dyn FnOnce()>)>>;
+
+thread_local! {
+    static SCHEDULER: Scheduler = Scheduler::new();
+}
+
+/// Per-thread queue of pending recomputations
+struct Scheduler {
+    /// Pending recomputations ordered by (rank, node id)
+    queue: PendingQueue,
+    /// Number of active `hold` scopes; draining waits until this is zero
+    hold_depth: Cell<usize>,
+    /// Whether the queue is currently being drained
+    draining: Cell<bool>,
+    /// Next node ID
+    next_id: Cell<usize>,
+    /// Number of active `transaction` scopes
+    transaction_depth: Cell<usize>,
+    /// Notifications deferred by the current transaction, in first-change order
+    deferred: DeferredList,
+}
+
+impl Scheduler {
+    fn new() -> Self {
+        Self {
+            queue: RefCell::new(BTreeMap::new()),
+            hold_depth: Cell::new(0),
+            draining: Cell::new(false),
+            next_id: Cell::new(0),
+            transaction_depth: Cell::new(0),
+            deferred: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn next_id(&self) -> usize {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        id
+    }
+
+    fn pop(&self) -> Option<Rc<dyn Fn()>> {
+        self.queue.borrow_mut().pop_first().map(|(_, node)| node)
+    }
+
+    /// Recompute queued nodes in rank order until the queue is empty
+    fn drain(&self) {
+        if self.draining.get() || self.hold_depth.get() > 0 {
+            return;
+        }
+
+        // Reset the flag even if a recompute panics
+        struct Reset<'a>(&'a Cell<bool>);
+        impl Drop for Reset<'_> {
+            fn drop(&mut self) {
+                self.0.set(false);
+            }
+        }
+
+        self.draining.set(true);
+        let _reset = Reset(&self.draining);
+        while let Some(recompute) = self.pop() {
+            recompute();
+        }
+    }
+}
+
+/// The propagation rank of a behavior, and the ranks of its dependents
+pub(crate) struct Rank {
+    /// Orders nodes of equal rank
+    id: usize,
+    rank: Cell<usize>,
+    /// Dependents, keyed by the subscription that registered them
+    dependents: RefCell<Vec<(usize, Weak<Rank>)>>,
+}
+
+impl Rank {
+    pub(crate) fn new(rank: usize) -> Rc<Self> {
+        Rc::new(Self {
+            id: SCHEDULER.with(Scheduler::next_id),
+            rank: Cell::new(rank),
+            dependents: RefCell::new(Vec::new()),
+        })
+    }
+
+    pub(crate) fn get(&self) -> usize {
+        self.rank.get()
+    }
+
+    /// ID of the node, unique on this thread and never reused
+    pub(crate) fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Register a dependent that must stay ranked above this one
+    pub(crate) fn add_dependent(&self, key: usize, dependent: &Rc<Rank>) {
+        self.dependents
+            .borrow_mut()
+            .push((key, Rc::downgrade(dependent)));
+    }
+
+    pub(crate) fn remove_dependent(&self, key: usize) {
+        self.dependents.borrow_mut().retain(|(k, _)| *k != key);
+    }
+
+    /// Raise the rank to at least `rank`, and every dependent above it
+    ///
+    /// A recomputation that is already pending moves to its new place in
+    /// the queue.
+    pub(crate) fn raise(&self, rank: usize) {
+        let old = self.rank.get();
+        if old >= rank {
+            return;
+        }
+        self.rank.set(rank);
+        SCHEDULER.with(|scheduler| {
+            let mut queue = scheduler.queue.borrow_mut();
+            if let Some(recompute) = queue.remove(&(old, self.id)) {
+                queue.insert((rank, self.id), recompute);
+            }
+        });
+
+        let dependents: Vec<_> = self
+            .dependents
+            .borrow()
+            .iter()
+            .filter_map(|(_, dependent)| dependent.upgrade())
+            .collect();
+        for dependent in dependents {
+            dependent.raise(rank + 1);
+        }
+    }
+}
+
+/// A derived behavior's recomputation, scheduled by rank
+#[derive(Clone)]
+pub(crate) struct DerivedNode {
+    rank: Rc<Rank>,
+    recompute: Rc<dyn Fn()>,
+}
+
+impl DerivedNode {
+    /// Create a node that runs `recompute` whenever one of its parents changes
+    pub(crate) fn new<F>(rank: Rc<Rank>, recompute: F) -> Self
+    where
+        F: Fn() + 'static,
+    {
+        Self {
+            rank,
+            recompute: Rc::new(recompute),
+        }
+    }
+
+    /// The rank this node is scheduled at
+    pub(crate) fn rank(&self) -> &Rc<Rank> {
+        &self.rank
+    }
+
+    /// Get a handle that schedules this node without keeping it alive
+    ///
+    /// Parents hold their dependents through weak handles, so dropping a
+    /// derived behavior's node stops it from being recomputed.
+    pub(crate) fn downgrade(&self) -> WeakDerivedNode {
+        WeakDerivedNode {
+            rank: Rc::downgrade(&self.rank),
+            recompute: Rc::downgrade(&self.recompute),
+        }
+    }
+
+    /// Queue this node for recomputation
+    #[cfg(test)]
+    pub(crate) fn schedule(&self) {
+        self.downgrade().schedule();
+    }
+}
+
+/// A weak handle to a `DerivedNode`
+#[derive(Clone)]
+pub(crate) struct WeakDerivedNode {
+    rank: Weak<Rank>,
+    recompute: Weak<dyn Fn()>,
+}
+
+impl WeakDerivedNode {
+    /// Queue the node for recomputation, if it is still alive
+    ///
+    /// Queuing a node that is already pending is a no-op. The queue is
+    /// drained immediately unless a `hold` scope is active or a drain is
+    /// already in progress.
+    pub(crate) fn schedule(&self) {
+        let (Some(recompute), Some(rank)) = (self.recompute.upgrade(), self.rank.upgrade()) else {
+            return;
+        };
+        SCHEDULER.with(|scheduler| {
+            scheduler
+                .queue
+                .borrow_mut()
+                .entry((rank.get(), rank.id))
+                .or_insert(recompute);
+            scheduler.drain();
+        });
+    }
+}
+
+/// Run `f` with draining deferred until it returns
+///
+/// Behaviors wrap subscriber notification in `hold` so that every
+/// dependent is queued before any of them is recomputed.
+pub(crate) fn hold<R>(f: impl FnOnce() -> R) -> R {
+    SCHEDULER.with(|scheduler| scheduler.hold_depth.set(scheduler.hold_depth.get() + 1));
+
+    struct Release;
+    impl Drop for Release {
+        fn drop(&mut self) {
+            SCHEDULER.with(|scheduler| {
+                scheduler.hold_depth.set(scheduler.hold_depth.get() - 1);
+                scheduler.drain();
+            });
+        }
+    }
+
+    let _release = Release;
+    f()
+}
+
+/// Check whether a transaction is currently open on this thread
+pub(crate) fn in_transaction() -> bool {
+    SCHEDULER.with(|scheduler| scheduler.transaction_depth.get() > 0)
+}
+
+/// Defer a behavior's notification until the outermost transaction ends
+///
+/// `key` identifies the behavior; a behavior that changes several times
+/// inside one transaction is only notified once.
+pub(crate) fn defer<F>(key: usize, notify: F)
+where
+    F: FnOnce() + 'static,
+{
+    SCHEDULER.with(|scheduler| {
+        let mut deferred = scheduler.deferred.borrow_mut();
+        if !deferred.iter().any(|(k, _)| *k == key) {
+            deferred.push((key, Box::new(notify)));
+        }
+    });
+}
+
+/// Run `f` as a transaction, batching subscriber notifications
+///
+/// Every `Behavior` updated inside the closure takes its new value
+/// immediately (so `sample` sees it), but its subscribers are not notified
+/// until the outermost transaction returns. Each changed behavior then
+/// notifies exactly once with its final value, and derived behaviors are
+/// recomputed once after all of the changes have been applied.
+///
+/// Transactions may be nested; only the outermost one flushes. Derived
+/// behaviors are not recomputed until the flush, so sampling them inside a
+/// transaction returns their previous value.
+///
+/// # Example
+///
+/// ```rust
+/// use cliffy_core::{behavior, transaction};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// let first = behavior("Ada".to_string());
+/// let last = behavior("Lovelace".to_string());
+/// let full = first.combine(&last, |f, l| format!("{} {}", f, l));
+///
+/// let renders = Rc::new(Cell::new(0));
+/// let renders_clone = Rc::clone(&renders);
+/// let _sub = full.subscribe(move |_| renders_clone.set(renders_clone.get() + 1));
+///
+/// transaction(|| {
+///     first.set("Grace".to_string());
+///     last.set("Hopper".to_string());
+/// });
+///
+/// assert_eq!(full.sample(), "Grace Hopper");
+/// assert_eq!(renders.get(), 1);
+/// ```
+pub fn transaction<R>(f: impl FnOnce() -> R) -> R {
+    SCHEDULER.with(|scheduler| {
+        scheduler
+            .transaction_depth
+            .set(scheduler.transaction_depth.get() + 1)
+    });
+
+    struct Commit;
+    impl Drop for Commit {
+        fn drop(&mut self) {
+            let deferred = SCHEDULER.with(|scheduler| {
+                let depth = scheduler.transaction_depth.get() - 1;
+                scheduler.transaction_depth.set(depth);
+                if depth == 0 {
+                    std::mem::take(&mut *scheduler.deferred.borrow_mut())
+                } else {
+                    Vec::new()
+                }
+            });
+
+            // A panicking transaction discards its notifications rather
+            // than running subscribers during unwinding
+            if std::thread::panicking() {
+                return;
+            }
+
+            hold(|| {
+                for (_, notify) in deferred {
+                    notify();
+                }
+            });
+        }
+    }
+
+    let _commit = Commit;
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nodes_run_in_rank_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let nodes: Vec<DerivedNode> = [3, 1, 2]
+            .into_iter()
+            .map(|rank| {
+                let log = Rc::clone(&log);
+                DerivedNode::new(Rank::new(rank), move || log.borrow_mut().push(rank))
+            })
+            .collect();
+
+        hold(|| {
+            for node in &nodes {
+                node.schedule();
+            }
+        });
+
+        assert_eq!(*log.borrow(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_raise_requeues_pending_dependents() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let node = |name: &'static str, rank: usize| {
+            let log = Rc::clone(&log);
+            DerivedNode::new(Rank::new(rank), move || log.borrow_mut().push(name))
+        };
+        let (a, b, c) = (node("a", 1), node("b", 2), node("c", 3));
+        a.rank().add_dependent(0, b.rank());
+
+        hold(|| {
+            b.schedule();
+            c.schedule();
+            a.rank().raise(3);
+        });
+
+        assert_eq!(b.rank().get(), 4);
+        assert_eq!(*log.borrow(), vec!["c", "b"]);
+    }
+
+    #[test]
+    fn test_node_scheduled_twice_runs_once() {
+        let count = Rc::new(Cell::new(0));
+        let count_clone = Rc::clone(&count);
+        let node = DerivedNode::new(Rank::new(1), move || count_clone.set(count_clone.get() + 1));
+
+        hold(|| {
+            node.schedule();
+            node.schedule();
+        });
+
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn test_schedule_without_hold_runs_immediately() {
+        let count = Rc::new(Cell::new(0));
+        let count_clone = Rc::clone(&count);
+        let node = DerivedNode::new(Rank::new(1), move || count_clone.set(count_clone.get() + 1));
+
+        node.schedule();
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn test_transaction_defers_until_end() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        transaction(|| {
+            let log_clone = Rc::clone(&log);
+            defer(1, move || log_clone.borrow_mut().push("a"));
+            assert!(log.borrow().is_empty());
+            assert!(in_transaction());
+        });
+
+        assert!(!in_transaction());
+        assert_eq!(*log.borrow(), vec!["a"]);
+    }
+
+    #[test]
+    fn test_transaction_dedupes_by_key() {
+        let count = Rc::new(Cell::new(0));
+
+        transaction(|| {
+            for _ in 0..3 {
+                let count_clone = Rc::clone(&count);
+                defer(7, move || count_clone.set(count_clone.get() + 1));
+            }
+        });
+
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn test_nested_transaction_flushes_at_outermost() {
+        let count = Rc::new(Cell::new(0));
+
+        transaction(|| {
+            transaction(|| {
+                let count_clone = Rc::clone(&count);
+                defer(1, move || count_clone.set(count_clone.get() + 1));
+            });
+            assert_eq!(count.get(), 0);
+        });
+
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn test_transaction_returns_value() {
+        assert_eq!(transaction(|| 42), 42);
+    }
+}
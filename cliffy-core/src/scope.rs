@@ -0,0 +1,310 @@
+//! Scope - Ownership of reactive registrations
+//!
+//! A `Scope` collects every subscription and derived behavior created while
+//! it is running. Dropping the scope removes all of those registrations
+//! from their sources, even if handles to the derived behaviors are still
+//! held elsewhere (they simply stop updating).
+//!
+//! Scopes created while another scope is running become its children and
+//! are disposed along with it, so a component tree can own its reactive
+//! wiring the same way it owns its elements.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::{behavior, Scope};
+//!
+//! let count = behavior(0i32);
+//!
+//! let scope = Scope::new();
+//! let doubled = scope.run(|| count.map(|n| n * 2));
+//! assert_eq!(count.subscriber_count(), 1);
+//!
+//! drop(scope);
+//! assert_eq!(count.subscriber_count(), 0);
+//!
+//! // The derived behavior keeps its last value but no longer updates
+//! count.set(5);
+//! assert_eq!(doubled.sample(), 0);
+//! ```
+
+use crate::behavior::Subscription;
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+/// Type alias for disposer callbacks to avoid clippy::type_complexity warning
+type DisposerList = RefCell<Vec<Box<dyn Disposer>>>;
+
+/// Fewest disposers a scope holds before it prunes dead ones
+const MIN_PRUNE: usize = 16;
+
+/// A cleanup callback for something a scope owns
+trait Disposer {
+    /// Whether the owner is still alive; a dead one needs no cleanup
+    fn is_live(&self) -> bool;
+
+    /// Run the cleanup, if the owner is still alive
+    fn dispose(self: Box<Self>);
+}
+
+/// A disposer that holds its owner weakly
+struct OwnedDisposer<T, F> {
+    owner: Weak<T>,
+    dispose: F,
+}
+
+impl<T, F: FnOnce(Rc<T>)> Disposer for OwnedDisposer<T, F> {
+    fn is_live(&self) -> bool {
+        self.owner.strong_count() > 0
+    }
+
+    fn dispose(self: Box<Self>) {
+        let this = *self;
+        if let Some(owner) = this.owner.upgrade() {
+            (this.dispose)(owner);
+        }
+    }
+}
+
+thread_local! {
+    /// Scopes currently running on this thread, innermost last
+    static CURRENT: RefCell<Vec<Rc<ScopeInner>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// An owner for subscriptions and derived behaviors
+///
+/// See the [module documentation](self) for details.
+pub struct Scope {
+    inner: Rc<ScopeInner>,
+}
+
+struct ScopeInner {
+    /// Cleanup callbacks, run in reverse registration order on disposal
+    disposers: DisposerList,
+    /// Number of disposers at which dead ones are next pruned
+    prune_at: Cell<usize>,
+    /// Subscriptions kept alive by this scope
+    adopted: RefCell<Vec<Subscription>>,
+}
+
+impl ScopeInner {
+    fn dispose(&self) {
+        let adopted = std::mem::take(&mut *self.adopted.borrow_mut());
+        drop(adopted);
+
+        let disposers = std::mem::take(&mut *self.disposers.borrow_mut());
+        for disposer in disposers.into_iter().rev() {
+            disposer.dispose();
+        }
+    }
+
+    /// Register a disposer, first dropping those whose owner is gone
+    ///
+    /// Pruning waits until the list has doubled since the last prune, so
+    /// registering stays amortised O(1).
+    fn register(&self, disposer: Box<dyn Disposer>) {
+        let mut disposers = self.disposers.borrow_mut();
+        let dead = if disposers.len() >= self.prune_at.get() {
+            let (live, dead): (Vec<_>, Vec<_>) = std::mem::take(&mut *disposers)
+                .into_iter()
+                .partition(|disposer| disposer.is_live());
+            *disposers = live;
+            self.prune_at.set((disposers.len() * 2).max(MIN_PRUNE));
+            dead
+        } else {
+            Vec::new()
+        };
+        disposers.push(disposer);
+        drop(disposers);
+        // Dropped outside the borrow, in case a capture registers again
+        drop(dead);
+    }
+}
+
+impl Scope {
+    /// Create a new scope
+    ///
+    /// If another scope is currently running, the new scope becomes its
+    /// child and is disposed when the parent is.
+    pub fn new() -> Self {
+        let inner = Rc::new(ScopeInner {
+            disposers: RefCell::new(Vec::new()),
+            prune_at: Cell::new(MIN_PRUNE),
+            adopted: RefCell::new(Vec::new()),
+        });
+
+        on_dispose(&inner, |child| child.dispose());
+
+        Self { inner }
+    }
+
+    /// Run `f` with this scope as the current owner
+    ///
+    /// Subscriptions and derived behaviors created inside `f` are removed
+    /// when this scope is dropped.
+    pub fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        CURRENT.with(|current| current.borrow_mut().push(Rc::clone(&self.inner)));
+
+        struct Exit;
+        impl Drop for Exit {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.borrow_mut().pop());
+            }
+        }
+
+        let _exit = Exit;
+        f()
+    }
+
+    /// Keep a subscription alive until this scope is dropped
+    pub fn adopt(&self, subscription: Subscription) {
+        self.inner.adopted.borrow_mut().push(subscription);
+    }
+
+    /// Get the number of registrations this scope will remove on disposal
+    ///
+    /// Registrations whose owner has already been dropped don't count.
+    pub fn registration_count(&self) -> usize {
+        let disposers = self.inner.disposers.borrow();
+        disposers.iter().filter(|d| d.is_live()).count() + self.inner.adopted.borrow().len()
+    }
+
+    /// Dispose the scope now (equivalent to dropping it)
+    pub fn dispose(self) {}
+}
+
+impl Default for Scope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        self.inner.dispose();
+    }
+}
+
+/// Register a cleanup of `owner` with the currently running scope
+///
+/// The scope only holds `owner` weakly: `dispose` runs on disposal if the
+/// owner is still alive, and is dropped once it isn't. Does nothing when
+/// no scope is running.
+pub(crate) fn on_dispose<T, F>(owner: &Rc<T>, dispose: F)
+where
+    T: 'static,
+    F: FnOnce(Rc<T>) + 'static,
+{
+    let current = CURRENT.with(|current| current.borrow().last().cloned());
+    if let Some(scope) = current {
+        scope.register(Box::new(OwnedDisposer {
+            owner: Rc::downgrade(owner),
+            dispose,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::behavior::behavior;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_scope_disposes_derived() {
+        let source = behavior(1i32);
+        let scope = Scope::new();
+
+        let derived = scope.run(|| {
+            let a = source.map(|n| n + 1);
+            let b = source.map(|n| n * 2);
+            a.combine(&b, |x, y| x + y)
+        });
+
+        assert_eq!(source.subscriber_count(), 2);
+        assert!(derived.is_derived());
+
+        drop(scope);
+
+        assert_eq!(source.subscriber_count(), 0);
+        assert!(!derived.is_derived());
+    }
+
+    #[test]
+    fn test_scope_disposes_subscriptions() {
+        let source = behavior(0i32);
+        let calls = Rc::new(Cell::new(0));
+        let scope = Scope::new();
+
+        let calls_clone = Rc::clone(&calls);
+        let sub = scope.run(|| source.subscribe(move |_| calls_clone.set(calls_clone.get() + 1)));
+
+        source.set(1);
+        assert_eq!(calls.get(), 1);
+
+        scope.dispose();
+        assert!(!sub.is_active());
+
+        source.set(2);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_adopt_keeps_subscription_alive() {
+        let source = behavior(0i32);
+        let calls = Rc::new(Cell::new(0));
+        let scope = Scope::new();
+
+        let calls_clone = Rc::clone(&calls);
+        scope.adopt(source.subscribe(move |_| calls_clone.set(calls_clone.get() + 1)));
+
+        source.set(1);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(scope.registration_count(), 1);
+
+        drop(scope);
+        source.set(2);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(source.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_child_scope_disposed_with_parent() {
+        let source = behavior(0i32);
+        let parent = Scope::new();
+
+        let child = parent.run(Scope::new);
+        let _derived = child.run(|| source.map(|n| n + 1));
+        assert_eq!(source.subscriber_count(), 1);
+
+        drop(parent);
+        assert_eq!(source.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_dropped_registrations_are_pruned() {
+        let source = behavior(0i32);
+        let scope = Scope::new();
+
+        for n in 0..1000 {
+            let derived = scope.run(|| source.map(move |x| x + n));
+            let _sub = scope.run(|| derived.subscribe(|_| {}));
+        }
+        let kept = scope.run(|| source.map(|x| x + 1));
+
+        assert_eq!(scope.registration_count(), 1);
+        assert!(scope.inner.disposers.borrow().len() <= 2 * MIN_PRUNE);
+
+        drop(scope);
+        assert_eq!(source.subscriber_count(), 0);
+        assert!(!kept.is_derived());
+    }
+
+    #[test]
+    fn test_no_scope_is_noop() {
+        let source = behavior(0i32);
+        let derived = source.map(|n| n + 1);
+        source.set(1);
+        assert_eq!(derived.sample(), 2);
+    }
+}
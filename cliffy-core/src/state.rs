@@ -20,15 +20,51 @@
 //! // Project to user types
 //! let (x, y, z) = translated.as_vector();
 //! ```
+//!
+//! # Numerical drift
+//!
+//! Every in-place operation rounds, so a long run of `apply_rotor_mut` calls
+//! slowly changes the magnitude of the state. A `RenormalizationPolicy`
+//! rescales the state back to its reference magnitude every N operations or
+//! once the drift passes a tolerance, optionally zeroing grades the state
+//! should never have, and records the drift it sees:
+//!
+//! ```rust
+//! use cliffy_core::{GeometricState, RenormalizationPolicy, Rotor};
+//!
+//! let heading = GeometricState::from_vector(1.0, 0.0, 0.0);
+//! heading.set_renormalization(RenormalizationPolicy::new().on_drift(1e-12).keep_grades(&[1]));
+//!
+//! let step = Rotor::xy(0.01);
+//! for _ in 0..10_000 {
+//!     heading.apply_rotor_mut(&step);
+//! }
+//!
+//! assert!(heading.drift() <= 1e-12);
+//! let diagnostics = heading.drift_diagnostics().unwrap();
+//! assert_eq!(diagnostics.operations, 10_000);
+//! ```
 
+use crate::algebra::EuclideanEmbedding;
 use crate::geometric::GA3;
+use crate::lens::{Focus, Lens};
 use crate::projection::Projection;
 use crate::transforms::{Rotor, Transform, Translation, Versor};
-use amari_core::{Bivector, Vector};
+use amari_core::{Bivector, Multivector, Vector};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
+/// Type alias for a subscriber callback to avoid clippy::type_complexity warning
+type Subscriber<const P: usize, const Q: usize, const R: usize> =
+    Arc<dyn Fn(&Multivector<P, Q, R>) + Send + Sync>;
+
 /// Type alias for subscriber callbacks to avoid clippy::type_complexity warning
-type SubscriberList = Arc<Mutex<Vec<Box<dyn Fn(&GA3) + Send + Sync>>>>;
+type SubscriberList<const P: usize, const Q: usize, const R: usize> =
+    Arc<Mutex<Vec<(usize, Subscriber<P, Q, R>)>>>;
+
+/// Next subscription ID, shared by all states
+static NEXT_SUBSCRIPTION: AtomicUsize = AtomicUsize::new(0);
 
 /// State that lives in geometric space with explicit transformation support.
 ///
@@ -38,23 +74,21 @@ type SubscriberList = Arc<Mutex<Vec<Box<dyn Fn(&GA3) + Send + Sync>>>>;
 /// - Physics simulations
 /// - Explicit rotation/translation control
 /// - Advanced users who understand geometric algebra
+///
+/// The state is generic over the algebra signature and defaults to GA3;
+/// `GeometricState<3, 0, 1>` (PGA3) and `GeometricState<4, 1, 0>` (CGA3)
+/// hold points as their algebra embeds them (see `from_point`).
 #[derive(Clone)]
-pub struct GeometricState {
+pub struct GeometricState<const P: usize = 3, const Q: usize = 0, const R: usize = 0> {
     /// The underlying multivector
-    inner: Arc<Mutex<GA3>>,
+    inner: Arc<Mutex<Multivector<P, Q, R>>>,
     /// Subscribers for reactive updates
-    subscribers: SubscriberList,
+    subscribers: SubscriberList<P, Q, R>,
+    /// Renormalisation policy and drift diagnostics, if enabled
+    stability: Arc<Mutex<Option<Stability>>>,
 }
 
 impl GeometricState {
-    /// Create a new geometric state from a multivector
-    pub fn new(mv: GA3) -> Self {
-        Self {
-            inner: Arc::new(Mutex::new(mv)),
-            subscribers: Arc::new(Mutex::new(Vec::new())),
-        }
-    }
-
     /// Create state representing a scalar value
     pub fn from_scalar(value: f64) -> Self {
         Self::new(GA3::scalar(value))
@@ -87,21 +121,6 @@ impl GeometricState {
         Self::new(GA3::scalar(1.0))
     }
 
-    /// Get the underlying multivector (cloned)
-    pub fn multivector(&self) -> GA3 {
-        self.inner.lock().unwrap().clone()
-    }
-
-    /// Get the raw coefficient at a given index
-    pub fn get(&self, index: usize) -> f64 {
-        self.inner.lock().unwrap().get(index)
-    }
-
-    /// Get the scalar component
-    pub fn scalar(&self) -> f64 {
-        self.get(0)
-    }
-
     /// Get the vector components (e1, e2, e3) as a tuple
     pub fn as_vector(&self) -> (f64, f64, f64) {
         let mv = self.inner.lock().unwrap();
@@ -126,26 +145,6 @@ impl GeometricState {
         Bivector::from_components(mv.get(3), mv.get(5), mv.get(6))
     }
 
-    /// Get the magnitude (norm) of the state
-    pub fn magnitude(&self) -> f64 {
-        self.inner.lock().unwrap().magnitude()
-    }
-
-    /// Project using a projection type
-    pub fn project<P: Projection>(&self, projection: &P) -> P::Output {
-        let mv = self.inner.lock().unwrap();
-        projection.project(&mv)
-    }
-
-    /// Set the state to a new multivector
-    pub fn set(&self, mv: GA3) {
-        {
-            let mut inner = self.inner.lock().unwrap();
-            *inner = mv;
-        }
-        self.notify_subscribers();
-    }
-
     /// Set the scalar value
     pub fn set_scalar(&self, value: f64) {
         self.set(GA3::scalar(value));
@@ -166,15 +165,112 @@ impl GeometricState {
     pub fn set_typed_bivector(&self, b: &Bivector<3, 0, 0>) {
         self.set(GA3::from_bivector(b));
     }
+}
+
+impl<const P: usize, const Q: usize, const R: usize> GeometricState<P, Q, R> {
+    /// Create a new geometric state from a multivector
+    pub fn new(mv: Multivector<P, Q, R>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(mv)),
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+            stability: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Create state representing a point, embedded the way its algebra
+    /// represents points (a vector in GA3, a trivector in PGA3, a null
+    /// vector in CGA3)
+    pub fn from_point(x: f64, y: f64, z: f64) -> Self
+    where
+        Multivector<P, Q, R>: EuclideanEmbedding,
+    {
+        Self::new(Multivector::point(x, y, z))
+    }
+
+    /// Read the state back as a point (see `from_point`)
+    pub fn as_point(&self) -> (f64, f64, f64)
+    where
+        Multivector<P, Q, R>: EuclideanEmbedding,
+    {
+        self.inner.lock().unwrap().to_point()
+    }
+
+    /// Get the underlying multivector (cloned)
+    pub fn multivector(&self) -> Multivector<P, Q, R> {
+        self.inner.lock().unwrap().clone()
+    }
+
+    /// Get the raw coefficient at a given index
+    pub fn get(&self, index: usize) -> f64 {
+        self.inner.lock().unwrap().get(index)
+    }
+
+    /// Get the scalar component
+    pub fn scalar(&self) -> f64 {
+        self.get(0)
+    }
+
+    /// Get the magnitude (norm) of the state
+    pub fn magnitude(&self) -> f64 {
+        self.inner.lock().unwrap().magnitude()
+    }
+
+    /// Project using a projection type
+    pub fn project<Pr: Projection<P, Q, R>>(&self, projection: &Pr) -> Pr::Output {
+        let mv = self.inner.lock().unwrap();
+        projection.project(&mv)
+    }
+
+    /// A writable view of the part of the state `lens` sees
+    ///
+    /// Reads project the current state and writes inject into it, so a
+    /// form field bound to the focus and the state stay in sync. Use
+    /// `Focus::to_behavior` for a `Behavior` of the focused value.
+    pub fn focus<L: Lens<P, Q, R> + 'static>(&self, lens: L) -> Focus<L, P, Q, R> {
+        Focus::new(self, lens)
+    }
+
+    /// Set the state to a new multivector
+    ///
+    /// With a renormalisation policy, the new magnitude becomes the
+    /// reference (unless the policy fixes a target magnitude).
+    pub fn set(&self, mv: Multivector<P, Q, R>) {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            *inner = mv;
+            self.rebase(&inner);
+        }
+        self.notify_subscribers();
+    }
+
+    /// Set the state to a value computed from the current one, atomically
+    ///
+    /// Unlike `update` this is an explicit set (see `set`).
+    pub(crate) fn set_with<F>(&self, f: F)
+    where
+        F: FnOnce(&Multivector<P, Q, R>) -> Multivector<P, Q, R>,
+    {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            *inner = f(&inner);
+            self.rebase(&inner);
+        }
+        self.notify_subscribers();
+    }
 
     /// Update the state by applying a function
+    ///
+    /// This counts as an operation for the renormalisation policy, which
+    /// runs before subscribers are notified. The `apply_*_mut` methods all
+    /// go through here.
     pub fn update<F>(&self, f: F)
     where
-        F: FnOnce(&GA3) -> GA3,
+        F: FnOnce(&Multivector<P, Q, R>) -> Multivector<P, Q, R>,
     {
         {
             let mut inner = self.inner.lock().unwrap();
             *inner = f(&inner);
+            self.stabilize(&mut inner);
         }
         self.notify_subscribers();
     }
@@ -182,88 +278,100 @@ impl GeometricState {
     /// Apply a rotor transformation (rotation)
     ///
     /// This creates a new state; the original is unchanged.
-    pub fn apply_rotor(&self, rotor: &Rotor) -> GeometricState {
+    pub fn apply_rotor(&self, rotor: &Rotor<P, Q, R>) -> GeometricState<P, Q, R> {
         let mv = self.inner.lock().unwrap();
         let transformed = rotor.transform(&mv);
         GeometricState::new(transformed)
     }
 
     /// Apply a rotor transformation in-place
-    pub fn apply_rotor_mut(&self, rotor: &Rotor) {
+    pub fn apply_rotor_mut(&self, rotor: &Rotor<P, Q, R>) {
         self.update(|mv| rotor.transform(mv));
     }
 
     /// Apply a translation
     ///
     /// This creates a new state; the original is unchanged.
-    pub fn apply_translation(&self, translation: &Translation) -> GeometricState {
+    pub fn apply_translation(&self, translation: &Translation) -> GeometricState<P, Q, R>
+    where
+        Multivector<P, Q, R>: EuclideanEmbedding,
+    {
         let mv = self.inner.lock().unwrap();
         let transformed = translation.transform(&mv);
         GeometricState::new(transformed)
     }
 
     /// Apply a translation in-place
-    pub fn apply_translation_mut(&self, translation: &Translation) {
+    pub fn apply_translation_mut(&self, translation: &Translation)
+    where
+        Multivector<P, Q, R>: EuclideanEmbedding,
+    {
         self.update(|mv| translation.transform(mv));
     }
 
     /// Apply a versor transformation
     ///
     /// This creates a new state; the original is unchanged.
-    pub fn apply_versor(&self, versor: &Versor) -> GeometricState {
+    pub fn apply_versor(&self, versor: &Versor<P, Q, R>) -> GeometricState<P, Q, R> {
         let mv = self.inner.lock().unwrap();
         let transformed = versor.transform(&mv);
         GeometricState::new(transformed)
     }
 
     /// Apply a versor transformation in-place
-    pub fn apply_versor_mut(&self, versor: &Versor) {
+    pub fn apply_versor_mut(&self, versor: &Versor<P, Q, R>) {
         self.update(|mv| versor.transform(mv));
     }
 
     /// Apply a general transform (rotation + translation)
     ///
     /// This creates a new state; the original is unchanged.
-    pub fn apply_transform(&self, transform: &Transform) -> GeometricState {
+    pub fn apply_transform(&self, transform: &Transform<P, Q, R>) -> GeometricState<P, Q, R>
+    where
+        Multivector<P, Q, R>: EuclideanEmbedding,
+    {
         let mv = self.inner.lock().unwrap();
         let transformed = transform.transform(&mv);
         GeometricState::new(transformed)
     }
 
     /// Apply a general transform in-place
-    pub fn apply_transform_mut(&self, transform: &Transform) {
+    pub fn apply_transform_mut(&self, transform: &Transform<P, Q, R>)
+    where
+        Multivector<P, Q, R>: EuclideanEmbedding,
+    {
         self.update(|mv| transform.transform(mv));
     }
 
     /// Add another state (geometric addition)
-    pub fn add(&self, other: &GeometricState) -> GeometricState {
+    pub fn add(&self, other: &GeometricState<P, Q, R>) -> GeometricState<P, Q, R> {
         let a = self.inner.lock().unwrap();
         let b = other.inner.lock().unwrap();
         GeometricState::new(&*a + &*b)
     }
 
     /// Subtract another state
-    pub fn sub(&self, other: &GeometricState) -> GeometricState {
+    pub fn sub(&self, other: &GeometricState<P, Q, R>) -> GeometricState<P, Q, R> {
         let a = self.inner.lock().unwrap();
         let b = other.inner.lock().unwrap();
         GeometricState::new(&*a - &*b)
     }
 
     /// Scale by a scalar value
-    pub fn scale(&self, factor: f64) -> GeometricState {
+    pub fn scale(&self, factor: f64) -> GeometricState<P, Q, R> {
         let mv = self.inner.lock().unwrap();
         GeometricState::new(&*mv * factor)
     }
 
     /// Geometric product with another state
-    pub fn geometric_product(&self, other: &GeometricState) -> GeometricState {
+    pub fn geometric_product(&self, other: &GeometricState<P, Q, R>) -> GeometricState<P, Q, R> {
         let a = self.inner.lock().unwrap();
         let b = other.inner.lock().unwrap();
         GeometricState::new(a.geometric_product(&b))
     }
 
     /// Normalize the state to unit magnitude
-    pub fn normalize(&self) -> Option<GeometricState> {
+    pub fn normalize(&self) -> Option<GeometricState<P, Q, R>> {
         let mv = self.inner.lock().unwrap();
         mv.normalize().map(GeometricState::new)
     }
@@ -274,6 +382,7 @@ impl GeometricState {
         match inner.normalize() {
             Some(normalized) => {
                 *inner = normalized;
+                self.rebase(&inner);
                 drop(inner);
                 self.notify_subscribers();
                 true
@@ -283,13 +392,13 @@ impl GeometricState {
     }
 
     /// Get the reverse (reversion) of this state
-    pub fn reverse(&self) -> GeometricState {
+    pub fn reverse(&self) -> GeometricState<P, Q, R> {
         let mv = self.inner.lock().unwrap();
         GeometricState::new(mv.reverse())
     }
 
     /// Linear interpolation to another state
-    pub fn lerp(&self, other: &GeometricState, t: f64) -> GeometricState {
+    pub fn lerp(&self, other: &GeometricState<P, Q, R>, t: f64) -> GeometricState<P, Q, R> {
         let a = self.inner.lock().unwrap();
         let b = other.inner.lock().unwrap();
 
@@ -302,7 +411,7 @@ impl GeometricState {
     /// Spherical linear interpolation (for rotor-like states)
     ///
     /// This assumes both states are unit rotors.
-    pub fn slerp(&self, other: &GeometricState, t: f64) -> GeometricState {
+    pub fn slerp(&self, other: &GeometricState<P, Q, R>, t: f64) -> GeometricState<P, Q, R> {
         // Convert to rotors and use rotor SLERP
         let a_mv = self.inner.lock().unwrap();
         let b_mv = other.inner.lock().unwrap();
@@ -314,14 +423,138 @@ impl GeometricState {
         GeometricState::new(interpolated.as_multivector().clone())
     }
 
+    /// Install a renormalisation policy, replacing any previous one
+    ///
+    /// The current magnitude becomes the reference (unless the policy
+    /// fixes a target magnitude) and diagnostics start afresh. The policy
+    /// is shared by clones of this state.
+    pub fn set_renormalization(&self, policy: RenormalizationPolicy) {
+        let magnitude = self.magnitude();
+        let reference = policy.target.unwrap_or(magnitude);
+        *self.stability.lock().unwrap() = Some(Stability {
+            policy,
+            reference,
+            since_renormalization: 0,
+            diagnostics: DriftDiagnostics::default(),
+        });
+    }
+
+    /// Remove the renormalisation policy and its diagnostics
+    pub fn clear_renormalization(&self) {
+        *self.stability.lock().unwrap() = None;
+    }
+
+    /// Relative drift of the squared magnitude from the reference,
+    /// `|M M̃ / m² - 1|`
+    ///
+    /// For a rotor state without a policy the reference is 1, so this is
+    /// `|R R̃ - 1|`.
+    pub fn drift(&self) -> f64 {
+        let reference = self
+            .stability
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(1.0, |s| s.reference);
+        relative_drift(self.magnitude(), reference)
+    }
+
+    /// Drift recorded since the policy was installed, or `None` without one
+    pub fn drift_diagnostics(&self) -> Option<DriftDiagnostics> {
+        self.stability
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|s| s.diagnostics.clone())
+    }
+
+    /// Renormalise now: zero unwanted grades and restore the reference
+    /// magnitude (1 without a policy)
+    ///
+    /// Returns false if the state is zero and can't be rescaled.
+    pub fn renormalize_mut(&self) -> bool {
+        let renormalized = {
+            let mut inner = self.inner.lock().unwrap();
+            let mut stability = self.stability.lock().unwrap();
+            let (grades, reference) = match stability.as_ref() {
+                Some(s) => (s.policy.grades.clone(), s.reference),
+                None => (None, 1.0),
+            };
+            if let Some(grades) = grades {
+                *inner = keep_grades(&inner, &grades);
+            }
+            let renormalized = rescale(&mut inner, reference);
+            if let (true, Some(s)) = (renormalized, stability.as_mut()) {
+                s.since_renormalization = 0;
+                s.diagnostics.renormalizations += 1;
+            }
+            renormalized
+        };
+        self.notify_subscribers();
+        renormalized
+    }
+
+    /// Apply the policy after an operation
+    fn stabilize(&self, mv: &mut Multivector<P, Q, R>) {
+        let mut guard = self.stability.lock().unwrap();
+        let Some(stability) = guard.as_mut() else {
+            return;
+        };
+        let policy = &stability.policy;
+
+        if let Some(grades) = &policy.grades {
+            *mv = keep_grades(mv, grades);
+        }
+
+        let magnitude = mv.magnitude();
+        let drift = relative_drift(magnitude, stability.reference);
+        stability.since_renormalization += 1;
+
+        let diagnostics = &mut stability.diagnostics;
+        diagnostics.operations += 1;
+        diagnostics.last_drift = drift;
+        diagnostics.max_drift = diagnostics.max_drift.max(drift);
+        if policy.history > 0 {
+            if diagnostics.history.len() == policy.history {
+                diagnostics.history.pop_front();
+            }
+            diagnostics.history.push_back(DriftSample {
+                operation: diagnostics.operations,
+                magnitude,
+                drift,
+            });
+        }
+
+        let due = policy
+            .every
+            .is_some_and(|n| stability.since_renormalization >= n)
+            || policy.tolerance.is_some_and(|epsilon| drift > epsilon);
+        if due && rescale(mv, stability.reference) {
+            stability.since_renormalization = 0;
+            stability.diagnostics.renormalizations += 1;
+        }
+    }
+
+    /// Take the magnitude of an explicitly set value as the new reference
+    fn rebase(&self, mv: &Multivector<P, Q, R>) {
+        if let Some(stability) = self.stability.lock().unwrap().as_mut() {
+            if stability.policy.target.is_none() {
+                stability.reference = mv.magnitude();
+            }
+            stability.since_renormalization = 0;
+        }
+    }
+
     /// Subscribe to state changes
-    pub fn subscribe<F>(&self, callback: F) -> GeometricSubscription
+    pub fn subscribe<F>(&self, callback: F) -> GeometricSubscription<P, Q, R>
     where
-        F: Fn(&GA3) + Send + Sync + 'static,
+        F: Fn(&Multivector<P, Q, R>) + Send + Sync + 'static,
     {
-        let mut subs = self.subscribers.lock().unwrap();
-        let id = subs.len();
-        subs.push(Box::new(callback));
+        let id = NEXT_SUBSCRIPTION.fetch_add(1, Ordering::Relaxed);
+        self.subscribers
+            .lock()
+            .unwrap()
+            .push((id, Arc::new(callback)));
 
         GeometricSubscription {
             id,
@@ -330,16 +563,28 @@ impl GeometricState {
     }
 
     /// Notify all subscribers of a state change
+    ///
+    /// Neither the state nor its subscriber list is locked while
+    /// subscribers run, so they can read and write the state (directly or
+    /// through a `Focus` behavior) and subscribe or unsubscribe. A
+    /// subscriber that writes the state makes `mv` stale for the
+    /// subscribers after it, which see the write in their own notification.
     fn notify_subscribers(&self) {
-        let mv = self.inner.lock().unwrap();
-        let subs = self.subscribers.lock().unwrap();
-        for callback in subs.iter() {
+        let mv = self.inner.lock().unwrap().clone();
+        let subs: Vec<Subscriber<P, Q, R>> = self
+            .subscribers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, callback)| Arc::clone(callback))
+            .collect();
+        for callback in subs {
             callback(&mv);
         }
     }
 }
 
-impl std::fmt::Debug for GeometricState {
+impl<const P: usize, const Q: usize, const R: usize> std::fmt::Debug for GeometricState<P, Q, R> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mv = self.inner.lock().unwrap();
         write!(f, "GeometricState({:?})", mv)
@@ -347,24 +592,169 @@ impl std::fmt::Debug for GeometricState {
 }
 
 /// A subscription handle for geometric state changes
-pub struct GeometricSubscription {
+pub struct GeometricSubscription<const P: usize = 3, const Q: usize = 0, const R: usize = 0> {
     id: usize,
-    subscribers: SubscriberList,
+    subscribers: SubscriberList<P, Q, R>,
 }
 
-impl GeometricSubscription {
+impl<const P: usize, const Q: usize, const R: usize> GeometricSubscription<P, Q, R> {
     /// Unsubscribe from updates
     ///
-    /// Note: This doesn't actually remove the callback (to maintain indices),
-    /// but marks it as inactive. In practice, subscriptions typically last
-    /// for the lifetime of components.
+    /// Dropping the handle without calling this keeps the subscription.
+    /// A notification already running on another thread may still call
+    /// the callback once.
     pub fn unsubscribe(self) {
-        // In a more sophisticated implementation, we'd mark the slot as inactive
-        // For now, we just drop the handle
-        let _ = (self.id, self.subscribers);
+        let removed = {
+            let mut subs = self.subscribers.lock().unwrap();
+            subs.iter()
+                .position(|(id, _)| *id == self.id)
+                .map(|index| subs.remove(index))
+        };
+        // Dropped outside the lock, as dropping a callback may unsubscribe others
+        drop(removed);
     }
 }
 
+/// When and how a `GeometricState` corrects numerical drift
+///
+/// A policy compares the state's magnitude after each in-place operation
+/// with a reference magnitude (the magnitude when it was installed or last
+/// set, or a fixed target) and rescales the state back to it. Without
+/// `every` or `on_drift` it only records diagnostics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenormalizationPolicy {
+    every: Option<usize>,
+    tolerance: Option<f64>,
+    grades: Option<Vec<usize>>,
+    target: Option<f64>,
+    history: usize,
+}
+
+impl RenormalizationPolicy {
+    /// A policy that records drift but never corrects it
+    pub fn new() -> Self {
+        Self {
+            every: None,
+            tolerance: None,
+            grades: None,
+            target: None,
+            history: 64,
+        }
+    }
+
+    /// Policy for rotor states: keep the even grades, hold unit magnitude
+    /// and renormalise as soon as `|R R̃ - 1|` exceeds 1e-12
+    pub fn rotor() -> Self {
+        Self::new()
+            .keep_grades(&[0, 2])
+            .with_target_magnitude(1.0)
+            .on_drift(1e-12)
+    }
+
+    /// Renormalise after every `operations` in-place operations
+    pub fn every(mut self, operations: usize) -> Self {
+        self.every = Some(operations.max(1));
+        self
+    }
+
+    /// Renormalise whenever the drift exceeds `epsilon`
+    pub fn on_drift(mut self, epsilon: f64) -> Self {
+        self.tolerance = Some(epsilon);
+        self
+    }
+
+    /// Zero every grade not in `grades` after each operation
+    pub fn keep_grades(mut self, grades: &[usize]) -> Self {
+        self.grades = Some(grades.to_vec());
+        self
+    }
+
+    /// Hold this magnitude instead of the one the state had when set
+    pub fn with_target_magnitude(mut self, magnitude: f64) -> Self {
+        self.target = Some(magnitude);
+        self
+    }
+
+    /// Number of recent samples kept in `DriftDiagnostics::history`
+    pub fn with_history(mut self, samples: usize) -> Self {
+        self.history = samples;
+        self
+    }
+}
+
+impl Default for RenormalizationPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Drift recorded by a `RenormalizationPolicy`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DriftDiagnostics {
+    /// In-place operations since the policy was installed
+    pub operations: usize,
+    /// Times the state was rescaled
+    pub renormalizations: usize,
+    /// Drift measured after the latest operation, before correction
+    pub last_drift: f64,
+    /// Largest drift measured
+    pub max_drift: f64,
+    /// The most recent samples, oldest first
+    pub history: VecDeque<DriftSample>,
+}
+
+/// Drift measured after one operation, before any correction
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DriftSample {
+    /// Operation count when the sample was taken (starting at 1)
+    pub operation: usize,
+    /// Magnitude of the state
+    pub magnitude: f64,
+    /// Relative drift from the reference magnitude
+    pub drift: f64,
+}
+
+/// Installed policy with its running state
+struct Stability {
+    policy: RenormalizationPolicy,
+    reference: f64,
+    since_renormalization: usize,
+    diagnostics: DriftDiagnostics,
+}
+
+/// `|m² / reference² - 1|`, or `m²` for a zero reference
+fn relative_drift(magnitude: f64, reference: f64) -> f64 {
+    if reference < 1e-12 {
+        magnitude * magnitude
+    } else {
+        (magnitude * magnitude / (reference * reference) - 1.0).abs()
+    }
+}
+
+/// Scale `mv` to `magnitude`; false if it is zero
+fn rescale<const P: usize, const Q: usize, const R: usize>(
+    mv: &mut Multivector<P, Q, R>,
+    magnitude: f64,
+) -> bool {
+    match mv.normalize() {
+        Some(unit) => {
+            *mv = &unit * magnitude;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Sum of the given grade projections of `mv`
+fn keep_grades<const P: usize, const Q: usize, const R: usize>(
+    mv: &Multivector<P, Q, R>,
+    grades: &[usize],
+) -> Multivector<P, Q, R> {
+    grades.iter().fold(Multivector::zero(), |acc, &grade| {
+        &acc + &mv.grade_projection(grade)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -473,6 +863,32 @@ mod tests {
         assert_eq!(call_count.load(Ordering::SeqCst), 2);
     }
 
+    #[test]
+    fn test_unsubscribe() {
+        let state = GeometricState::from_scalar(0.0);
+        let call_count = Arc::new(AtomicUsize::new(0));
+        let call_count_clone = call_count.clone();
+        let sub = state.subscribe(move |_mv| {
+            call_count_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        // Subscribers can write the state they are notified by
+        let echo = state.clone();
+        let _echo = state.subscribe(move |mv| {
+            if mv.get(0) < 2.0 {
+                echo.set_scalar(mv.get(0) + 1.0);
+            }
+        });
+
+        state.set_scalar(1.0);
+        assert_eq!(state.scalar(), 2.0);
+        assert_eq!(call_count.load(Ordering::SeqCst), 2);
+
+        sub.unsubscribe();
+        state.set_scalar(5.0);
+        assert_eq!(call_count.load(Ordering::SeqCst), 2);
+    }
+
     #[test]
     fn test_typed_vector_accessors() {
         let state = GeometricState::from_vector(1.0, 2.0, 3.0);
@@ -527,4 +943,107 @@ mod tests {
         assert!((x - 0.6).abs() < 1e-10);
         assert!((y - 0.8).abs() < 1e-10);
     }
+
+    /// A rotor slightly off unit magnitude, as accumulated rounding leaves it
+    fn drifting_rotor() -> Rotor {
+        Rotor::from_multivector(Rotor::xy(0.1).as_multivector() * (1.0 + 1e-6))
+    }
+
+    #[test]
+    fn test_drift_accumulates_without_policy() {
+        let state = GeometricState::from_vector(1.0, 0.0, 0.0);
+        for _ in 0..1000 {
+            state.apply_rotor_mut(&drifting_rotor());
+        }
+        assert!(state.magnitude() > 1.001);
+        assert!(state.drift() > 1e-3);
+        assert!(state.drift_diagnostics().is_none());
+    }
+
+    #[test]
+    fn test_renormalize_every_n_operations() {
+        let state = GeometricState::from_vector(2.0, 0.0, 0.0);
+        state.set_renormalization(RenormalizationPolicy::new().every(10));
+
+        for _ in 0..25 {
+            state.apply_rotor_mut(&drifting_rotor());
+        }
+
+        let diagnostics = state.drift_diagnostics().unwrap();
+        assert_eq!(diagnostics.operations, 25);
+        assert_eq!(diagnostics.renormalizations, 2);
+        assert!(diagnostics.max_drift > 1e-5);
+
+        // Five operations of drift since the last renormalisation
+        assert!(state.drift() > 1e-6 && state.drift() < 3e-5);
+        assert!(state.renormalize_mut());
+        assert!((state.magnitude() - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_renormalize_on_drift() {
+        let state = GeometricState::identity();
+        state.set_renormalization(RenormalizationPolicy::rotor().with_history(8));
+
+        for _ in 0..100 {
+            state.update(|mv| drifting_rotor().as_multivector().geometric_product(mv));
+        }
+
+        // |R R̃ - 1| is corrected after every operation
+        assert!(state.drift() < 1e-12);
+        let diagnostics = state.drift_diagnostics().unwrap();
+        assert_eq!(diagnostics.renormalizations, 100);
+        assert_eq!(diagnostics.history.len(), 8);
+        assert_eq!(diagnostics.history.back().unwrap().operation, 100);
+        assert!(diagnostics.history.iter().all(|s| s.drift > 1e-7));
+    }
+
+    #[test]
+    fn test_grade_cleanup() {
+        let state = GeometricState::from_vector(1.0, 0.0, 0.0);
+        state.set_renormalization(RenormalizationPolicy::new().keep_grades(&[1]));
+
+        // Leak a little scalar and bivector into a vector state
+        state.update(|mv| {
+            let mut leaked = mv.clone();
+            leaked.set(0, 1e-3);
+            leaked.set(3, 1e-3);
+            leaked
+        });
+
+        assert_eq!(state.scalar(), 0.0);
+        assert_eq!(state.as_bivector(), (0.0, 0.0, 0.0));
+        assert_eq!(state.as_vector(), (1.0, 0.0, 0.0));
+        assert_eq!(state.drift_diagnostics().unwrap().renormalizations, 0);
+    }
+
+    #[test]
+    fn test_set_rebases_reference() {
+        let state = GeometricState::from_vector(1.0, 0.0, 0.0);
+        let shared = state.clone();
+        state.set_renormalization(RenormalizationPolicy::new().every(1));
+
+        // Explicit sets are not drift
+        state.set_vector(0.0, 5.0, 0.0);
+        assert!(state.drift() < 1e-12);
+
+        shared.apply_rotor_mut(&drifting_rotor());
+        assert!((state.magnitude() - 5.0).abs() < 1e-12);
+        assert_eq!(shared.drift_diagnostics().unwrap().renormalizations, 1);
+    }
+
+    #[test]
+    fn test_renormalization_notifies_once_per_operation() {
+        let state = GeometricState::from_vector(1.0, 0.0, 0.0);
+        state.set_renormalization(RenormalizationPolicy::new().every(1));
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let _sub = state.subscribe(move |mv| seen_clone.lock().unwrap().push(mv.magnitude()));
+
+        state.apply_rotor_mut(&drifting_rotor());
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert!((seen[0] - 1.0).abs() < 1e-12);
+    }
 }
@@ -0,0 +1,672 @@
+//! Thread-safe Behavior and Event variants
+//!
+//! `Behavior<T>` and `Event<T>` are single-threaded (`Rc<RefCell<..>>`) so
+//! they stay cheap in the browser. `SyncBehavior<T>` and `SyncEvent<T>`
+//! offer the same `map`/`combine`/`subscribe`/`fold` surface on top of
+//! `Arc` and locks, so server-side Rust (tokio tasks, the consensus and
+//! sync machinery in cliffy-protocols) can drive the same reactive graphs.
+//!
+//! Subscribers are called outside of any lock, so a callback may freely
+//! sample, set or subscribe to behaviors, including the one that notified it.
+//! Notifications of one behavior are serialized and carry its latest value:
+//! a change made while its subscribers run, on another thread or by one of
+//! them, is delivered once they finish. Subscribers may skip intermediate
+//! values, but never end on a stale one. Derived values are recomputed
+//! eagerly on the notifying thread, from their parents' current values.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::sync::SyncBehavior;
+//! use std::thread;
+//!
+//! let count = SyncBehavior::new(0i32);
+//! let doubled = count.map(|n| n * 2);
+//!
+//! let handle = {
+//!     let count = count.clone();
+//!     thread::spawn(move || count.update(|n| n + 21))
+//! };
+//! handle.join().unwrap();
+//!
+//! assert_eq!(doubled.sample(), 42);
+//! ```
+
+use crate::geometric::{FromGeometric, IntoGeometric, GA3};
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Type alias for subscriber callbacks to avoid clippy::type_complexity warning
+type SyncSubscriberList<T> = Arc<Mutex<Vec<(usize, Arc<dyn Fn(&T) + Send + Sync>)>>>;
+
+/// Type alias for values kept alive by a derived behavior or event
+type KeepAlive = Mutex<Vec<Arc<dyn Any + Send + Sync>>>;
+
+/// A thread-safe subscription handle
+///
+/// Dropping the handle unsubscribes.
+#[must_use = "dropping a SyncSubscription unsubscribes immediately"]
+pub struct SyncSubscription {
+    unsubscribe: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl SyncSubscription {
+    fn new<T: 'static>(id: usize, subscribers: &SyncSubscriberList<T>) -> Self {
+        let subscribers = Arc::downgrade(subscribers);
+        Self {
+            unsubscribe: Some(Box::new(move || {
+                if let Some(subscribers) = subscribers.upgrade() {
+                    subscribers.lock().unwrap().retain(|(i, _)| *i != id);
+                }
+            })),
+        }
+    }
+
+    /// Unsubscribe from updates
+    pub fn unsubscribe(self) {
+        // Dropping performs the unsubscribe
+    }
+}
+
+impl Drop for SyncSubscription {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.take() {
+            unsubscribe();
+        }
+    }
+}
+
+/// Register `callback` and return its subscription
+fn subscribe_to<T: 'static>(
+    subscribers: &SyncSubscriberList<T>,
+    next_id: &AtomicUsize,
+    callback: Arc<dyn Fn(&T) + Send + Sync>,
+) -> SyncSubscription {
+    let id = next_id.fetch_add(1, Ordering::Relaxed);
+    subscribers.lock().unwrap().push((id, callback));
+    SyncSubscription::new(id, subscribers)
+}
+
+/// Call every subscriber with `value`, without holding the subscriber lock
+fn notify<T>(subscribers: &SyncSubscriberList<T>, value: &T) {
+    let callbacks: Vec<_> = subscribers
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(_, callback)| Arc::clone(callback))
+        .collect();
+    for callback in callbacks {
+        callback(value);
+    }
+}
+
+/// A thread-safe time-varying value backed by geometric algebra
+///
+/// The `Send + Sync` counterpart of `Behavior<T>`. Clones share state.
+pub struct SyncBehavior<T> {
+    inner: Arc<SyncBehaviorInner<T>>,
+}
+
+struct SyncBehaviorInner<T> {
+    /// Internal geometric state and cached value, updated together
+    value: RwLock<(GA3, T)>,
+    /// Subscribers to notify on update
+    subscribers: SyncSubscriberList<T>,
+    /// Next subscriber ID
+    next_id: AtomicUsize,
+    /// Subscriptions on parents (for derived behaviors)
+    upstream: Mutex<Vec<SyncSubscription>>,
+    /// Parents kept alive by a derived behavior
+    parents: KeepAlive,
+    /// Serializes read-modify-writes: updates and recomputations
+    serial: Mutex<()>,
+    /// Set while some thread is notifying subscribers
+    notifying: AtomicBool,
+    /// Set when the value changed since subscribers were last notified
+    changed: AtomicBool,
+}
+
+impl<T> Clone for SyncBehavior<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> SyncBehavior<T>
+where
+    T: IntoGeometric + FromGeometric + Clone + Send + Sync + 'static,
+{
+    /// Create a new behavior with an initial value
+    pub fn new(initial: T) -> Self {
+        let mv = initial.clone().into_geometric();
+        Self {
+            inner: Arc::new(SyncBehaviorInner {
+                value: RwLock::new((mv, initial)),
+                subscribers: Arc::new(Mutex::new(Vec::new())),
+                next_id: AtomicUsize::new(0),
+                upstream: Mutex::new(Vec::new()),
+                parents: Mutex::new(Vec::new()),
+                serial: Mutex::new(()),
+                notifying: AtomicBool::new(false),
+                changed: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// Sample the current value
+    pub fn sample(&self) -> T {
+        self.inner.value.read().unwrap().1.clone()
+    }
+
+    /// Get the internal geometric state (for advanced users)
+    pub fn geometric_state(&self) -> GA3 {
+        self.inner.value.read().unwrap().0.clone()
+    }
+
+    /// Update the value using a transformation function
+    ///
+    /// The read-modify-write is atomic with respect to other updates. `f`
+    /// runs without the value locked, so it may sample this behavior (or
+    /// anything derived from it), but must not update or set it. All
+    /// subscribers are notified after the update.
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(T) -> T,
+    {
+        {
+            let _serial = self.inner.serial.lock().unwrap();
+            let new_value = f(self.sample());
+            self.store(new_value.clone().into_geometric(), new_value);
+        }
+        self.publish();
+    }
+
+    /// Set the value directly
+    pub fn set(&self, value: T) {
+        self.update(|_| value);
+    }
+
+    /// Apply a geometric transformation directly (for advanced users)
+    pub fn apply_geometric<F>(&self, transform: F)
+    where
+        F: FnOnce(&GA3) -> GA3,
+    {
+        {
+            let _serial = self.inner.serial.lock().unwrap();
+            let new_mv = transform(&self.geometric_state());
+            let new_value = T::from_geometric(&new_mv);
+            self.store(new_mv, new_value);
+        }
+        self.publish();
+    }
+
+    /// Subscribe to value changes
+    ///
+    /// Returns a `SyncSubscription` that unsubscribes when dropped.
+    pub fn subscribe<F>(&self, callback: F) -> SyncSubscription
+    where
+        F: Fn(&T) + Send + Sync + 'static,
+    {
+        subscribe_to(
+            &self.inner.subscribers,
+            &self.inner.next_id,
+            Arc::new(callback),
+        )
+    }
+
+    /// Get the number of active subscribers (including derived behaviors)
+    pub fn subscriber_count(&self) -> usize {
+        self.inner.subscribers.lock().unwrap().len()
+    }
+
+    /// Record an upstream subscription and parent owned by this (derived) behavior
+    fn own<P: Send + Sync + 'static>(&self, subscription: SyncSubscription, parent: Arc<P>) {
+        self.inner.upstream.lock().unwrap().push(subscription);
+        self.inner.parents.lock().unwrap().push(parent);
+    }
+
+    /// Recompute a derived behavior from its parents and notify subscribers
+    ///
+    /// `compute` samples the parents while no other recomputation of this
+    /// behavior runs, so the last recomputation always sees the parents'
+    /// latest values.
+    fn recompute<F>(&self, compute: F)
+    where
+        F: FnOnce() -> T,
+    {
+        {
+            let _serial = self.inner.serial.lock().unwrap();
+            let new_value = compute();
+            self.store(new_value.clone().into_geometric(), new_value);
+        }
+        self.publish();
+    }
+
+    /// Replace the geometric state and cached value together
+    fn store(&self, mv: GA3, value: T) {
+        *self.inner.value.write().unwrap() = (mv, value);
+    }
+
+    /// Notify subscribers of the current value
+    ///
+    /// If another thread (or a subscriber further up this thread's stack)
+    /// is already notifying, it notifies again with the latest value once
+    /// its current round is done, so this returns straight away.
+    fn publish(&self) {
+        let inner = &self.inner;
+        inner.changed.store(true, Ordering::SeqCst);
+        while inner.changed.load(Ordering::SeqCst) {
+            if inner.notifying.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            while inner.changed.swap(false, Ordering::SeqCst) {
+                notify(&inner.subscribers, &self.sample());
+            }
+            inner.notifying.store(false, Ordering::SeqCst);
+        }
+    }
+
+    /// Create a derived behavior by mapping a function over this behavior
+    ///
+    /// The derived behavior owns its subscription on this one; dropping its
+    /// last handle unsubscribes.
+    pub fn map<U, F>(&self, f: F) -> SyncBehavior<U>
+    where
+        U: IntoGeometric + FromGeometric + Clone + Send + Sync + 'static,
+        F: Fn(T) -> U + Send + Sync + 'static,
+    {
+        let derived = SyncBehavior::new(f(self.sample()));
+
+        let target = Arc::downgrade(&derived.inner);
+        let source = Arc::downgrade(&self.inner);
+        let subscription = self.subscribe(move |_| {
+            if let (Some(inner), Some(source)) = (target.upgrade(), source.upgrade()) {
+                let source = SyncBehavior { inner: source };
+                SyncBehavior { inner }.recompute(|| f(source.sample()));
+            }
+        });
+        derived.own(subscription, Arc::clone(&self.inner));
+
+        derived
+    }
+
+    /// Combine two behaviors into a new behavior
+    pub fn combine<U, V, F>(&self, other: &SyncBehavior<U>, f: F) -> SyncBehavior<V>
+    where
+        U: IntoGeometric + FromGeometric + Clone + Send + Sync + 'static,
+        V: IntoGeometric + FromGeometric + Clone + Send + Sync + 'static,
+        F: Fn(T, U) -> V + Send + Sync + 'static,
+    {
+        let combined = SyncBehavior::new(f(self.sample(), other.sample()));
+
+        // Either parent changing recomputes from both parents' current values
+        let target = Arc::downgrade(&combined.inner);
+        let self_weak = Arc::downgrade(&self.inner);
+        let other_weak = Arc::downgrade(&other.inner);
+        let recompute = Arc::new(move || {
+            if let (Some(inner), Some(a), Some(b)) =
+                (target.upgrade(), self_weak.upgrade(), other_weak.upgrade())
+            {
+                let (a, b) = (SyncBehavior { inner: a }, SyncBehavior { inner: b });
+                SyncBehavior { inner }.recompute(|| f(a.sample(), b.sample()));
+            }
+        });
+
+        let recompute_a = Arc::clone(&recompute);
+        let sub_a = self.subscribe(move |_| recompute_a());
+        let sub_b = other.subscribe(move |_| recompute());
+
+        combined.own(sub_a, Arc::clone(&self.inner));
+        combined.own(sub_b, Arc::clone(&other.inner));
+
+        combined
+    }
+}
+
+/// A thread-safe stream of discrete occurrences
+///
+/// The `Send + Sync` counterpart of `Event<T>`. Clones share subscribers.
+pub struct SyncEvent<T> {
+    inner: Arc<SyncEventInner<T>>,
+}
+
+struct SyncEventInner<T> {
+    /// Subscribers to notify on occurrence
+    subscribers: SyncSubscriberList<T>,
+    /// Next subscriber ID
+    next_id: AtomicUsize,
+    /// Subscriptions on source events (for derived events)
+    upstream: Mutex<Vec<SyncSubscription>>,
+    /// Sources kept alive by a derived event
+    parents: KeepAlive,
+}
+
+impl<T> Clone for SyncEvent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> SyncEvent<T> {
+    /// Create a new event stream
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(SyncEventInner {
+                subscribers: Arc::new(Mutex::new(Vec::new())),
+                next_id: AtomicUsize::new(0),
+                upstream: Mutex::new(Vec::new()),
+                parents: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Emit a value to all subscribers
+    pub fn emit(&self, value: T) {
+        notify(&self.inner.subscribers, &value);
+    }
+
+    /// Subscribe to this event stream
+    ///
+    /// Returns a `SyncSubscription` that unsubscribes when dropped.
+    pub fn subscribe<F>(&self, callback: F) -> SyncSubscription
+    where
+        F: Fn(&T) + Send + Sync + 'static,
+    {
+        subscribe_to(
+            &self.inner.subscribers,
+            &self.inner.next_id,
+            Arc::new(callback),
+        )
+    }
+
+    /// Get the number of active subscribers (including derived events)
+    pub fn subscriber_count(&self) -> usize {
+        self.inner.subscribers.lock().unwrap().len()
+    }
+
+    /// Create a derived event fed by `forward` from this one
+    fn derive<U, F>(&self, forward: F) -> SyncEvent<U>
+    where
+        U: Clone + Send + Sync + 'static,
+        F: Fn(&SyncEvent<U>, &T) + Send + Sync + 'static,
+    {
+        let derived = SyncEvent::<U>::new();
+        let target = Arc::downgrade(&derived.inner);
+        let subscription = self.subscribe(move |value| {
+            if let Some(inner) = target.upgrade() {
+                forward(&SyncEvent { inner }, value);
+            }
+        });
+        derived.inner.upstream.lock().unwrap().push(subscription);
+        derived
+            .inner
+            .parents
+            .lock()
+            .unwrap()
+            .push(Arc::clone(&self.inner) as Arc<dyn Any + Send + Sync>);
+        derived
+    }
+
+    /// Map a function over this event stream
+    pub fn map<U, F>(&self, f: F) -> SyncEvent<U>
+    where
+        U: Clone + Send + Sync + 'static,
+        F: Fn(T) -> U + Send + Sync + 'static,
+    {
+        self.derive(move |out, value| out.emit(f(value.clone())))
+    }
+
+    /// Filter events based on a predicate
+    pub fn filter<F>(&self, predicate: F) -> SyncEvent<T>
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.derive(move |out, value| {
+            if predicate(value) {
+                out.emit(value.clone());
+            }
+        })
+    }
+
+    /// Merge two event streams
+    pub fn merge(&self, other: &SyncEvent<T>) -> SyncEvent<T> {
+        let merged = self.derive(|out, value| out.emit(value.clone()));
+        let target = Arc::downgrade(&merged.inner);
+        let subscription = other.subscribe(move |value| {
+            if let Some(inner) = target.upgrade() {
+                SyncEvent { inner }.emit(value.clone());
+            }
+        });
+        merged.inner.upstream.lock().unwrap().push(subscription);
+        merged
+            .inner
+            .parents
+            .lock()
+            .unwrap()
+            .push(Arc::clone(&other.inner) as Arc<dyn Any + Send + Sync>);
+        merged
+    }
+
+    /// Fold events into a behavior, accumulating values
+    pub fn fold<S, F>(&self, initial: S, f: F) -> SyncBehavior<S>
+    where
+        S: IntoGeometric + FromGeometric + Clone + Send + Sync + 'static,
+        F: Fn(S, T) -> S + Send + Sync + 'static,
+    {
+        let behavior = SyncBehavior::new(initial);
+        let target = Arc::downgrade(&behavior.inner);
+        let subscription = self.subscribe(move |value| {
+            if let Some(inner) = target.upgrade() {
+                SyncBehavior { inner }.update(|state| f(state, value.clone()));
+            }
+        });
+        behavior.own(subscription, Arc::clone(&self.inner));
+        behavior
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Default for SyncEvent<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_sync_types_are_send_and_sync() {
+        assert_send_sync::<SyncBehavior<i32>>();
+        assert_send_sync::<SyncEvent<String>>();
+        assert_send_sync::<SyncSubscription>();
+    }
+
+    #[test]
+    fn test_sync_behavior_map_and_combine() {
+        let a = SyncBehavior::new(10i32);
+        let b = SyncBehavior::new(20i32);
+        let doubled = a.map(|n| n * 2);
+        let sum = a.combine(&b, |x, y| x + y);
+
+        a.set(5);
+        assert_eq!(doubled.sample(), 10);
+        assert_eq!(sum.sample(), 25);
+
+        b.set(1);
+        assert_eq!(sum.sample(), 6);
+    }
+
+    #[test]
+    fn test_sync_behavior_concurrent_updates() {
+        let count = SyncBehavior::new(0i32);
+        let doubled = count.map(|n| n * 2);
+
+        thread::scope(|s| {
+            for _ in 0..8 {
+                let count = count.clone();
+                s.spawn(move || {
+                    for _ in 0..100 {
+                        count.update(|n| n + 1);
+                    }
+                });
+            }
+        });
+
+        assert_eq!(count.sample(), 800);
+        assert_eq!(doubled.sample(), 1600);
+    }
+
+    #[test]
+    fn test_sync_derived_converges_under_contention() {
+        for _ in 0..20 {
+            let a = SyncBehavior::new(0i64);
+            let b = SyncBehavior::new(0i64);
+            let doubled = a.map(|n| n * 2);
+            let sum = a.combine(&b, |x, y| x + y);
+            let chained = sum.map(|n| n + 1);
+
+            thread::scope(|s| {
+                for i in 0..8i64 {
+                    let target = if i % 2 == 0 { a.clone() } else { b.clone() };
+                    s.spawn(move || {
+                        for n in 0..200 {
+                            target.set(i * 1000 + n);
+                        }
+                    });
+                }
+            });
+
+            let (a, b) = (a.sample(), b.sample());
+            assert_eq!(doubled.sample(), a * 2);
+            assert_eq!(sum.sample(), a + b);
+            assert_eq!(chained.sample(), a + b + 1);
+        }
+    }
+
+    #[test]
+    fn test_sync_update_may_sample_itself() {
+        let count = SyncBehavior::new(1i32);
+        let doubled = count.map(|n| n * 2);
+
+        count.update(|n| n + count.sample() + doubled.sample());
+        assert_eq!(count.sample(), 4);
+        assert_eq!(doubled.sample(), 8);
+
+        count.apply_geometric(|mv| mv.clone() * 2.0 + doubled.geometric_state());
+        assert_eq!(count.sample(), 16);
+    }
+
+    #[test]
+    fn test_sync_subscribers_end_on_latest_value() {
+        for _ in 0..20 {
+            let value = SyncBehavior::new(0i64);
+            let seen = Arc::new(Mutex::new(Vec::new()));
+            let seen_clone = Arc::clone(&seen);
+            let _sub = value.subscribe(move |v| seen_clone.lock().unwrap().push(*v));
+
+            // A subscriber's own set is delivered after the current round
+            let clamp = value.clone();
+            let _clamp = value.subscribe(move |v| {
+                if *v < 0 {
+                    clamp.set(0);
+                }
+            });
+
+            thread::scope(|s| {
+                for i in 0..8i64 {
+                    let value = value.clone();
+                    s.spawn(move || {
+                        for n in 0..200 {
+                            value.set(if n == 100 { -1 } else { i * 1000 + n });
+                        }
+                    });
+                }
+            });
+
+            let last = *seen.lock().unwrap().last().unwrap();
+            assert!(value.sample() >= 0);
+            assert_eq!(last, value.sample());
+        }
+    }
+
+    #[test]
+    fn test_sync_subscription_drop_unsubscribes() {
+        let b = SyncBehavior::new(0i32);
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let calls_clone = Arc::clone(&calls);
+        let sub = b.subscribe(move |_| {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+        });
+        b.set(1);
+        drop(sub);
+        b.set(2);
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(b.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_dropping_derived_releases_parent() {
+        let source = SyncBehavior::new(0i32);
+        let derived = source.map(|n| n + 1).map(|n| n * 2);
+        assert_eq!(source.subscriber_count(), 1);
+
+        source.set(1);
+        assert_eq!(derived.sample(), 4);
+
+        drop(derived);
+        assert_eq!(source.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_sync_event_map_filter_merge_fold() {
+        let a = SyncEvent::<i32>::new();
+        let b = SyncEvent::<i32>::new();
+        let evens = a.merge(&b).filter(|n| n % 2 == 0).map(|n| n * 10);
+        let total = evens.fold(0i32, |acc, n| acc + n);
+
+        a.emit(1);
+        a.emit(2);
+        b.emit(4);
+
+        assert_eq!(total.sample(), 60);
+    }
+
+    #[test]
+    fn test_sync_event_from_other_thread() {
+        let clicks = SyncEvent::<()>::new();
+        let count = clicks.fold(0i32, |n, _| n + 1);
+
+        thread::scope(|s| {
+            for _ in 0..4 {
+                let clicks = clicks.clone();
+                s.spawn(move || clicks.emit(()));
+            }
+        });
+
+        assert_eq!(count.sample(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_sync_behavior_in_tokio_task() {
+        let state = SyncBehavior::new(1.0f64);
+        let scaled = state.map(|x| x * 3.0);
+
+        let task_state = state.clone();
+        tokio::spawn(async move { task_state.set(2.0) })
+            .await
+            .unwrap();
+
+        assert!((scaled.sample() - 6.0).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,356 @@
+//! Time - Clocks and schedulers for events and time-based combinators
+//!
+//! Nothing in the reactive core reads the wall clock directly. Events
+//! timestamp their occurrences with a `Clock`, and time-based combinators
+//! (`Event::debounce`, `throttle`, `delay`) run on a `Scheduler`, which is a
+//! `Clock` that can also "run this later".
+//!
+//! - `SystemClock` reads `std::time::Instant` (native targets only; `Instant`
+//!   panics on `wasm32-unknown-unknown`)
+//! - `PerformanceClock` reads `performance.now()` (wasm32 only)
+//! - `DefaultClock` is whichever of the two fits the target
+//! - `ManualClock` only moves when it is told to
+//!
+//! `VirtualScheduler` is a host-driven scheduler: nothing happens until
+//! time is advanced. Tests advance it explicitly, and in the browser a
+//! `requestAnimationFrame` loop advances it to the frame timestamp:
+//!
+//! ```text
+//! requestAnimationFrame(t => scheduler.advance_to_secs(t / 1000))
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::{event, time::VirtualScheduler};
+//! use std::time::Duration;
+//!
+//! let scheduler = VirtualScheduler::new();
+//! let slider = event::<i32>();
+//! let settled = slider.debounce(Duration::from_millis(100), &scheduler);
+//! let last = settled.fold(0, |_, v| v);
+//!
+//! slider.emit(10);
+//! slider.emit(20);
+//! assert_eq!(last.sample(), 0);
+//!
+//! scheduler.advance(Duration::from_millis(100));
+//! assert_eq!(last.sample(), 20);
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// A source of the current time
+pub trait Clock {
+    /// Current time in seconds
+    fn now(&self) -> f64;
+}
+
+/// A clock that follows `std::time::Instant`
+///
+/// Time is measured from the moment the clock was created.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    origin: std::time::Instant,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SystemClock {
+    /// Create a clock starting at zero now
+    pub fn new() -> Self {
+        Self {
+            origin: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for SystemClock {
+    fn now(&self) -> f64 {
+        self.origin.elapsed().as_secs_f64()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod performance {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        /// `performance.now()`, available in windows and workers
+        #[wasm_bindgen(js_namespace = performance, js_name = now)]
+        pub fn now() -> f64;
+    }
+}
+
+/// A clock that follows the browser's `performance.now()`
+///
+/// Time is measured from the moment the clock was created, so it lines up
+/// with `requestAnimationFrame` timestamps up to a constant offset.
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Clone, Copy)]
+pub struct PerformanceClock {
+    origin_ms: f64,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl PerformanceClock {
+    /// Create a clock starting at zero now
+    pub fn new() -> Self {
+        Self {
+            origin_ms: performance::now(),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Default for PerformanceClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Clock for PerformanceClock {
+    fn now(&self) -> f64 {
+        (performance::now() - self.origin_ms) / 1000.0
+    }
+}
+
+/// The real-time clock for the current target
+#[cfg(not(target_arch = "wasm32"))]
+pub type DefaultClock = SystemClock;
+
+/// The real-time clock for the current target
+#[cfg(target_arch = "wasm32")]
+pub type DefaultClock = PerformanceClock;
+
+/// A clock whose time only moves when it is set or advanced
+///
+/// Clones share the same time.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    now: Rc<Cell<Duration>>,
+}
+
+impl ManualClock {
+    /// Create a clock starting at time zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current time
+    pub fn elapsed(&self) -> Duration {
+        self.now.get()
+    }
+
+    /// Move time forward by `delta`
+    pub fn advance(&self, delta: Duration) {
+        self.now.set(self.now.get() + delta);
+    }
+
+    /// Set the current time (it may move backwards)
+    pub fn set(&self, time: Duration) {
+        self.now.set(time);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> f64 {
+        self.elapsed().as_secs_f64()
+    }
+}
+
+/// A clock that can also defer work, for time-based combinators
+pub trait Scheduler: Clock {
+    /// Run `task` once `delay` has elapsed
+    fn schedule(&self, delay: Duration, task: Box<dyn FnOnce()>);
+}
+
+/// Type alias for pending tasks to avoid clippy::type_complexity warning
+type TaskQueue = RefCell<BTreeMap<(Duration, u64), Box<dyn FnOnce()>>>;
+
+/// A scheduler whose time only moves when it is advanced
+///
+/// Clones share the same timeline and task queue.
+#[derive(Clone, Default)]
+pub struct VirtualScheduler {
+    clock: ManualClock,
+    inner: Rc<VirtualInner>,
+}
+
+#[derive(Default)]
+struct VirtualInner {
+    /// Pending tasks ordered by (due time, insertion order)
+    tasks: TaskQueue,
+    /// Next insertion sequence number
+    next_seq: Cell<u64>,
+}
+
+impl VirtualScheduler {
+    /// Create a scheduler starting at time zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current virtual time
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed()
+    }
+
+    /// Advance time by `delta`, running every task that becomes due
+    pub fn advance(&self, delta: Duration) {
+        self.advance_to(self.elapsed() + delta);
+    }
+
+    /// Advance time to `time`, running every task due at or before it
+    ///
+    /// Tasks run in due-time order and observe `now()` as their own due
+    /// time. Tasks scheduled by other tasks run in the same call if they
+    /// fall due before `time`. Moving backwards is a no-op.
+    pub fn advance_to(&self, time: Duration) {
+        if time < self.elapsed() {
+            return;
+        }
+
+        loop {
+            let due = {
+                let mut tasks = self.inner.tasks.borrow_mut();
+                match tasks.first_key_value() {
+                    Some((&(at, _), _)) if at <= time => tasks.pop_first(),
+                    _ => None,
+                }
+            };
+            let Some(((at, _), task)) = due else {
+                break;
+            };
+            self.clock.set(at);
+            task();
+        }
+
+        self.clock.set(time);
+    }
+
+    /// Advance time to `seconds` (e.g. a `requestAnimationFrame` timestamp / 1000)
+    pub fn advance_to_secs(&self, seconds: f64) {
+        self.advance_to(Duration::from_secs_f64(seconds.max(0.0)));
+    }
+
+    /// Number of tasks waiting to run
+    pub fn pending(&self) -> usize {
+        self.inner.tasks.borrow().len()
+    }
+}
+
+impl Clock for VirtualScheduler {
+    fn now(&self) -> f64 {
+        self.clock.now()
+    }
+}
+
+impl Scheduler for VirtualScheduler {
+    fn schedule(&self, delay: Duration, task: Box<dyn FnOnce()>) {
+        let seq = self.inner.next_seq.get();
+        self.inner.next_seq.set(seq + 1);
+        self.inner
+            .tasks
+            .borrow_mut()
+            .insert((self.elapsed() + delay, seq), task);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_shared_between_clones() {
+        let clock = ManualClock::new();
+        let handle = clock.clone();
+
+        handle.advance(Duration::from_millis(1500));
+        assert!((clock.now() - 1.5).abs() < 1e-9);
+
+        clock.set(Duration::from_secs(1));
+        assert_eq!(handle.elapsed(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_system_clock_is_monotonic() {
+        let clock = DefaultClock::new();
+        let first = clock.now();
+        assert!(first >= 0.0);
+        assert!(clock.now() >= first);
+    }
+
+    #[test]
+    fn test_tasks_run_when_due() {
+        let scheduler = VirtualScheduler::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        for ms in [30u64, 10, 20] {
+            let log = Rc::clone(&log);
+            scheduler.schedule(
+                Duration::from_millis(ms),
+                Box::new(move || log.borrow_mut().push(ms)),
+            );
+        }
+
+        scheduler.advance(Duration::from_millis(15));
+        assert_eq!(*log.borrow(), vec![10]);
+
+        scheduler.advance(Duration::from_millis(15));
+        assert_eq!(*log.borrow(), vec![10, 20, 30]);
+        assert_eq!(scheduler.pending(), 0);
+    }
+
+    #[test]
+    fn test_task_sees_its_due_time() {
+        let scheduler = VirtualScheduler::new();
+        let seen = Rc::new(Cell::new(0.0));
+
+        let seen_clone = Rc::clone(&seen);
+        let clock = scheduler.clone();
+        scheduler.schedule(
+            Duration::from_millis(250),
+            Box::new(move || seen_clone.set(clock.now())),
+        );
+
+        scheduler.advance(Duration::from_secs(1));
+        assert!((seen.get() - 0.25).abs() < 1e-9);
+        assert!((scheduler.now() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tasks_scheduled_by_tasks() {
+        let scheduler = VirtualScheduler::new();
+        let count = Rc::new(Cell::new(0));
+
+        let inner_scheduler = scheduler.clone();
+        let count_clone = Rc::clone(&count);
+        scheduler.schedule(
+            Duration::from_millis(10),
+            Box::new(move || {
+                let count = Rc::clone(&count_clone);
+                inner_scheduler.schedule(
+                    Duration::from_millis(10),
+                    Box::new(move || count.set(count.get() + 1)),
+                );
+            }),
+        );
+
+        scheduler.advance(Duration::from_millis(25));
+        assert_eq!(count.get(), 1);
+    }
+}
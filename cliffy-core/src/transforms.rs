@@ -3,13 +3,24 @@
 //! This module provides explicit geometric transformation types:
 //! - `Rotor`: Represents rotations in geometric algebra
 //! - `Versor`: General geometric transformations (rotations + reflections)
-//! - `Motor`: Translations combined with rotations (for CGA)
+//! - `Motor`: Rigid motions (rotation + translation) as PGA3 motors, with
+//!   screw interpolation
+//! - [`spline`]: Smooth curves through many rotor, translation and
+//!   transform keys, with arc-length parameterisation
 //!
 //! These allow users to apply explicit geometric operations to state,
 //! rather than hiding all geometry behind scalar updates.
+//!
+//! All types are generic over the algebra signature `<P, Q, R>` and default
+//! to GA3. In PGA3 and CGA3 a translation is a versor as well, so
+//! `Versor::translator` and `Versor::motor` build a single versor for a
+//! whole rigid motion (see `crate::algebra`).
+
+use crate::algebra::{pga, EuclideanEmbedding, TranslatorAlgebra};
+use crate::geometric::{FromGeometric, IntoGeometric, GA3, PGA3};
+use amari_core::{Bivector, Multivector, Rotor as AmariRotor, Vector};
 
-use crate::geometric::GA3;
-use amari_core::{Bivector, Rotor as AmariRotor, Vector};
+pub mod spline;
 
 /// A rotor represents a rotation in geometric algebra.
 ///
@@ -19,23 +30,19 @@ use amari_core::{Bivector, Rotor as AmariRotor, Vector};
 /// In 3D, a rotor encodes rotation by angle θ around an axis n as:
 /// R = cos(θ/2) + sin(θ/2) * B
 /// where B is the unit bivector representing the rotation plane.
+///
+/// Constructors build GA3 rotors; use `embed` to move one into PGA3 or
+/// CGA3, where e1, e2 and e3 (and so every rotation) are unchanged.
 #[derive(Clone, Debug)]
-pub struct Rotor {
+pub struct Rotor<const P: usize = 3, const Q: usize = 0, const R: usize = 0> {
     /// The internal multivector representation
-    inner: GA3,
+    inner: Multivector<P, Q, R>,
 }
 
 impl Rotor {
-    /// Create a rotor from a multivector (assumes it's already a valid rotor)
-    pub fn from_multivector(mv: GA3) -> Self {
-        Self { inner: mv }
-    }
-
     /// Create the identity rotor (no rotation)
     pub fn identity() -> Self {
-        Self {
-            inner: GA3::scalar(1.0),
-        }
+        Self::unit()
     }
 
     /// Create a rotor for rotation by angle (in radians) in the XY plane
@@ -64,23 +71,247 @@ impl Rotor {
     /// The bivector (xy, xz, yz) defines the rotation plane.
     /// Components are normalized internally.
     pub fn from_bivector_angle(angle: f64, xy: f64, xz: f64, yz: f64) -> Self {
+        Self::from_plane(angle, xy, xz, yz)
+    }
+
+    /// Create a rotor for rotation around an axis vector by an angle
+    ///
+    /// The axis does not need to be normalized.
+    pub fn from_axis_angle(axis_x: f64, axis_y: f64, axis_z: f64, angle: f64) -> Self {
+        // The bivector for rotation around axis (x,y,z) is proportional to
+        // x*(e2^e3) + y*(e3^e1) + z*(e1^e2)
+        // which in our basis order (e12, e13, e23) is:
+        // xy = z, xz = -y, yz = x
+        Self::from_bivector_angle(angle, axis_z, -axis_y, axis_x)
+    }
+
+    /// Create the rotor that turns direction `a` onto direction `b`
+    ///
+    /// The rotation is the smallest one, in the plane of `a` and `b`. The
+    /// vectors don't need to be normalized; if either is zero the result
+    /// is the identity. Opposite vectors are related by a half turn about
+    /// any perpendicular axis, and one is chosen.
+    pub fn from_vectors(a: (f64, f64, f64), b: (f64, f64, f64)) -> Self {
+        let (Some(a), Some(b)) = (
+            GA3::point(a.0, a.1, a.2).normalize(),
+            GA3::point(b.0, b.1, b.2).normalize(),
+        ) else {
+            return Self::identity();
+        };
+
+        // R = (1 + b a) / |1 + b a|
+        let halfway = &GA3::scalar(1.0) + &b.geometric_product(&a);
+        if halfway.get(0) > 1e-10 {
+            if let Some(inner) = halfway.normalize() {
+                return Self { inner };
+            }
+        }
+
+        // Opposite directions: half turn about an axis perpendicular to a
+        let (x, y, z) = a.to_point();
+        let (px, py, pz) = if x.abs() < 0.9 {
+            (0.0, z, -y)
+        } else {
+            (-z, 0.0, x)
+        };
+        Self::from_axis_angle(px, py, pz, std::f64::consts::PI)
+    }
+
+    /// Create a rotor from Euler angles (radians) about the X, Y and Z axes
+    ///
+    /// `order` gives the axis order as in three.js (see `EulerOrder`).
+    pub fn from_euler(order: EulerOrder, x: f64, y: f64, z: f64) -> Self {
+        let rx = Self::from_axis_angle(1.0, 0.0, 0.0, x);
+        let ry = Self::from_axis_angle(0.0, 1.0, 0.0, y);
+        let rz = Self::from_axis_angle(0.0, 0.0, 1.0, z);
+
+        // The matrix for order ABC is A·B·C, so C is applied first
+        let (first, second, third) = match order {
+            EulerOrder::XYZ => (rz, ry, rx),
+            EulerOrder::XZY => (ry, rz, rx),
+            EulerOrder::YXZ => (rz, rx, ry),
+            EulerOrder::YZX => (rx, rz, ry),
+            EulerOrder::ZXY => (ry, rx, rz),
+            EulerOrder::ZYX => (rx, ry, rz),
+        };
+        first.then(&second).then(&third)
+    }
+
+    /// Create a rotor from a 3x3 rotation matrix
+    ///
+    /// The matrix is indexed `m[row][column]` and acts on column vectors,
+    /// as returned by `to_matrix3`. It should be orthonormal with
+    /// determinant 1; other matrices give the nearest rotor only roughly.
+    pub fn from_matrix3(m: [[f64; 3]; 3]) -> Self {
+        // Shepperd's method: divide by the largest of w, x, y, z for stability
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        let (x, y, z, w) = if trace > 0.0 {
+            let s = 0.5 / (trace + 1.0).sqrt();
+            (
+                (m[2][1] - m[1][2]) * s,
+                (m[0][2] - m[2][0]) * s,
+                (m[1][0] - m[0][1]) * s,
+                0.25 / s,
+            )
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = 2.0 * (1.0 + m[0][0] - m[1][1] - m[2][2]).sqrt();
+            (
+                0.25 * s,
+                (m[0][1] + m[1][0]) / s,
+                (m[0][2] + m[2][0]) / s,
+                (m[2][1] - m[1][2]) / s,
+            )
+        } else if m[1][1] > m[2][2] {
+            let s = 2.0 * (1.0 + m[1][1] - m[0][0] - m[2][2]).sqrt();
+            (
+                (m[0][1] + m[1][0]) / s,
+                0.25 * s,
+                (m[1][2] + m[2][1]) / s,
+                (m[0][2] - m[2][0]) / s,
+            )
+        } else {
+            let s = 2.0 * (1.0 + m[2][2] - m[0][0] - m[1][1]).sqrt();
+            (
+                (m[0][2] + m[2][0]) / s,
+                (m[1][2] + m[2][1]) / s,
+                0.25 * s,
+                (m[1][0] - m[0][1]) / s,
+            )
+        };
+        Self::from_quaternion(x, y, z, w)
+    }
+
+    /// Create a rotor from a unit quaternion `w + xi + yj + zk`
+    ///
+    /// Arguments are in glTF and three.js order, with the scalar last. The
+    /// quaternion is normalized.
+    pub fn from_quaternion(x: f64, y: f64, z: f64, w: f64) -> Self {
+        // i, j, k are the rotations about X, Y and Z: -e23, e13 and -e12
+        Self {
+            inner: GA3::from_coefficients(vec![w, 0.0, 0.0, -z, 0.0, y, -x, 0.0]),
+        }
+        .normalize()
+    }
+
+    /// The unit quaternion for this rotation as (x, y, z, w)
+    ///
+    /// The scalar comes last, as in glTF and three.js.
+    pub fn to_quaternion(&self) -> (f64, f64, f64, f64) {
+        (
+            -self.inner.get(6),
+            self.inner.get(5),
+            -self.inner.get(3),
+            self.inner.get(0),
+        )
+    }
+
+    /// The 3x3 rotation matrix, indexed `m[row][column]`
+    ///
+    /// Column `j` is the image of the j-th basis vector, so `M v` rotates
+    /// the column vector `v`.
+    pub fn to_matrix3(&self) -> [[f64; 3]; 3] {
+        let columns = [
+            self.rotate_vector(1.0, 0.0, 0.0),
+            self.rotate_vector(0.0, 1.0, 0.0),
+            self.rotate_vector(0.0, 0.0, 1.0),
+        ];
+        let mut m = [[0.0; 3]; 3];
+        for (j, (x, y, z)) in columns.into_iter().enumerate() {
+            m[0][j] = x;
+            m[1][j] = y;
+            m[2][j] = z;
+        }
+        m
+    }
+
+    /// The 4x4 homogeneous rotation matrix, indexed `m[row][column]`
+    ///
+    /// The upper-left block is `to_matrix3`; there is no translation.
+    pub fn to_matrix4(&self) -> [[f64; 4]; 4] {
+        let rotation = self.to_matrix3();
+        let mut m = [[0.0; 4]; 4];
+        for (row, values) in rotation.iter().enumerate() {
+            m[row][..3].copy_from_slice(values);
+        }
+        m[3][3] = 1.0;
+        m
+    }
+
+    /// Convert to an amari-core `Rotor<3,0,0>`
+    ///
+    /// This enables interop with amari-core's typed rotor operations
+    /// (slerp, compose, logarithm, power, etc.)
+    pub fn to_amari_rotor(&self) -> AmariRotor<3, 0, 0> {
+        // Extract and normalize the bivector plane
+        let e12 = self.inner.get(3);
+        let e13 = self.inner.get(5);
+        let e23 = self.inner.get(6);
+        let biv_mag = (e12 * e12 + e13 * e13 + e23 * e23).sqrt();
+
+        if biv_mag < 1e-10 {
+            // Identity or near-identity rotor
+            return AmariRotor::identity();
+        }
+
+        // Create unit bivector (negate to match amari convention;
+        // cliffy negates bivector components in from_bivector_angle)
+        let biv =
+            Bivector::<3, 0, 0>::from_components(-e12 / biv_mag, -e13 / biv_mag, -e23 / biv_mag);
+        AmariRotor::from_bivector(&biv, self.angle())
+    }
+
+    /// Create from an amari-core `Rotor<3,0,0>`
+    pub fn from_amari_rotor(rotor: &AmariRotor<3, 0, 0>) -> Self {
+        Self {
+            inner: rotor.as_multivector().clone(),
+        }
+    }
+
+    /// The same rotation in another algebra (PGA3, CGA3)
+    ///
+    /// Rotations only involve e1, e2 and e3, which have the same basis
+    /// indices in every signature.
+    pub fn embed<const P: usize, const Q: usize, const R: usize>(&self) -> Rotor<P, Q, R> {
+        let mut inner = Multivector::zero();
+        for index in [0, 3, 5, 6] {
+            inner.set(index, self.inner.get(index));
+        }
+        Rotor { inner }
+    }
+}
+
+impl<const P: usize, const Q: usize, const R: usize> Rotor<P, Q, R> {
+    /// Create a rotor from a multivector (assumes it's already a valid rotor)
+    pub fn from_multivector(mv: Multivector<P, Q, R>) -> Self {
+        Self { inner: mv }
+    }
+
+    /// The identity rotor in this algebra
+    fn unit() -> Self {
+        Self {
+            inner: Multivector::scalar(1.0),
+        }
+    }
+
+    /// `cos(θ/2) + sin(θ/2) B` for the unit bivector B along (xy, xz, yz)
+    fn from_plane(angle: f64, xy: f64, xz: f64, yz: f64) -> Self {
         let half_angle = angle / 2.0;
 
         // Create unit bivector
         // Note: Negate to match standard right-hand rotation convention
-        let biv = Bivector::<3, 0, 0>::from_components(-xy, -xz, -yz);
-        let biv_mv = GA3::from_bivector(&biv);
+        let biv = Bivector::<P, Q, R>::from_components(-xy, -xz, -yz);
+        let biv_mv = Multivector::from_bivector(&biv);
         let mag = biv_mv.magnitude();
 
         if mag < 1e-10 {
             // Degenerate case - return identity
-            return Self::identity();
+            return Self::unit();
         }
 
         let biv_unit = &biv_mv * (1.0 / mag);
 
         // R = cos(θ/2) + sin(θ/2) * B
-        let cos_part = GA3::scalar(half_angle.cos());
+        let cos_part = Multivector::scalar(half_angle.cos());
         let sin_part = &biv_unit * half_angle.sin();
 
         Self {
@@ -88,26 +319,15 @@ impl Rotor {
         }
     }
 
-    /// Create a rotor for rotation around an axis vector by an angle
-    ///
-    /// The axis does not need to be normalized.
-    pub fn from_axis_angle(axis_x: f64, axis_y: f64, axis_z: f64, angle: f64) -> Self {
-        // The bivector for rotation around axis (x,y,z) is proportional to
-        // x*(e2^e3) + y*(e3^e1) + z*(e1^e2)
-        // which in our basis order (e12, e13, e23) is:
-        // xy = z, xz = -y, yz = x
-        Self::from_bivector_angle(angle, axis_z, -axis_y, axis_x)
-    }
-
     /// Get the internal multivector
-    pub fn as_multivector(&self) -> &GA3 {
+    pub fn as_multivector(&self) -> &Multivector<P, Q, R> {
         &self.inner
     }
 
     /// Apply this rotor to transform a multivector (sandwich product)
     ///
     /// Returns R * v * R†
-    pub fn transform(&self, v: &GA3) -> GA3 {
+    pub fn transform(&self, v: &Multivector<P, Q, R>) -> Multivector<P, Q, R> {
         let rev = self.inner.reverse();
         self.inner.geometric_product(v).geometric_product(&rev)
     }
@@ -115,14 +335,14 @@ impl Rotor {
     /// Compose two rotors: self followed by other
     ///
     /// The result applies self first, then other.
-    pub fn then(&self, other: &Rotor) -> Rotor {
+    pub fn then(&self, other: &Rotor<P, Q, R>) -> Rotor<P, Q, R> {
         Rotor {
             inner: other.inner.geometric_product(&self.inner),
         }
     }
 
     /// Get the inverse rotor (reverse rotation)
-    pub fn inverse(&self) -> Rotor {
+    pub fn inverse(&self) -> Rotor<P, Q, R> {
         // For unit rotors, inverse = reverse
         Rotor {
             inner: self.inner.reverse(),
@@ -130,10 +350,10 @@ impl Rotor {
     }
 
     /// Normalize the rotor to unit magnitude
-    pub fn normalize(&self) -> Rotor {
+    pub fn normalize(&self) -> Rotor<P, Q, R> {
         match self.inner.normalize() {
             Some(normalized) => Rotor { inner: normalized },
-            None => Self::identity(),
+            None => Self::unit(),
         }
     }
 
@@ -145,40 +365,10 @@ impl Rotor {
         2.0 * scalar.clamp(-1.0, 1.0).acos()
     }
 
-    /// Convert to an amari-core `Rotor<3,0,0>`
-    ///
-    /// This enables interop with amari-core's typed rotor operations
-    /// (slerp, compose, logarithm, power, etc.)
-    pub fn to_amari_rotor(&self) -> AmariRotor<3, 0, 0> {
-        // Extract and normalize the bivector plane
-        let e12 = self.inner.get(3);
-        let e13 = self.inner.get(5);
-        let e23 = self.inner.get(6);
-        let biv_mag = (e12 * e12 + e13 * e13 + e23 * e23).sqrt();
-
-        if biv_mag < 1e-10 {
-            // Identity or near-identity rotor
-            return AmariRotor::identity();
-        }
-
-        // Create unit bivector (negate to match amari convention;
-        // cliffy negates bivector components in from_bivector_angle)
-        let biv =
-            Bivector::<3, 0, 0>::from_components(-e12 / biv_mag, -e13 / biv_mag, -e23 / biv_mag);
-        AmariRotor::from_bivector(&biv, self.angle())
-    }
-
-    /// Create from an amari-core `Rotor<3,0,0>`
-    pub fn from_amari_rotor(rotor: &AmariRotor<3, 0, 0>) -> Self {
-        Self {
-            inner: rotor.as_multivector().clone(),
-        }
-    }
-
     /// Spherical linear interpolation between identity and this rotor
     ///
     /// t=0 gives identity, t=1 gives self
-    pub fn slerp(&self, t: f64) -> Rotor {
+    pub fn slerp(&self, t: f64) -> Rotor<P, Q, R> {
         let angle = self.angle();
         let new_angle = angle * t;
 
@@ -191,9 +381,9 @@ impl Rotor {
 
         if biv_mag < 1e-10 {
             // No rotation - return identity
-            Self::identity()
+            Self::unit()
         } else {
-            Self::from_bivector_angle(
+            Self::from_plane(
                 new_angle,
                 biv_xy / biv_mag,
                 biv_xz / biv_mag,
@@ -203,13 +393,36 @@ impl Rotor {
     }
 
     /// Spherical linear interpolation between two rotors
-    pub fn slerp_to(&self, other: &Rotor, t: f64) -> Rotor {
+    pub fn slerp_to(&self, other: &Rotor<P, Q, R>, t: f64) -> Rotor<P, Q, R> {
         // Compute relative rotation: other = relative * self
         // So relative = other * self.inverse()
         let relative = other.then(&self.inverse());
         let interpolated = relative.slerp(t);
         interpolated.then(self)
     }
+
+    /// Rotate the Euclidean vector (x, y, z)
+    fn rotate_vector(&self, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        let v = Multivector::from_vector(&Vector::<P, Q, R>::from_components(x, y, z));
+        let rotated = self.transform(&v);
+        (rotated.get(1), rotated.get(2), rotated.get(4))
+    }
+}
+
+/// Axis order for `Rotor::from_euler`
+///
+/// Orders follow three.js: for `XYZ` the rotation matrix is `Rx·Ry·Rz`,
+/// which rotates about X, then the new Y, then the new Z (intrinsic), or
+/// equivalently about the fixed Z, Y and X axes in that order (extrinsic).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EulerOrder {
+    #[default]
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
 }
 
 /// A versor is a general geometric transformation (rotation, reflection, or their composition).
@@ -217,20 +430,18 @@ impl Rotor {
 /// Every versor can be written as a product of vectors.
 /// Versors with an even number of vectors are rotors.
 /// Versors with an odd number of vectors include reflections.
+///
+/// In PGA3 and CGA3, translators and motors (rotation followed by
+/// translation) are even versors too.
 #[derive(Clone, Debug)]
-pub struct Versor {
+pub struct Versor<const P: usize = 3, const Q: usize = 0, const R: usize = 0> {
     /// The internal multivector representation
-    inner: GA3,
+    inner: Multivector<P, Q, R>,
     /// Whether this is an even versor (rotor) or odd versor (includes reflection)
     is_even: bool,
 }
 
 impl Versor {
-    /// Create a versor from a multivector
-    pub fn from_multivector(mv: GA3, is_even: bool) -> Self {
-        Self { inner: mv, is_even }
-    }
-
     /// Create the identity versor
     pub fn identity() -> Self {
         Self {
@@ -255,17 +466,44 @@ impl Versor {
             is_even: false,
         }
     }
+}
+
+impl<const P: usize, const Q: usize, const R: usize> Versor<P, Q, R> {
+    /// Create a versor from a multivector
+    pub fn from_multivector(mv: Multivector<P, Q, R>, is_even: bool) -> Self {
+        Self { inner: mv, is_even }
+    }
 
     /// Convert from a Rotor
-    pub fn from_rotor(rotor: Rotor) -> Self {
+    pub fn from_rotor(rotor: Rotor<P, Q, R>) -> Self {
         Self {
             inner: rotor.inner,
             is_even: true,
         }
     }
 
+    /// Create a translator: a translation as a versor (PGA3, CGA3)
+    pub fn translator(x: f64, y: f64, z: f64) -> Self
+    where
+        Multivector<P, Q, R>: TranslatorAlgebra,
+    {
+        Self {
+            inner: Multivector::translator(x, y, z),
+            is_even: true,
+        }
+    }
+
+    /// Create a motor: `rotor` followed by `translation`, as a single versor
+    /// (PGA3, CGA3)
+    pub fn motor(rotor: &Rotor<P, Q, R>, translation: &Translation) -> Self
+    where
+        Multivector<P, Q, R>: TranslatorAlgebra,
+    {
+        Self::from_rotor(rotor.clone()).then(&translation.to_versor())
+    }
+
     /// Get the internal multivector
-    pub fn as_multivector(&self) -> &GA3 {
+    pub fn as_multivector(&self) -> &Multivector<P, Q, R> {
         &self.inner
     }
 
@@ -273,7 +511,7 @@ impl Versor {
     ///
     /// For even versors: V * v * V†
     /// For odd versors: V * v * V† (with grade involution handling)
-    pub fn transform(&self, v: &GA3) -> GA3 {
+    pub fn transform(&self, v: &Multivector<P, Q, R>) -> Multivector<P, Q, R> {
         let rev = self.inner.reverse();
         if self.is_even {
             self.inner.geometric_product(v).geometric_product(&rev)
@@ -286,7 +524,7 @@ impl Versor {
     }
 
     /// Compose two versors
-    pub fn then(&self, other: &Versor) -> Versor {
+    pub fn then(&self, other: &Versor<P, Q, R>) -> Versor<P, Q, R> {
         Versor {
             inner: other.inner.geometric_product(&self.inner),
             is_even: self.is_even == other.is_even, // Even * Even = Even, Odd * Odd = Even
@@ -299,7 +537,7 @@ impl Versor {
     }
 
     /// Try to convert to a Rotor (only succeeds for even versors)
-    pub fn to_rotor(&self) -> Option<Rotor> {
+    pub fn to_rotor(&self) -> Option<Rotor<P, Q, R>> {
         if self.is_even {
             Some(Rotor {
                 inner: self.inner.clone(),
@@ -314,7 +552,8 @@ impl Versor {
 ///
 /// In standard GA3 (Euclidean), translations are not directly representable
 /// as versors. This type provides a convenient API that internally uses
-/// vector addition.
+/// vector addition. In PGA3 and CGA3 it is applied as a translator versor
+/// (see `to_versor`).
 #[derive(Clone, Debug)]
 pub struct Translation {
     /// Translation vector components
@@ -344,14 +583,32 @@ impl Translation {
         Self::new(0.0, 0.0, amount)
     }
 
+    /// The translation vector as (x, y, z)
+    pub fn components(&self) -> (f64, f64, f64) {
+        (self.x, self.y, self.z)
+    }
+
     /// Apply this translation to a multivector
     ///
-    /// For vectors, this adds the translation. For other grades,
-    /// behavior depends on the geometric interpretation.
-    pub fn transform(&self, v: &GA3) -> GA3 {
-        let trans_vec = Vector::<3, 0, 0>::from_components(self.x, self.y, self.z);
-        let trans_mv = GA3::from_vector(&trans_vec);
-        v + &trans_mv
+    /// In GA3 this adds the translation vector, which is only meaningful
+    /// for positions. In PGA3 and CGA3 it is a translator sandwich, so
+    /// lines, planes and points all move rigidly.
+    pub fn transform<const P: usize, const Q: usize, const R: usize>(
+        &self,
+        v: &Multivector<P, Q, R>,
+    ) -> Multivector<P, Q, R>
+    where
+        Multivector<P, Q, R>: EuclideanEmbedding,
+    {
+        v.translate(self.x, self.y, self.z)
+    }
+
+    /// This translation as a translator versor (PGA3, CGA3)
+    pub fn to_versor<const P: usize, const Q: usize, const R: usize>(&self) -> Versor<P, Q, R>
+    where
+        Multivector<P, Q, R>: TranslatorAlgebra,
+    {
+        Versor::translator(self.x, self.y, self.z)
     }
 
     /// Compose two translations
@@ -393,51 +650,66 @@ impl Translation {
 
 /// A general geometric transformation combining rotation and translation
 #[derive(Clone, Debug)]
-pub struct Transform {
+pub struct Transform<const P: usize = 3, const Q: usize = 0, const R: usize = 0> {
     /// Rotation component
-    rotor: Rotor,
+    rotor: Rotor<P, Q, R>,
     /// Translation component (applied after rotation)
     translation: Translation,
 }
 
 impl Transform {
-    /// Create a new transform with rotation and translation
-    pub fn new(rotor: Rotor, translation: Translation) -> Self {
-        Self { rotor, translation }
-    }
-
     /// Create identity transform
     pub fn identity() -> Self {
+        Self::rotation(Rotor::identity())
+    }
+
+    /// Create a pure translation transform
+    pub fn translation(translation: Translation) -> Self {
         Self {
             rotor: Rotor::identity(),
-            translation: Translation::new(0.0, 0.0, 0.0),
+            translation,
         }
     }
+}
+
+impl<const P: usize, const Q: usize, const R: usize> Transform<P, Q, R> {
+    /// Create a new transform with rotation and translation
+    pub fn new(rotor: Rotor<P, Q, R>, translation: Translation) -> Self {
+        Self { rotor, translation }
+    }
 
     /// Create a pure rotation transform
-    pub fn rotation(rotor: Rotor) -> Self {
+    pub fn rotation(rotor: Rotor<P, Q, R>) -> Self {
         Self {
             rotor,
             translation: Translation::new(0.0, 0.0, 0.0),
         }
     }
 
-    /// Create a pure translation transform
-    pub fn translation(translation: Translation) -> Self {
-        Self {
-            rotor: Rotor::identity(),
-            translation,
-        }
+    /// The rotation and the translation applied after it
+    pub fn parts(&self) -> (&Rotor<P, Q, R>, &Translation) {
+        (&self.rotor, &self.translation)
     }
 
     /// Apply this transform to a multivector
-    pub fn transform(&self, v: &GA3) -> GA3 {
+    pub fn transform(&self, v: &Multivector<P, Q, R>) -> Multivector<P, Q, R>
+    where
+        Multivector<P, Q, R>: EuclideanEmbedding,
+    {
         let rotated = self.rotor.transform(v);
         self.translation.transform(&rotated)
     }
 
+    /// This transform as a single motor versor (PGA3, CGA3)
+    pub fn to_versor(&self) -> Versor<P, Q, R>
+    where
+        Multivector<P, Q, R>: TranslatorAlgebra,
+    {
+        Versor::motor(&self.rotor, &self.translation)
+    }
+
     /// Compose two transforms: self followed by other
-    pub fn then(&self, other: &Transform) -> Transform {
+    pub fn then(&self, other: &Transform<P, Q, R>) -> Transform<P, Q, R> {
         // First apply self's rotation, then self's translation
         // Then apply other's rotation, then other's translation
         //
@@ -446,18 +718,15 @@ impl Transform {
         let combined_rotor = self.rotor.then(&other.rotor);
 
         // Transform self's translation by other's rotation, then add other's translation
-        let self_trans_vec = Vector::<3, 0, 0>::from_components(
-            self.translation.x,
-            self.translation.y,
-            self.translation.z,
-        );
-        let self_trans_mv = GA3::from_vector(&self_trans_vec);
-        let rotated_trans = other.rotor.transform(&self_trans_mv);
+        let (x, y, z) =
+            other
+                .rotor
+                .rotate_vector(self.translation.x, self.translation.y, self.translation.z);
 
         let combined_translation = Translation::new(
-            rotated_trans.get(1) + other.translation.x,
-            rotated_trans.get(2) + other.translation.y,
-            rotated_trans.get(4) + other.translation.z,
+            x + other.translation.x,
+            y + other.translation.y,
+            z + other.translation.z,
         );
 
         Transform {
@@ -467,27 +736,27 @@ impl Transform {
     }
 
     /// Get the inverse transform
-    pub fn inverse(&self) -> Transform {
+    pub fn inverse(&self) -> Transform<P, Q, R> {
         let inv_rotor = self.rotor.inverse();
         let inv_trans_base = self.translation.inverse();
 
         // Rotate the inverse translation by the inverse rotation
-        let trans_vec = Vector::<3, 0, 0>::from_components(
-            inv_trans_base.x,
-            inv_trans_base.y,
-            inv_trans_base.z,
-        );
-        let trans_mv = GA3::from_vector(&trans_vec);
-        let rotated = inv_rotor.transform(&trans_mv);
+        let (x, y, z) =
+            inv_rotor.rotate_vector(inv_trans_base.x, inv_trans_base.y, inv_trans_base.z);
 
         Transform {
             rotor: inv_rotor,
-            translation: Translation::new(rotated.get(1), rotated.get(2), rotated.get(4)),
+            translation: Translation::new(x, y, z),
         }
     }
 
     /// Interpolate between identity and this transform
-    pub fn interpolate(&self, t: f64) -> Transform {
+    ///
+    /// The rotation and translation are blended independently, which is
+    /// not the rigid-body (screw) path when both change: a point rotated
+    /// about an off-origin pivot cuts across the arc. Use `sclerp` (or
+    /// `interpolate_with` and `Interpolation::Screw`) for the screw path.
+    pub fn interpolate(&self, t: f64) -> Transform<P, Q, R> {
         Transform {
             rotor: self.rotor.slerp(t),
             translation: self.translation.lerp(t),
@@ -495,7 +764,10 @@ impl Transform {
     }
 
     /// Interpolate to another transform
-    pub fn interpolate_to(&self, other: &Transform, t: f64) -> Transform {
+    ///
+    /// Blends rotation and translation independently, like `interpolate`;
+    /// `sclerp_to` follows the screw path.
+    pub fn interpolate_to(&self, other: &Transform<P, Q, R>, t: f64) -> Transform<P, Q, R> {
         Transform {
             rotor: self.rotor.slerp_to(&other.rotor, t),
             translation: self.translation.lerp_to(&other.translation, t),
@@ -503,6 +775,294 @@ impl Transform {
     }
 }
 
+/// How `Transform::interpolate_with` moves between two transforms
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Rotation (slerp) and translation (lerp) blended independently
+    #[default]
+    Independent,
+    /// Along the screw axis of the relative motion (see `Transform::sclerp`)
+    Screw,
+}
+
+impl Transform {
+    /// Interpolate between identity and this transform, along the path
+    /// `mode` chooses
+    pub fn interpolate_with(&self, t: f64, mode: Interpolation) -> Transform {
+        match mode {
+            Interpolation::Independent => self.interpolate(t),
+            Interpolation::Screw => self.sclerp(t),
+        }
+    }
+
+    /// Interpolate to another transform, along the path `mode` chooses
+    pub fn interpolate_to_with(&self, other: &Transform, t: f64, mode: Interpolation) -> Transform {
+        match mode {
+            Interpolation::Independent => self.interpolate_to(other, t),
+            Interpolation::Screw => self.sclerp_to(other, t),
+        }
+    }
+
+    /// Screw interpolation between identity and this transform
+    ///
+    /// Unlike `interpolate`, which blends rotation and translation
+    /// independently, this follows the rigid-body path: a constant-speed
+    /// rotation about, and translation along, a single screw axis.
+    pub fn sclerp(&self, t: f64) -> Transform {
+        Motor::from_transform(self).pow(t).to_transform()
+    }
+
+    /// Screw interpolation to another transform (see `sclerp`)
+    pub fn sclerp_to(&self, other: &Transform, t: f64) -> Transform {
+        Motor::from_transform(self)
+            .sclerp_to(&Motor::from_transform(other), t)
+            .to_transform()
+    }
+}
+
+/// A rigid motion as a PGA3 motor
+///
+/// A motor is an even element of PGA3 (scalar, six bivector components and
+/// the pseudoscalar), which is the geometric-algebra form of a unit dual
+/// quaternion. Every rigid motion is a screw: a rotation about a line
+/// combined with a translation along it. `log` recovers that screw as a
+/// bivector, `exp` builds the motor back, and `sclerp_to` moves along the
+/// screw at constant speed.
+///
+/// # Example
+///
+/// ```rust
+/// use cliffy_core::{GeometricState, Motor, Rotor, Transform, Translation};
+/// use std::f64::consts::PI;
+///
+/// // Half a turn about Z while climbing 2 along it
+/// let end = Motor::from_transform(&Transform::new(Rotor::xy(PI), Translation::z(2.0)));
+///
+/// // Halfway along the screw: a quarter turn and half the climb
+/// let half = Motor::identity().sclerp_to(&end, 0.5).to_transform();
+/// let (x, y, z) = GeometricState::from_vector(1.0, 0.0, 0.0)
+///     .apply_transform(&half)
+///     .as_vector();
+///
+/// assert!(x.abs() < 1e-10 && (y - 1.0).abs() < 1e-10 && (z - 1.0).abs() < 1e-10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Motor {
+    /// Even-grade PGA3 multivector
+    inner: PGA3,
+}
+
+impl Motor {
+    /// The identity motion
+    pub fn identity() -> Self {
+        Self {
+            inner: PGA3::scalar(1.0),
+        }
+    }
+
+    /// Create a motor from a multivector (assumes it's already a valid motor)
+    pub fn from_multivector(mv: PGA3) -> Self {
+        Self { inner: mv }
+    }
+
+    /// Get the internal multivector
+    pub fn as_multivector(&self) -> &PGA3 {
+        &self.inner
+    }
+
+    /// The motor of a `Transform` (its rotation followed by its translation)
+    pub fn from_transform(transform: &Transform) -> Self {
+        let embedded =
+            Transform::<3, 0, 1>::new(transform.rotor.embed(), transform.translation.clone());
+        Self {
+            inner: embedded.to_versor().as_multivector().clone(),
+        }
+    }
+
+    /// Split the motion back into a rotation followed by a translation
+    pub fn to_transform(&self) -> Transform {
+        let motor = self.normalize();
+
+        // M = T R, and the e0-free part of T R is R
+        let mut rotor = GA3::zero();
+        for index in [0, 3, 5, 6] {
+            rotor.set(index, motor.inner.get(index));
+        }
+
+        // R fixes the origin, so M moves it by exactly the translation
+        let (x, y, z) = motor.transform(&PGA3::point(0.0, 0.0, 0.0)).to_point();
+
+        Transform::new(Rotor::from_multivector(rotor), Translation::new(x, y, z))
+    }
+
+    /// Apply this motor to a PGA3 element (sandwich product)
+    pub fn transform(&self, mv: &PGA3) -> PGA3 {
+        self.inner
+            .geometric_product(mv)
+            .geometric_product(&self.inner.reverse())
+    }
+
+    /// Compose two motors: self followed by other
+    pub fn compose(&self, other: &Motor) -> Motor {
+        Motor {
+            inner: other.inner.geometric_product(&self.inner),
+        }
+    }
+
+    /// Get the inverse motion
+    pub fn inverse(&self) -> Motor {
+        // For unit motors, inverse = reverse
+        Motor {
+            inner: self.inner.reverse(),
+        }
+    }
+
+    /// Normalize to a unit motor (`M M̃ = 1`)
+    ///
+    /// Besides scaling, this removes the pseudoscalar part of `M M̃` that
+    /// accumulates through repeated composition.
+    pub fn normalize(&self) -> Motor {
+        let norm = self.inner.geometric_product(&self.inner.reverse());
+        let s = norm.get(0);
+        if s < 1e-14 {
+            return Self::identity();
+        }
+
+        // 1/√(s + pI) = (1 - p/(2s) I) / √s
+        let p = dual_part(&norm);
+        let correction = &PGA3::scalar(1.0) - &(&pga::pseudoscalar() * (p / (2.0 * s)));
+        Motor {
+            inner: &self.inner.geometric_product(&correction) * (1.0 / s.sqrt()),
+        }
+    }
+
+    /// The bivector `B` with `exp(B) = self`
+    ///
+    /// `B = ½(θ + d I) L` for a unit screw axis `L`, rotation angle `θ` about
+    /// it and translation `d` along it. Of the two motors representing each
+    /// motion, the one with non-negative scalar part is used, so `log`
+    /// always describes the shorter screw.
+    pub fn log(&self) -> PGA3 {
+        let mut motor = self.normalize().inner;
+        if motor.get(0) < 0.0 {
+            motor = &motor * -1.0;
+        }
+
+        let bivector = motor.grade_projection(2);
+        let sin = euclidean_bivector_norm(&bivector);
+        if sin < 1e-12 {
+            // Pure translation: M = 1 + B with B² = 0
+            return bivector;
+        }
+
+        // M = cos(a + βI) + sin(a + βI) L̂ with a = θ/2, β = d/2
+        let a = sin.atan2(motor.get(0));
+        let beta = -dual_part(&motor) / sin;
+        let dual_factor = beta * (1.0 - a * a.cos() / sin) / sin;
+
+        &(&bivector * (a / sin))
+            + &(&pga::pseudoscalar().geometric_product(&bivector) * dual_factor)
+    }
+
+    /// The motor `exp(B)` of a PGA3 bivector
+    pub fn exp(bivector: &PGA3) -> Motor {
+        let bivector = bivector.grade_projection(2);
+        let a = euclidean_bivector_norm(&bivector);
+        if a < 1e-12 {
+            // B² = 0, so the series stops after the linear term
+            return Motor {
+                inner: &PGA3::scalar(1.0) + &bivector,
+            };
+        }
+
+        // B = (a + βI) L̂ with L̂² = -1, so B² = -a² - 2aβI
+        let square = bivector.geometric_product(&bivector);
+        let beta = -dual_part(&square) / (2.0 * a);
+        let i = pga::pseudoscalar();
+        let unit_axis = &(&bivector - &(&i.geometric_product(&bivector) * (beta / a))) * (1.0 / a);
+
+        // exp((a + βI) L̂) = cos(a + βI) + sin(a + βI) L̂
+        let (sin, cos) = a.sin_cos();
+        let scalar = &PGA3::scalar(cos) - &(&i * (beta * sin));
+        let axis_factor = &PGA3::scalar(sin) + &(&i * (beta * cos));
+        Motor {
+            inner: &scalar + &axis_factor.geometric_product(&unit_axis),
+        }
+    }
+
+    /// The motion scaled along its screw: `exp(t log M)`
+    ///
+    /// t=0 gives identity, t=1 gives self
+    pub fn pow(&self, t: f64) -> Motor {
+        Motor::exp(&(&self.log() * t))
+    }
+
+    /// Screw linear interpolation (ScLERP) to another motor
+    ///
+    /// t=0 gives self, t=1 gives other; in between the motion is a constant
+    /// rotation about and translation along the screw relating the two.
+    pub fn sclerp_to(&self, other: &Motor, t: f64) -> Motor {
+        let relative = self.inverse().compose(other);
+        self.compose(&relative.pow(t))
+    }
+}
+
+/// Coefficient of the pseudoscalar e0123
+fn dual_part(mv: &PGA3) -> f64 {
+    let i = pga::pseudoscalar();
+    mv.get(15) * i.get(15)
+}
+
+/// Norm of the Euclidean (e12, e13, e23) part of a bivector
+fn euclidean_bivector_norm(mv: &PGA3) -> f64 {
+    (mv.get(3).powi(2) + mv.get(5).powi(2) + mv.get(6).powi(2)).sqrt()
+}
+
+// ============================================================================
+// Geometric encodings - so transforms can be held in a Behavior
+// ============================================================================
+
+/// Rotors are stored as their (even) multivector
+impl IntoGeometric for Rotor {
+    fn into_geometric(self) -> GA3 {
+        self.inner
+    }
+}
+
+impl FromGeometric for Rotor {
+    fn from_geometric(mv: &GA3) -> Self {
+        Rotor::from_multivector(&mv.grade_projection(0) + &mv.grade_projection(2))
+    }
+}
+
+/// Translations are stored as the vector (x, y, z)
+impl IntoGeometric for Translation {
+    fn into_geometric(self) -> GA3 {
+        GA3::point(self.x, self.y, self.z)
+    }
+}
+
+impl FromGeometric for Translation {
+    fn from_geometric(mv: &GA3) -> Self {
+        let (x, y, z) = mv.to_point();
+        Translation::new(x, y, z)
+    }
+}
+
+/// The rotor occupies the even grades and the translation the vector
+/// grade, so a transform fits losslessly in one multivector
+impl IntoGeometric for Transform {
+    fn into_geometric(self) -> GA3 {
+        &self.rotor.inner + &GA3::point(self.translation.x, self.translation.y, self.translation.z)
+    }
+}
+
+impl FromGeometric for Transform {
+    fn from_geometric(mv: &GA3) -> Self {
+        Transform::new(Rotor::from_geometric(mv), Translation::from_geometric(mv))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -644,4 +1204,343 @@ mod tests {
         assert!((result1.get(2) - result2.get(2)).abs() < 1e-10);
         assert!((result1.get(4) - result2.get(4)).abs() < 1e-10);
     }
+
+    fn sample_rotors() -> Vec<Rotor> {
+        vec![
+            Rotor::identity(),
+            Rotor::xy(PI / 3.0),
+            Rotor::yz(-2.5),
+            Rotor::from_axis_angle(1.0, 2.0, 3.0, 0.8),
+            Rotor::from_axis_angle(-1.0, 0.5, 0.2, 3.0),
+            Rotor::from_axis_angle(0.0, 1.0, 0.0, PI),
+        ]
+    }
+
+    fn assert_matrices_match(a: [[f64; 3]; 3], b: [[f64; 3]; 3]) {
+        for row in 0..3 {
+            for col in 0..3 {
+                assert!(
+                    (a[row][col] - b[row][col]).abs() < 1e-9,
+                    "{:?} != {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    fn multiply(a: [[f64; 3]; 3], b: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
+        let mut m = [[0.0; 3]; 3];
+        for (row, values) in m.iter_mut().enumerate() {
+            for (col, value) in values.iter_mut().enumerate() {
+                *value = (0..3).map(|k| a[row][k] * b[k][col]).sum();
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn test_rotor_matrix_roundtrip() {
+        for r in sample_rotors() {
+            let expected = r.to_amari_rotor().to_rotation_matrix();
+            assert_matrices_match(r.to_matrix3(), expected);
+            assert_matrices_match(Rotor::from_matrix3(expected).to_matrix3(), expected);
+
+            let m4 = r.to_matrix4();
+            for (row, values) in r.to_matrix3().iter().enumerate() {
+                assert_eq!(m4[row][..3], values[..]);
+                assert_eq!((m4[row][3], m4[3][row]), (0.0, 0.0));
+            }
+            assert_eq!(m4[3][3], 1.0);
+        }
+    }
+
+    #[test]
+    fn test_rotor_quaternion_roundtrip() {
+        // Right-handed quaternions: (sin(θ/2) n, cos(θ/2))
+        let (x, y, z, w) = Rotor::from_axis_angle(0.0, 0.6, 0.8, 1.0).to_quaternion();
+        let s = 0.5f64.sin();
+        assert!((x).abs() < 1e-12 && (y - 0.6 * s).abs() < 1e-12 && (z - 0.8 * s).abs() < 1e-12);
+        assert!((w - 0.5f64.cos()).abs() < 1e-12);
+
+        for r in sample_rotors() {
+            let (x, y, z, w) = r.to_quaternion();
+            let restored = Rotor::from_quaternion(x, y, z, w);
+            assert_matrices_match(
+                restored.to_amari_rotor().to_rotation_matrix(),
+                r.to_amari_rotor().to_rotation_matrix(),
+            );
+
+            let (x2, y2, z2, w2) = Rotor::from_amari_rotor(&r.to_amari_rotor()).to_quaternion();
+            let sign = if w * w2 + x * x2 + y * y2 + z * z2 < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            for (a, b) in [(x, x2), (y, y2), (z, z2), (w, w2)] {
+                assert!((a - sign * b).abs() < 1e-9);
+            }
+        }
+
+        // Non-unit quaternions are normalized
+        let (_, _, z, w) = Rotor::from_quaternion(0.0, 0.0, 2.0, 2.0).to_quaternion();
+        assert!((z - 0.5f64.sqrt()).abs() < 1e-12 && (w - 0.5f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rotor_from_vectors() {
+        let pairs = [
+            ((1.0, 0.0, 0.0), (0.0, 1.0, 0.0)),
+            ((1.0, 2.0, 3.0), (-2.0, 0.5, 1.0)),
+            ((0.0, 0.0, 2.0), (0.0, 0.0, 5.0)),
+            ((1.0, 0.0, 0.0), (-3.0, 0.0, 0.0)),
+            ((0.0, 1.0, 1.0), (0.0, -1.0, -1.0)),
+        ];
+        for (a, b) in pairs {
+            let r = Rotor::from_vectors(a, b);
+            let (x, y, z) = r.rotate_vector(a.0, a.1, a.2);
+            let scale = (a.0 * a.0 + a.1 * a.1 + a.2 * a.2).sqrt()
+                / (b.0 * b.0 + b.1 * b.1 + b.2 * b.2).sqrt();
+            assert!((x - b.0 * scale).abs() < 1e-9);
+            assert!((y - b.1 * scale).abs() < 1e-9);
+            assert!((z - b.2 * scale).abs() < 1e-9);
+
+            let (a_mv, b_mv) = (GA3::point(a.0, a.1, a.2), GA3::point(b.0, b.1, b.2));
+            if let Some(amari) = AmariRotor::from_vectors_mv(&a_mv, &b_mv) {
+                if a_mv.scalar_product(&b_mv) > -1e-6 {
+                    assert_matrices_match(
+                        r.to_amari_rotor().to_rotation_matrix(),
+                        amari.to_rotation_matrix(),
+                    );
+                }
+            }
+        }
+
+        assert_eq!(
+            Rotor::from_vectors((0.0, 0.0, 0.0), (1.0, 0.0, 0.0)).angle(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_rotor_from_euler() {
+        let (x, y, z) = (0.3, -1.1, 2.0);
+        let rx = Rotor::from_axis_angle(1.0, 0.0, 0.0, x)
+            .to_amari_rotor()
+            .to_rotation_matrix();
+        let ry = Rotor::from_axis_angle(0.0, 1.0, 0.0, y)
+            .to_amari_rotor()
+            .to_rotation_matrix();
+        let rz = Rotor::from_axis_angle(0.0, 0.0, 1.0, z)
+            .to_amari_rotor()
+            .to_rotation_matrix();
+
+        let orders = [
+            (EulerOrder::XYZ, [rx, ry, rz]),
+            (EulerOrder::XZY, [rx, rz, ry]),
+            (EulerOrder::YXZ, [ry, rx, rz]),
+            (EulerOrder::YZX, [ry, rz, rx]),
+            (EulerOrder::ZXY, [rz, rx, ry]),
+            (EulerOrder::ZYX, [rz, ry, rx]),
+        ];
+        for (order, [a, b, c]) in orders {
+            let r = Rotor::from_euler(order, x, y, z);
+            assert_matrices_match(
+                r.to_amari_rotor().to_rotation_matrix(),
+                multiply(multiply(a, b), c),
+            );
+        }
+
+        // A quarter turn about Z alone takes X to Y
+        let (x, y, _) =
+            Rotor::from_euler(EulerOrder::XYZ, 0.0, 0.0, PI / 2.0).rotate_vector(1.0, 0.0, 0.0);
+        assert!(x.abs() < 1e-12 && (y - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_embedded_rotor_matches_ga3() {
+        use crate::algebra::EuclideanEmbedding;
+        use crate::geometric::{CGA3, PGA3};
+
+        let r = Rotor::from_axis_angle(1.0, 2.0, 3.0, 0.8);
+        let expected = r.transform(&GA3::point(1.0, -1.0, 2.0)).to_point();
+
+        let pga: Rotor<3, 0, 1> = r.embed();
+        let cga: Rotor<4, 1, 0> = r.embed();
+        let in_pga = pga.transform(&PGA3::point(1.0, -1.0, 2.0)).to_point();
+        let in_cga = cga.transform(&CGA3::point(1.0, -1.0, 2.0)).to_point();
+
+        for actual in [in_pga, in_cga] {
+            assert!((actual.0 - expected.0).abs() < 1e-10);
+            assert!((actual.1 - expected.1).abs() < 1e-10);
+            assert!((actual.2 - expected.2).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_motor_matches_transform() {
+        use crate::algebra::EuclideanEmbedding;
+        use crate::geometric::PGA3;
+
+        let transform: Transform<3, 0, 1> = Transform::new(
+            Rotor::from_axis_angle(0.0, 1.0, 1.0, 1.1).embed(),
+            Translation::new(1.0, -2.0, 0.5),
+        );
+        let point = PGA3::point(0.3, 0.4, -0.2);
+
+        let by_parts = transform.transform(&point).to_point();
+        let by_motor = transform.to_versor().transform(&point).to_point();
+
+        assert!((by_parts.0 - by_motor.0).abs() < 1e-10);
+        assert!((by_parts.1 - by_motor.1).abs() < 1e-10);
+        assert!((by_parts.2 - by_motor.2).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_translator_composes_in_cga() {
+        use crate::algebra::EuclideanEmbedding;
+        use crate::geometric::CGA3;
+
+        let combined: Versor<4, 1, 0> = Translation::new(1.0, 0.0, 0.0)
+            .to_versor()
+            .then(&Versor::translator(0.0, 2.0, 0.0));
+        let (x, y, z) = combined.transform(&CGA3::point(0.0, 0.0, 1.0)).to_point();
+
+        assert!((x - 1.0).abs() < 1e-10);
+        assert!((y - 2.0).abs() < 1e-10);
+        assert!((z - 1.0).abs() < 1e-10);
+    }
+
+    fn assert_transforms_match(a: &Transform, b: &Transform) {
+        for (x, y, z) in [(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.3, -2.0, 1.5)] {
+            let v = GA3::from_vector(&Vector::from_components(x, y, z));
+            let (pa, pb) = (a.transform(&v), b.transform(&v));
+            for index in [1, 2, 4] {
+                assert!(
+                    (pa.get(index) - pb.get(index)).abs() < 1e-9,
+                    "{:?} != {:?}",
+                    pa,
+                    pb
+                );
+            }
+        }
+    }
+
+    fn sample_transform() -> Transform {
+        Transform::new(
+            Rotor::from_axis_angle(1.0, -2.0, 0.5, 2.1),
+            Translation::new(0.7, 1.5, -3.0),
+        )
+    }
+
+    #[test]
+    fn test_motor_transform_roundtrip() {
+        let transform = sample_transform();
+        let back = Motor::from_transform(&transform).to_transform();
+        assert_transforms_match(&transform, &back);
+    }
+
+    #[test]
+    fn test_motor_compose_matches_transform_then() {
+        let a = sample_transform();
+        let b = Transform::new(Rotor::yz(0.4), Translation::new(-1.0, 0.0, 2.0));
+
+        let composed = Motor::from_transform(&a)
+            .compose(&Motor::from_transform(&b))
+            .to_transform();
+        assert_transforms_match(&composed, &a.then(&b));
+
+        let undone = Motor::from_transform(&a)
+            .compose(&Motor::from_transform(&a).inverse())
+            .to_transform();
+        assert_transforms_match(&undone, &Transform::identity());
+    }
+
+    #[test]
+    fn test_motor_exp_log_roundtrip() {
+        let motor = Motor::from_transform(&sample_transform());
+        let back = Motor::exp(&motor.log());
+        assert_transforms_match(&back.to_transform(), &motor.to_transform());
+
+        let translation = Motor::from_transform(&Transform::translation(Translation::x(3.0)));
+        let back = Motor::exp(&translation.log());
+        assert_transforms_match(&back.to_transform(), &translation.to_transform());
+    }
+
+    #[test]
+    fn test_motor_exp_matches_series() {
+        let bivector = Motor::from_transform(&sample_transform()).log();
+
+        // exp(B) = Σ Bᵏ/k!
+        let mut term = PGA3::scalar(1.0);
+        let mut series = PGA3::scalar(1.0);
+        for k in 1..40 {
+            term = &term.geometric_product(&bivector) * (1.0 / k as f64);
+            series = &series + &term;
+        }
+
+        let closed = Motor::exp(&bivector);
+        for index in 0..16 {
+            assert!((closed.as_multivector().get(index) - series.get(index)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_sclerp_follows_screw() {
+        // A half turn about the vertical axis through (1, 0, 0)
+        let pivot = Translation::new(1.0, 0.0, 0.0);
+        let about_pivot = Transform::translation(pivot.inverse())
+            .then(&Transform::rotation(Rotor::xy(PI)))
+            .then(&Transform::translation(pivot));
+
+        // The origin swings around the pivot, staying 1 away from it
+        for t in [0.25, 0.5, 0.75] {
+            let moved = about_pivot.sclerp(t).transform(&GA3::zero());
+            let (dx, dy) = (moved.get(1) - 1.0, moved.get(2));
+            assert!(((dx * dx + dy * dy).sqrt() - 1.0).abs() < 1e-9);
+        }
+
+        // Decoupled interpolation cuts through the middle instead
+        let moved = about_pivot.interpolate(0.5).transform(&GA3::zero());
+        let (dx, dy) = (moved.get(1) - 1.0, moved.get(2));
+        assert!(((dx * dx + dy * dy).sqrt() - 1.0).abs() > 0.1);
+
+        // The mode picks between the two
+        let start = sample_transform();
+        for t in [0.25, 0.5] {
+            assert_transforms_match(
+                &about_pivot.interpolate_with(t, Interpolation::Screw),
+                &about_pivot.sclerp(t),
+            );
+            assert_transforms_match(
+                &start.interpolate_to_with(&about_pivot, t, Interpolation::Independent),
+                &start.interpolate_to(&about_pivot, t),
+            );
+            assert_transforms_match(
+                &start.interpolate_to_with(&about_pivot, t, Interpolation::Screw),
+                &start.sclerp_to(&about_pivot, t),
+            );
+        }
+    }
+
+    #[test]
+    fn test_sclerp_endpoints() {
+        let a = sample_transform();
+        let b = Transform::new(Rotor::xz(-0.9), Translation::new(2.0, 2.0, 2.0));
+
+        assert_transforms_match(&a.sclerp_to(&b, 0.0), &a);
+        assert_transforms_match(&a.sclerp_to(&b, 1.0), &b);
+    }
+
+    #[test]
+    fn test_transform_geometric_roundtrip() {
+        let transform = sample_transform();
+        let decoded = Transform::from_geometric(&transform.clone().into_geometric());
+        assert_transforms_match(&decoded, &transform);
+
+        let (x, y, z) =
+            Translation::from_geometric(&Translation::new(1.0, -2.0, 3.0).into_geometric())
+                .components();
+        assert_eq!((x, y, z), (1.0, -2.0, 3.0));
+    }
 }
@@ -0,0 +1,569 @@
+//! Splines through many keyframes
+//!
+//! `Rotor::slerp_to` and `Translation::lerp_to` blend between two values;
+//! camera paths and design-tool handles pass through many. This module
+//! provides smooth curves through a sequence of keys:
+//!
+//! - `RotorSpline`: SQUAD (spherical quadrangle) through rotor keys
+//! - `TranslationSpline`: Catmull-Rom through translation keys
+//! - `TransformBezier`: a Bezier curve with transform control points,
+//!   evaluated with screw interpolation (`Transform::sclerp_to`)
+//!
+//! All of them are `Spline`s sampled with a parameter `u` in `[0, 1]`. The
+//! parameter does not advance at constant speed, so `ArcLength` re-samples
+//! a spline by distance travelled and can drive a `Behavior` from ticks.
+//!
+//! # Example
+//!
+//! ```rust
+//! use cliffy_core::transforms::spline::{ArcLength, TranslationSpline};
+//! use cliffy_core::{event, Easing, Translation};
+//! use std::time::Duration;
+//!
+//! let path = TranslationSpline::new(vec![
+//!     Translation::new(0.0, 0.0, 0.0),
+//!     Translation::new(1.0, 0.0, 0.0),
+//!     Translation::new(1.0, 5.0, 0.0),
+//! ]);
+//!
+//! let ticks = event::<f64>();
+//! let position = ArcLength::new(path, 256).animate(Easing::Linear, Duration::from_secs(2), &ticks);
+//!
+//! ticks.emit(1.0); // halfway along the path by distance
+//! ticks.emit(1.0);
+//! let (x, y, _) = position.sample().components();
+//! assert!((x - 1.0).abs() < 1e-10 && (y - 5.0).abs() < 1e-10);
+//! ```
+
+use super::{Rotor, Transform, Translation};
+use crate::behavior::Behavior;
+use crate::continuous::Easing;
+use crate::event::Event;
+use crate::geometric::{FromGeometric, IntoGeometric, GA3};
+use std::cell::Cell;
+use std::time::Duration;
+
+/// A curve sampled by a parameter in `[0, 1]`
+pub trait Spline {
+    /// The type of value along the curve
+    type Value: Clone;
+
+    /// The value at parameter `u` (clamped to `[0, 1]`)
+    fn sample(&self, u: f64) -> Self::Value;
+
+    /// Distance between two values, used for arc-length parameterisation
+    fn distance(a: &Self::Value, b: &Self::Value) -> f64;
+}
+
+/// Map `u` in `[0, 1]` to a segment index and the parameter within it
+fn segment(u: f64, segments: usize) -> (usize, f64) {
+    let scaled = u.clamp(0.0, 1.0) * segments as f64;
+    let index = (scaled.floor() as usize).min(segments - 1);
+    (index, scaled - index as f64)
+}
+
+// ============================================================================
+// Rotors - SQUAD
+// ============================================================================
+
+/// Logarithm of a unit rotor: the bivector `(θ/2) B`
+fn rotor_log(rotor: &GA3) -> GA3 {
+    let bivector = rotor.grade_projection(2);
+    let sin = bivector.magnitude();
+    if sin < 1e-12 {
+        return GA3::zero();
+    }
+    &bivector * (sin.atan2(rotor.get(0)) / sin)
+}
+
+/// Exponential of a bivector: the rotor `cos|B| + sin|B| B/|B|`
+fn rotor_exp(bivector: &GA3) -> GA3 {
+    let angle = bivector.magnitude();
+    if angle < 1e-12 {
+        return GA3::scalar(1.0);
+    }
+    &GA3::scalar(angle.cos()) + &(bivector * (angle.sin() / angle))
+}
+
+/// Spherical interpolation without choosing the shorter arc, as SQUAD
+/// requires for its inner control rotors
+fn slerp(from: &GA3, to: &GA3, t: f64) -> GA3 {
+    let relative = from.reverse().geometric_product(to);
+    from.geometric_product(&rotor_exp(&(&rotor_log(&relative) * t)))
+}
+
+/// A smooth rotation through rotor keys (SQUAD)
+///
+/// The curve passes through every key with continuous angular velocity.
+/// Keys are spaced evenly in the parameter; with a single key the spline
+/// is constant.
+#[derive(Clone, Debug)]
+pub struct RotorSpline {
+    keys: Vec<GA3>,
+    controls: Vec<GA3>,
+}
+
+impl RotorSpline {
+    /// Create a spline through `keys`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` is empty.
+    pub fn new(keys: Vec<Rotor>) -> Self {
+        assert!(!keys.is_empty(), "a spline needs at least one key");
+
+        // R and -R are the same rotation; pick signs so that consecutive
+        // keys are on the same hemisphere and every segment is the short arc
+        let mut mvs: Vec<GA3> = Vec::with_capacity(keys.len());
+        for key in keys {
+            let mv = key.normalize().as_multivector().clone();
+            let flip = mvs
+                .last()
+                .is_some_and(|previous| previous.scalar_product(&mv.reverse()) < 0.0);
+            mvs.push(if flip { &mv * -1.0 } else { mv });
+        }
+
+        // s_i = q_i exp(-(log(q_i⁻¹ q_{i-1}) + log(q_i⁻¹ q_{i+1})) / 4), and
+        // s_i = q_i at the ends, so that two keys give a plain slerp
+        let last = mvs.len() - 1;
+        let controls = (0..mvs.len())
+            .map(|i| {
+                let current = &mvs[i];
+                if i == 0 || i == last {
+                    return current.clone();
+                }
+                let inverse = current.reverse();
+                let tangent = &rotor_log(&inverse.geometric_product(&mvs[i - 1]))
+                    + &rotor_log(&inverse.geometric_product(&mvs[i + 1]));
+                current.geometric_product(&rotor_exp(&(&tangent * -0.25)))
+            })
+            .collect();
+
+        Self {
+            keys: mvs,
+            controls,
+        }
+    }
+}
+
+impl Spline for RotorSpline {
+    type Value = Rotor;
+
+    fn sample(&self, u: f64) -> Rotor {
+        if self.keys.len() == 1 {
+            return Rotor::from_multivector(self.keys[0].clone());
+        }
+
+        let (i, h) = segment(u, self.keys.len() - 1);
+        let outer = slerp(&self.keys[i], &self.keys[i + 1], h);
+        let inner = slerp(&self.controls[i], &self.controls[i + 1], h);
+        Rotor::from_multivector(slerp(&outer, &inner, 2.0 * h * (1.0 - h)))
+    }
+
+    /// The angle of the rotation between `a` and `b`
+    fn distance(a: &Rotor, b: &Rotor) -> f64 {
+        let relative = a.inverse().then(b);
+        let cos = relative.as_multivector().get(0).abs().min(1.0);
+        2.0 * cos.acos()
+    }
+}
+
+// ============================================================================
+// Translations - Catmull-Rom
+// ============================================================================
+
+/// A smooth path through translation keys (uniform Catmull-Rom)
+///
+/// The curve passes through every key with a continuous tangent. The first
+/// and last segments get their outer tangents from phantom keys reflected
+/// through the end keys, so evenly spaced keys on a line give a straight,
+/// constant-speed path.
+#[derive(Clone, Debug)]
+pub struct TranslationSpline {
+    keys: Vec<[f64; 3]>,
+}
+
+impl TranslationSpline {
+    /// Create a spline through `keys`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` is empty.
+    pub fn new(keys: Vec<Translation>) -> Self {
+        assert!(!keys.is_empty(), "a spline needs at least one key");
+        Self {
+            keys: keys.iter().map(|t| [t.x, t.y, t.z]).collect(),
+        }
+    }
+}
+
+impl Spline for TranslationSpline {
+    type Value = Translation;
+
+    fn sample(&self, u: f64) -> Translation {
+        let last = self.keys.len() - 1;
+        if last == 0 {
+            let [x, y, z] = self.keys[0];
+            return Translation::new(x, y, z);
+        }
+
+        let (i, t) = segment(u, last);
+        let p1 = self.keys[i];
+        let p2 = self.keys[i + 1];
+        let reflect =
+            |a: [f64; 3], b: [f64; 3]| [2.0 * a[0] - b[0], 2.0 * a[1] - b[1], 2.0 * a[2] - b[2]];
+        let p0 = if i == 0 {
+            reflect(p1, p2)
+        } else {
+            self.keys[i - 1]
+        };
+        let p3 = if i + 1 == last {
+            reflect(p2, p1)
+        } else {
+            self.keys[i + 2]
+        };
+
+        let (t2, t3) = (t * t, t * t * t);
+        let c = |k: usize| {
+            0.5 * (2.0 * p1[k]
+                + (p2[k] - p0[k]) * t
+                + (2.0 * p0[k] - 5.0 * p1[k] + 4.0 * p2[k] - p3[k]) * t2
+                + (3.0 * p1[k] - p0[k] - 3.0 * p2[k] + p3[k]) * t3)
+        };
+        Translation::new(c(0), c(1), c(2))
+    }
+
+    /// Euclidean distance between the translations
+    fn distance(a: &Translation, b: &Translation) -> f64 {
+        ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
+    }
+}
+
+// ============================================================================
+// Transforms - Bezier
+// ============================================================================
+
+/// A Bezier curve of rigid motions
+///
+/// The curve starts at the first control transform and ends at the last;
+/// the ones in between pull it towards them. It is evaluated with de
+/// Casteljau's algorithm, using screw interpolation in place of linear
+/// interpolation, so the blend between any two transforms is a rigid
+/// motion.
+#[derive(Clone, Debug)]
+pub struct TransformBezier {
+    controls: Vec<Transform>,
+}
+
+impl TransformBezier {
+    /// Create a curve with the given control transforms
+    ///
+    /// Four controls give a cubic curve.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `controls` is empty.
+    pub fn new(controls: Vec<Transform>) -> Self {
+        assert!(!controls.is_empty(), "a Bezier curve needs a control point");
+        Self { controls }
+    }
+}
+
+impl Spline for TransformBezier {
+    type Value = Transform;
+
+    fn sample(&self, u: f64) -> Transform {
+        let u = u.clamp(0.0, 1.0);
+        let mut points = self.controls.clone();
+        while points.len() > 1 {
+            points = points
+                .windows(2)
+                .map(|pair| pair[0].sclerp_to(&pair[1], u))
+                .collect();
+        }
+        points.remove(0)
+    }
+
+    /// Distance between the translations plus the rotation angle between
+    /// the rotors (as if measured on a unit radius)
+    fn distance(a: &Transform, b: &Transform) -> f64 {
+        TranslationSpline::distance(&a.translation, &b.translation)
+            + RotorSpline::distance(&a.rotor, &b.rotor)
+    }
+}
+
+// ============================================================================
+// Arc-length parameterisation
+// ============================================================================
+
+/// A spline re-parameterised by distance travelled
+///
+/// The spline is sampled at `samples + 1` evenly spaced parameters and
+/// the distances between neighbouring samples are accumulated. Lookups
+/// interpolate linearly in that table, so more samples follow the curve
+/// more closely.
+#[derive(Clone, Debug)]
+pub struct ArcLength<S> {
+    spline: S,
+    /// Cumulative distance at each sampled parameter
+    lengths: Vec<f64>,
+}
+
+impl<S: Spline> ArcLength<S> {
+    /// Measure `spline` with `samples` segments (at least one)
+    pub fn new(spline: S, samples: usize) -> Self {
+        let samples = samples.max(1);
+        let mut lengths = Vec::with_capacity(samples + 1);
+        let mut total = 0.0;
+        let mut previous = spline.sample(0.0);
+        lengths.push(0.0);
+        for i in 1..=samples {
+            let current = spline.sample(i as f64 / samples as f64);
+            total += S::distance(&previous, &current);
+            lengths.push(total);
+            previous = current;
+        }
+        Self { spline, lengths }
+    }
+
+    /// Total length of the curve
+    pub fn length(&self) -> f64 {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    /// The spline parameter at which `distance` has been travelled
+    pub fn parameter_at(&self, distance: f64) -> f64 {
+        let samples = self.lengths.len() - 1;
+        let total = self.length();
+        if total <= 0.0 {
+            return distance.clamp(0.0, 1.0);
+        }
+
+        let distance = distance.clamp(0.0, total);
+        let i = self
+            .lengths
+            .partition_point(|&l| l < distance)
+            .clamp(1, samples);
+        let (start, end) = (self.lengths[i - 1], self.lengths[i]);
+        let within = if end > start {
+            (distance - start) / (end - start)
+        } else {
+            0.0
+        };
+        (i as f64 - 1.0 + within) / samples as f64
+    }
+
+    /// The value after travelling `distance` along the curve
+    pub fn at_distance(&self, distance: f64) -> S::Value {
+        self.spline.sample(self.parameter_at(distance))
+    }
+
+    /// The value after travelling `fraction` (in `[0, 1]`) of the length
+    pub fn at_fraction(&self, fraction: f64) -> S::Value {
+        self.at_distance(fraction.clamp(0.0, 1.0) * self.length())
+    }
+
+    /// Get the underlying spline
+    pub fn spline(&self) -> &S {
+        &self.spline
+    }
+}
+
+impl<S> ArcLength<S>
+where
+    S: Spline + 'static,
+    S::Value: IntoGeometric + FromGeometric + 'static,
+{
+    /// Travel along the curve at constant speed over `duration`
+    ///
+    /// Like `Behavior::animate`, progress advances by `dt` on every tick
+    /// and is shaped by `easing`; eased progress is the fraction of the
+    /// length travelled. The behavior holds the end of the curve once the
+    /// animation ends.
+    pub fn animate(
+        self,
+        easing: Easing,
+        duration: Duration,
+        dt: &Event<f64>,
+    ) -> Behavior<S::Value> {
+        let animated = Behavior::new(self.at_fraction(0.0));
+
+        let elapsed = Cell::new(0.0);
+        let finished = Cell::new(false);
+        let duration = duration.as_secs_f64();
+
+        let animated_clone = animated.clone();
+        dt.subscribe(move |dt| {
+            if finished.get() {
+                return;
+            }
+            elapsed.set(elapsed.get() + dt.max(0.0));
+
+            let progress = if duration > 0.0 {
+                (elapsed.get() / duration).min(1.0)
+            } else {
+                1.0
+            };
+            finished.set(progress >= 1.0);
+            animated_clone.set(self.at_fraction(easing.apply(progress)));
+        });
+
+        animated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::event;
+    use amari_core::Vector;
+    use std::f64::consts::PI;
+
+    fn rotate(rotor: &Rotor, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        let v = rotor.transform(&GA3::from_vector(&Vector::from_components(x, y, z)));
+        (v.get(1), v.get(2), v.get(4))
+    }
+
+    fn assert_same_rotation(a: &Rotor, b: &Rotor) {
+        for (x, y, z) in [(1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)] {
+            let (pa, pb) = (rotate(a, x, y, z), rotate(b, x, y, z));
+            assert!(
+                (pa.0 - pb.0).abs() < 1e-9
+                    && (pa.1 - pb.1).abs() < 1e-9
+                    && (pa.2 - pb.2).abs() < 1e-9,
+                "{:?} != {:?}",
+                pa,
+                pb
+            );
+        }
+    }
+
+    #[test]
+    fn test_rotor_spline_passes_through_keys() {
+        let keys = vec![
+            Rotor::identity(),
+            Rotor::xy(PI / 2.0),
+            Rotor::from_axis_angle(1.0, 1.0, 0.0, 1.0),
+            Rotor::yz(-0.5),
+        ];
+        let spline = RotorSpline::new(keys.clone());
+        for (i, key) in keys.iter().enumerate() {
+            assert_same_rotation(&spline.sample(i as f64 / 3.0), key);
+        }
+    }
+
+    #[test]
+    fn test_rotor_spline_two_keys_is_slerp() {
+        let (a, b) = (Rotor::xz(0.3), Rotor::xy(1.2));
+        let spline = RotorSpline::new(vec![a.clone(), b.clone()]);
+        for t in [0.25, 0.5, 0.75] {
+            assert_same_rotation(&spline.sample(t), &a.slerp_to(&b, t));
+        }
+    }
+
+    #[test]
+    fn test_rotor_spline_takes_short_arc() {
+        // -R is the same rotation as R; the spline must not spin the long way
+        let b = Rotor::from_multivector(Rotor::xy(0.2).as_multivector() * -1.0);
+        let spline = RotorSpline::new(vec![Rotor::identity(), b]);
+        assert!((spline.sample(0.5).angle() - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_catmull_rom_passes_through_keys() {
+        let keys = vec![
+            Translation::new(0.0, 0.0, 0.0),
+            Translation::new(1.0, 2.0, 0.0),
+            Translation::new(3.0, 2.0, 1.0),
+            Translation::new(4.0, 0.0, 1.0),
+        ];
+        let spline = TranslationSpline::new(keys.clone());
+        for (i, key) in keys.iter().enumerate() {
+            let d = TranslationSpline::distance(&spline.sample(i as f64 / 3.0), key);
+            assert!(d < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_catmull_rom_collinear_keys_stay_on_line() {
+        let spline = TranslationSpline::new(vec![
+            Translation::x(0.0),
+            Translation::x(1.0),
+            Translation::x(2.0),
+        ]);
+        let (x, y, z) = spline.sample(0.25).components();
+        assert!((x - 0.5).abs() < 1e-12 && y == 0.0 && z == 0.0);
+    }
+
+    #[test]
+    fn test_bezier_endpoints_and_linear_case() {
+        let start = Transform::translation(Translation::x(0.0));
+        let end = Transform::new(Rotor::xy(PI / 2.0), Translation::new(2.0, 0.0, 0.0));
+        let curve = TransformBezier::new(vec![start.clone(), end.clone()]);
+
+        assert!(TransformBezier::distance(&curve.sample(0.0), &start) < 1e-9);
+        assert!(TransformBezier::distance(&curve.sample(1.0), &end) < 1e-9);
+
+        // With two controls the curve is the screw interpolation itself
+        let mid = curve.sample(0.5);
+        assert!(TransformBezier::distance(&mid, &start.sclerp_to(&end, 0.5)) < 1e-9);
+    }
+
+    #[test]
+    fn test_bezier_pulled_towards_controls() {
+        let curve = TransformBezier::new(vec![
+            Transform::translation(Translation::new(0.0, 0.0, 0.0)),
+            Transform::translation(Translation::new(0.0, 4.0, 0.0)),
+            Transform::translation(Translation::new(4.0, 4.0, 0.0)),
+            Transform::translation(Translation::new(4.0, 0.0, 0.0)),
+        ]);
+        let (x, y, _) = curve.sample(0.5).translation.components();
+        assert!((x - 2.0).abs() < 1e-9 && (y - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_arc_length_is_uniform() {
+        // x = 10u²: equal parameter steps cover unequal distances
+        let spline = TransformBezier::new(vec![
+            Transform::translation(Translation::x(0.0)),
+            Transform::translation(Translation::x(0.0)),
+            Transform::translation(Translation::x(10.0)),
+        ]);
+        assert!((spline.sample(0.5).translation.x - 2.5).abs() < 1e-9);
+
+        let arc = ArcLength::new(spline, 1024);
+        assert!((arc.length() - 10.0).abs() < 1e-3);
+
+        for fraction in [0.1, 0.5, 0.9] {
+            let (x, _, _) = arc.at_fraction(fraction).translation.components();
+            assert!((x - 10.0 * fraction).abs() < 1e-2, "{} at {}", x, fraction);
+        }
+    }
+
+    #[test]
+    fn test_arc_length_animation_drives_behavior() {
+        let curve = TransformBezier::new(vec![
+            Transform::identity(),
+            Transform::new(Rotor::xy(PI / 2.0), Translation::new(0.0, 3.0, 0.0)),
+        ]);
+        let ticks = event::<f64>();
+        let animated =
+            ArcLength::new(curve, 64).animate(Easing::Linear, Duration::from_secs(1), &ticks);
+
+        let notifications = std::rc::Rc::new(Cell::new(0));
+        let counter = notifications.clone();
+        let _sub = animated.subscribe(move |_| counter.set(counter.get() + 1));
+
+        ticks.emit(0.5);
+        assert!(animated.sample().translation.y > 0.0);
+
+        ticks.emit(1.0);
+        let end = animated.sample();
+        assert!((end.translation.y - 3.0).abs() < 1e-9);
+        assert!((end.rotor.angle() - PI / 2.0).abs() < 1e-9);
+
+        // Finished: further ticks don't notify
+        ticks.emit(1.0);
+        assert_eq!(notifications.get(), 2);
+    }
+}
@@ -0,0 +1,611 @@
+//! # Cliffy Derive
+//!
+//! `#[derive(Geometric)]` implements `IntoGeometric` and `FromGeometric` for
+//! user structs and enums by laying their fields out on the components of a
+//! GA3 multivector. Use it through `cliffy-core` with the `derive` feature.
+//!
+//! ## Field placement
+//!
+//! Every field is converted with its own `IntoGeometric` impl, then the
+//! relevant part of its multivector is copied onto the combined one:
+//!
+//! - `#[geometric(scalar)]`, `#[geometric(e1)]` ... `#[geometric(e123)]`:
+//!   the field's scalar part goes on that basis blade
+//! - `#[geometric(vector)]`: the field's grade-1 part (`e1`, `e2`, `e3`)
+//! - `#[geometric(bivector)]`: the field's grade-2 part (`e12`, `e13`, `e23`)
+//! - `#[geometric(skip)]`: not encoded; decoded with `Default::default()`
+//!
+//! Fields without an attribute take the next free blade in the order
+//! scalar, `e1`, `e2`, `e3`, `e12`, `e13`, `e23`, `e123`. Overlapping
+//! placements and fields that don't fit are compile errors.
+//!
+//! Enums store the variant index in the scalar component; the fields of
+//! each variant are placed on the remaining blades.
+//!
+//! ## Round-trip test
+//!
+//! `#[geometric(test)]` on the type generates a unit test that decodes a
+//! probe multivector, re-encodes it and checks that nothing was lost. The
+//! type must implement `Clone`, `PartialEq` and `Debug`. A generic type
+//! names the instantiation to test, as in `#[geometric(test = "Pair<f64>")]`;
+//! a bare `test` on it is an error:
+//!
+//! ```compile_fail
+//! use cliffy_core::Geometric;
+//!
+//! #[derive(Geometric, Clone, Debug, PartialEq)]
+//! #[geometric(test)]
+//! struct Pair<T> {
+//!     first: T,
+//!     second: T,
+//! }
+//! ```
+//!
+//! ## Example
+//!
+//! ```rust
+//! use cliffy_core::{FromGeometric, Geometric, IntoGeometric, GA3};
+//!
+//! #[derive(Geometric, Clone, Debug, PartialEq)]
+//! struct Particle {
+//!     #[geometric(vector)]
+//!     position: GA3,
+//!     mass: f64,
+//!     #[geometric(e123)]
+//!     charge: i32,
+//! }
+//!
+//! let p = Particle {
+//!     position: GA3::from_coefficients(vec![0.0, 1.0, 2.0, 0.0, 3.0, 0.0, 0.0, 0.0]),
+//!     mass: 4.5,
+//!     charge: -1,
+//! };
+//! let mv = p.clone().into_geometric();
+//! assert_eq!(mv.get(0), 4.5);
+//! assert_eq!(Particle::from_geometric(&mv), p);
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, Ident, Index, Type};
+
+/// Number of components in a GA3 multivector
+const COMPONENTS: usize = 8;
+
+/// Order in which fields without an attribute are placed
+const AUTO_ORDER: [usize; COMPONENTS] = [0, 1, 2, 4, 3, 5, 6, 7];
+
+/// Components of the grade-1 (vector) part
+const VECTOR: [usize; 3] = [1, 2, 4];
+
+/// Components of the grade-2 (bivector) part
+const BIVECTOR: [usize; 3] = [3, 5, 6];
+
+/// Derive `IntoGeometric` and `FromGeometric`
+///
+/// See the [crate documentation](crate) for the layout rules.
+#[proc_macro_derive(Geometric, attributes(geometric))]
+pub fn derive_geometric(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Where a field lives in the combined multivector
+#[derive(Debug, Clone, PartialEq)]
+enum Placement {
+    /// The field's scalar part on one blade
+    Blade(usize),
+    /// The field's vector part
+    Vector,
+    /// The field's bivector part
+    Bivector,
+    /// Not encoded
+    Skip,
+    /// Next free blade
+    Auto,
+}
+
+impl Placement {
+    /// `(field component, multivector component)` pairs, once resolved
+    fn layout(&self) -> Vec<(usize, usize)> {
+        match self {
+            Placement::Blade(to) => vec![(0, *to)],
+            Placement::Vector => VECTOR.iter().map(|&i| (i, i)).collect(),
+            Placement::Bivector => BIVECTOR.iter().map(|&i| (i, i)).collect(),
+            Placement::Skip | Placement::Auto => Vec::new(),
+        }
+    }
+}
+
+/// A field with its resolved layout
+struct FieldLayout {
+    /// How to name the field in patterns (`name` or `field_0`)
+    binding: Ident,
+    /// How to access or construct the field (`name` or `0`)
+    member: syn::Member,
+    ty: Type,
+    skip: bool,
+    layout: Vec<(usize, usize)>,
+}
+
+/// Options set on the type itself
+#[derive(Default)]
+struct ContainerOptions {
+    /// The type to generate a round-trip test for
+    test: Option<Type>,
+}
+
+fn parse_blade(name: &str) -> Option<usize> {
+    match name {
+        "scalar" => Some(0),
+        "e1" => Some(1),
+        "e2" => Some(2),
+        "e12" => Some(3),
+        "e3" => Some(4),
+        "e13" => Some(5),
+        "e23" => Some(6),
+        "e123" => Some(7),
+        _ => None,
+    }
+}
+
+fn parse_placement(attrs: &[Attribute]) -> syn::Result<Placement> {
+    let mut placement = Placement::Auto;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("geometric")) {
+        attr.parse_nested_meta(|meta| {
+            let Some(ident) = meta.path.get_ident() else {
+                return Err(meta.error("expected a placement such as `vector` or `e1`"));
+            };
+            if placement != Placement::Auto {
+                return Err(meta.error("a field can only have one placement"));
+            }
+            let name = ident.to_string();
+            placement = match name.as_str() {
+                "vector" => Placement::Vector,
+                "bivector" => Placement::Bivector,
+                "skip" => Placement::Skip,
+                _ => match parse_blade(&name) {
+                    Some(blade) => Placement::Blade(blade),
+                    None => {
+                        return Err(meta.error(
+                            "unknown placement; expected scalar, vector, bivector, skip, \
+                             or a basis blade (e1, e2, e3, e12, e13, e23, e123)",
+                        ))
+                    }
+                },
+            };
+            Ok(())
+        })?;
+    }
+    Ok(placement)
+}
+
+fn parse_container(input: &DeriveInput) -> syn::Result<ContainerOptions> {
+    let mut options = ContainerOptions::default();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("geometric"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("test") {
+                return Err(meta.error("unknown option; expected `test`"));
+            }
+            let ty = if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::LitStr>()?.parse()?
+            } else if input.generics.params.is_empty() {
+                let name = &input.ident;
+                syn::parse_quote!(#name)
+            } else {
+                return Err(meta.error(
+                    "a generic type needs a concrete type to test, \
+                     such as `#[geometric(test = \"Pair<f64>\")]`",
+                ));
+            };
+            options.test = Some(ty);
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+/// Resolve the placement of every field, starting with `reserved` blades taken
+fn resolve(fields: &Fields, reserved: &[usize]) -> syn::Result<Vec<FieldLayout>> {
+    let mut used = [false; COMPONENTS];
+    for &i in reserved {
+        used[i] = true;
+    }
+
+    let mut placements = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let placement = parse_placement(&field.attrs)?;
+        let (binding, member) = match &field.ident {
+            Some(ident) => (ident.clone(), syn::Member::Named(ident.clone())),
+            None => (
+                format_ident!("field_{}", i),
+                syn::Member::Unnamed(Index::from(i)),
+            ),
+        };
+        placements.push((field, binding, member, placement));
+    }
+
+    // Explicit placements first, so automatic ones fill around them
+    for (field, _, _, placement) in &placements {
+        for (_, to) in placement.layout() {
+            if used[to] {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "this placement overlaps another field (or the enum variant index)",
+                ));
+            }
+            used[to] = true;
+        }
+    }
+
+    let mut layouts = Vec::new();
+    for (field, binding, member, placement) in placements {
+        let placement = if placement == Placement::Auto {
+            let Some(&free) = AUTO_ORDER.iter().find(|&&i| !used[i]) else {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "no free multivector component left for this field; \
+                     place it explicitly or mark it #[geometric(skip)]",
+                ));
+            };
+            used[free] = true;
+            Placement::Blade(free)
+        } else {
+            placement
+        };
+        layouts.push(FieldLayout {
+            binding,
+            member,
+            ty: field.ty.clone(),
+            skip: placement == Placement::Skip,
+            layout: placement.layout(),
+        });
+    }
+    Ok(layouts)
+}
+
+fn layout_tokens(layout: &[(usize, usize)]) -> TokenStream2 {
+    let pairs = layout.iter().map(|(from, to)| quote!((#from, #to)));
+    quote!(&[#(#pairs),*])
+}
+
+/// Statements placing each (bound) field into `coefficients`
+fn encode_fields(fields: &[FieldLayout]) -> TokenStream2 {
+    let statements = fields.iter().filter(|f| !f.skip).map(|f| {
+        let binding = &f.binding;
+        let layout = layout_tokens(&f.layout);
+        quote! {
+            ::cliffy_core::geometric::derive_support::place(
+                &mut coefficients,
+                &::cliffy_core::IntoGeometric::into_geometric(#binding),
+                #layout,
+            );
+        }
+    });
+    quote!(#(#statements)*)
+}
+
+/// Field initialisers decoding each field from `mv`
+fn decode_fields(fields: &[FieldLayout]) -> Vec<TokenStream2> {
+    fields
+        .iter()
+        .map(|f| {
+            let member = &f.member;
+            let ty = &f.ty;
+            if f.skip {
+                quote!(#member: ::core::default::Default::default())
+            } else {
+                let layout = layout_tokens(&f.layout);
+                quote! {
+                    #member: <#ty as ::cliffy_core::FromGeometric>::from_geometric(
+                        &::cliffy_core::geometric::derive_support::extract(mv, #layout),
+                    )
+                }
+            }
+        })
+        .collect()
+}
+
+/// Pattern binding every field (`{ a, b }`, `{ 0: field_0 }` or nothing)
+fn bind_fields(fields: &[FieldLayout]) -> TokenStream2 {
+    let bindings = fields.iter().map(|f| {
+        let member = &f.member;
+        let binding = &f.binding;
+        match (f.skip, member) {
+            (true, _) => quote!(#member: _),
+            (false, syn::Member::Named(_)) => quote!(#binding),
+            (false, syn::Member::Unnamed(_)) => quote!(#member: #binding),
+        }
+    });
+    quote!({ #(#bindings),* })
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let options = parse_container(input)?;
+
+    let (encode, decode, field_types) = match &input.data {
+        Data::Struct(data) => {
+            let fields = resolve(&data.fields, &[])?;
+            let pattern = bind_fields(&fields);
+            let place = encode_fields(&fields);
+            let inits = decode_fields(&fields);
+            let encode = quote! {
+                let #name #pattern = self;
+                #place
+            };
+            let decode = quote!(#name { #(#inits),* });
+            let types: Vec<Type> = fields
+                .iter()
+                .filter(|f| !f.skip)
+                .map(|f| f.ty.clone())
+                .collect();
+            (encode, decode, types)
+        }
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "cannot derive Geometric for an enum without variants",
+                ));
+            }
+            let mut arms = Vec::new();
+            let mut decoders = Vec::new();
+            let mut types = Vec::new();
+            for (index, variant) in data.variants.iter().enumerate() {
+                let ident = &variant.ident;
+                let fields = resolve(&variant.fields, &[0])?;
+                let pattern = bind_fields(&fields);
+                let place = encode_fields(&fields);
+                let inits = decode_fields(&fields);
+                let tag = index as f64;
+                arms.push(quote! {
+                    #name::#ident #pattern => {
+                        coefficients[0] = #tag;
+                        #place
+                    }
+                });
+                let index = index as i64;
+                decoders.push((index, quote!(#name::#ident { #(#inits),* })));
+                types.extend(fields.iter().filter(|f| !f.skip).map(|f| f.ty.clone()));
+            }
+            let encode = quote! {
+                match self {
+                    #(#arms)*
+                }
+            };
+            let first = decoders[0].1.clone();
+            let decode_arms = decoders.iter().map(|(i, d)| quote!(#i => #d,));
+            let decode = quote! {
+                match ::cliffy_core::geometric::derive_support::variant_index(mv) {
+                    #(#decode_arms)*
+                    _ => #first,
+                }
+            };
+            (encode, decode, types)
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "cannot derive Geometric for a union",
+            ))
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let generic = !input.generics.params.is_empty();
+    let into_bounds = field_types
+        .iter()
+        .filter(|_| generic)
+        .map(|ty| quote!(#ty: ::cliffy_core::IntoGeometric,));
+    let from_bounds = field_types
+        .iter()
+        .filter(|_| generic)
+        .map(|ty| quote!(#ty: ::cliffy_core::FromGeometric,));
+    let predicates = where_clause.map(|w| {
+        let predicates = &w.predicates;
+        quote!(#predicates,)
+    });
+
+    let test = options.test.map(|ty| round_trip_test(name, &ty));
+
+    Ok(quote! {
+        impl #impl_generics ::cliffy_core::IntoGeometric for #name #ty_generics
+        where
+            #predicates
+            #(#into_bounds)*
+        {
+            #[allow(unused_variables, unused_mut)]
+            fn into_geometric(self) -> ::cliffy_core::GA3 {
+                let mut coefficients = [0.0f64; 8];
+                #encode
+                ::cliffy_core::geometric::derive_support::finish(coefficients)
+            }
+        }
+
+        impl #impl_generics ::cliffy_core::FromGeometric for #name #ty_generics
+        where
+            #predicates
+            #(#from_bounds)*
+        {
+            #[allow(unused_variables)]
+            fn from_geometric(mv: &::cliffy_core::GA3) -> Self {
+                #decode
+            }
+        }
+
+        #test
+    })
+}
+
+/// A unit test that decoding, re-encoding and decoding again is stable
+/// for `ty`, an instantiation of the type `name`
+fn round_trip_test(name: &Ident, ty: &Type) -> TokenStream2 {
+    let module = format_ident!("__geometric_round_trip_{}", snake_case(&name.to_string()));
+    quote! {
+        #[cfg(test)]
+        #[allow(non_snake_case)]
+        mod #module {
+            use super::*;
+
+            #[test]
+            fn round_trip() {
+                let probe = ::cliffy_core::GA3::from_coefficients(
+                    (1..=8).map(f64::from).collect(),
+                );
+                let value = <#ty as ::cliffy_core::FromGeometric>::from_geometric(&probe);
+                let encoded = ::cliffy_core::IntoGeometric::into_geometric(value.clone());
+                let decoded = <#ty as ::cliffy_core::FromGeometric>::from_geometric(&encoded);
+                assert_eq!(decoded, value);
+
+                let reencoded = ::cliffy_core::IntoGeometric::into_geometric(decoded);
+                for i in 0..8 {
+                    assert_eq!(reencoded.get(i), encoded.get(i), "component {} changed", i);
+                }
+            }
+        }
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn fields_of(input: DeriveInput) -> Fields {
+        match input.data {
+            Data::Struct(data) => data.fields,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_auto_fields_fill_free_blades() {
+        let fields = fields_of(parse_quote! {
+            struct S {
+                a: f64,
+                #[geometric(vector)]
+                v: GA3,
+                b: f64,
+            }
+        });
+        let layouts = resolve(&fields, &[]).unwrap();
+        assert_eq!(layouts[0].layout, vec![(0, 0)]);
+        assert_eq!(layouts[1].layout, vec![(1, 1), (2, 2), (4, 4)]);
+        assert_eq!(layouts[2].layout, vec![(0, 3)]);
+    }
+
+    #[test]
+    fn test_enum_reserves_scalar() {
+        let fields = fields_of(parse_quote! {
+            struct V(f64, f64);
+        });
+        let layouts = resolve(&fields, &[0]).unwrap();
+        assert_eq!(layouts[0].layout, vec![(0, 1)]);
+        assert_eq!(layouts[1].layout, vec![(0, 2)]);
+    }
+
+    #[test]
+    fn test_overlap_is_an_error() {
+        let fields = fields_of(parse_quote! {
+            struct S {
+                #[geometric(vector)]
+                a: GA3,
+                #[geometric(e2)]
+                b: f64,
+            }
+        });
+        assert!(resolve(&fields, &[]).is_err());
+    }
+
+    #[test]
+    fn test_too_many_fields_is_an_error() {
+        let fields = fields_of(parse_quote! {
+            struct S(u8, u8, u8, u8, u8, u8, u8, u8, u8);
+        });
+        assert!(resolve(&fields, &[]).is_err());
+    }
+
+    #[test]
+    fn test_skip_takes_no_blade() {
+        let fields = fields_of(parse_quote! {
+            struct S {
+                #[geometric(skip)]
+                label: String,
+                value: f64,
+            }
+        });
+        let layouts = resolve(&fields, &[]).unwrap();
+        assert!(layouts[0].skip);
+        assert_eq!(layouts[1].layout, vec![(0, 0)]);
+    }
+
+    #[test]
+    fn test_unknown_placement_is_an_error() {
+        let fields = fields_of(parse_quote! {
+            struct S {
+                #[geometric(trivector)]
+                a: f64,
+            }
+        });
+        assert!(resolve(&fields, &[]).is_err());
+    }
+
+    #[test]
+    fn test_container_test_type() {
+        let test_type = |input: DeriveInput| {
+            parse_container(&input).map(|o| o.test.map(|ty| quote!(#ty).to_string()))
+        };
+        assert_eq!(
+            test_type(parse_quote! {
+                #[geometric(test)]
+                struct Rgb(f64, f64, f64);
+            })
+            .unwrap()
+            .as_deref(),
+            Some("Rgb")
+        );
+        assert_eq!(
+            test_type(parse_quote! {
+                #[geometric(test = "Pair<f64>")]
+                struct Pair<T>(T, T);
+            })
+            .unwrap()
+            .as_deref(),
+            Some("Pair < f64 >")
+        );
+        assert!(test_type(parse_quote! {
+            #[geometric(test)]
+            struct Pair<T>(T, T);
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("ColorHSL"), "color_h_s_l");
+        assert_eq!(snake_case("Particle"), "particle");
+    }
+}
@@ -0,0 +1,161 @@
+//! Round-trip tests for `#[derive(Geometric)]`
+
+use cliffy_core::{behavior, FromGeometric, Geometric, IntoGeometric, GA3};
+
+fn vector(x: f64, y: f64, z: f64) -> GA3 {
+    GA3::from_coefficients(vec![0.0, x, y, 0.0, z, 0.0, 0.0, 0.0])
+}
+
+#[derive(Geometric, Clone, Debug, PartialEq)]
+#[geometric(test)]
+struct Particle {
+    #[geometric(vector)]
+    position: GA3,
+    mass: f64,
+    #[geometric(e123)]
+    charge: i32,
+}
+
+#[derive(Geometric, Clone, Debug, PartialEq)]
+#[geometric(test)]
+struct Spin {
+    #[geometric(bivector)]
+    plane: GA3,
+    #[geometric(scalar)]
+    rate: f64,
+}
+
+#[derive(Geometric, Clone, Debug, PartialEq)]
+#[geometric(test)]
+struct Rgb(f64, f64, f64);
+
+#[derive(Geometric, Clone, Debug, PartialEq, Default)]
+struct Labelled {
+    #[geometric(skip)]
+    label: String,
+    value: i64,
+}
+
+#[derive(Geometric, Clone, Copy, Debug, PartialEq)]
+#[geometric(test)]
+enum Tool {
+    Pen,
+    Eraser,
+    Select,
+}
+
+#[derive(Geometric, Clone, Debug, PartialEq)]
+#[geometric(test)]
+enum Shape {
+    Circle {
+        radius: f64,
+    },
+    Rect(f64, f64),
+    Arrow {
+        #[geometric(vector)]
+        direction: GA3,
+        #[geometric(e123)]
+        filled: bool,
+    },
+}
+
+#[derive(Geometric, Clone, Debug, PartialEq)]
+#[geometric(test = "Pair<f64>")]
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+#[test]
+fn test_struct_layout() {
+    let particle = Particle {
+        position: vector(1.0, 2.0, 3.0),
+        mass: 4.0,
+        charge: -2,
+    };
+    let mv = particle.clone().into_geometric();
+
+    assert_eq!(mv.get(0), 4.0);
+    assert_eq!((mv.get(1), mv.get(2), mv.get(4)), (1.0, 2.0, 3.0));
+    assert_eq!(mv.get(7), -2.0);
+    assert_eq!(Particle::from_geometric(&mv), particle);
+}
+
+#[test]
+fn test_bivector_field() {
+    let spin = Spin {
+        plane: GA3::from_coefficients(vec![0.0, 0.0, 0.0, 0.5, 0.0, -0.5, 0.25, 0.0]),
+        rate: 2.0,
+    };
+    assert_eq!(Spin::from_geometric(&spin.clone().into_geometric()), spin);
+}
+
+#[test]
+fn test_tuple_struct() {
+    let color = Rgb(0.1, 0.5, 0.9);
+    let mv = color.clone().into_geometric();
+    assert_eq!((mv.get(0), mv.get(1), mv.get(2)), (0.1, 0.5, 0.9));
+    assert_eq!(Rgb::from_geometric(&mv), color);
+}
+
+#[test]
+fn test_skipped_field_defaults() {
+    let value = Labelled {
+        label: "ignored".to_string(),
+        value: 7,
+    };
+    let back = Labelled::from_geometric(&value.into_geometric());
+    assert_eq!(
+        back,
+        Labelled {
+            label: String::new(),
+            value: 7
+        }
+    );
+}
+
+#[test]
+fn test_unit_enum() {
+    for tool in [Tool::Pen, Tool::Eraser, Tool::Select] {
+        assert_eq!(Tool::from_geometric(&tool.into_geometric()), tool);
+    }
+    assert_eq!(Tool::Select.into_geometric().get(0), 2.0);
+    assert_eq!(Tool::from_geometric(&GA3::scalar(42.0)), Tool::Pen);
+}
+
+#[test]
+fn test_data_enum() {
+    let shapes = [
+        Shape::Circle { radius: 3.0 },
+        Shape::Rect(2.0, 5.0),
+        Shape::Arrow {
+            direction: vector(0.0, 1.0, 0.0),
+            filled: true,
+        },
+    ];
+    for shape in shapes {
+        assert_eq!(
+            Shape::from_geometric(&shape.clone().into_geometric()),
+            shape
+        );
+    }
+}
+
+#[test]
+fn test_generic_struct() {
+    let pair = Pair {
+        first: 1.5f64,
+        second: -2.5,
+    };
+    assert_eq!(Pair::from_geometric(&pair.clone().into_geometric()), pair);
+}
+
+#[test]
+fn test_derived_type_in_behavior() {
+    let tool = behavior(Tool::Pen);
+    let label = tool.map(|t| format!("{:?}", t));
+
+    tool.set(Tool::Eraser);
+    assert_eq!(label.sample(), "Eraser");
+    assert_eq!(tool.geometric_state().get(0), 1.0);
+}
@@ -13,27 +13,28 @@ struct Multivector {
 
 // Geometric product lookup table for basis elements
 // Cl(3,0): 1, e1, e2, e12, e3, e13, e23, e123
-var<private> gp_table: array<array<i32, 8>, 8> = array<array<i32, 8>, 8>(
-    array<i32, 8>( 0,  1,  2,  3,  4,  5,  6,  7), // 1 * ...
-    array<i32, 8>( 1,  0,  3, -2,  5, -4, -7,  6), // e1 * ...
-    array<i32, 8>( 2, -3,  0,  1,  6,  7, -4, -5), // e2 * ...
-    array<i32, 8>( 3,  2, -1,  0,  7, -6,  5, -4), // e12 * ...
-    array<i32, 8>( 4, -5, -6, -7,  0,  1,  2,  3), // e3 * ...
-    array<i32, 8>( 5,  4,  7, -6, -1,  0, -3,  2), // e13 * ...
-    array<i32, 8>( 6, -7,  4,  5, -2,  3,  0, -1), // e23 * ...
-    array<i32, 8>( 7,  6,  5,  4, -3, -2,  1,  0)  // e123 * ...
+// Blade indices are bitmasks of e1, e2, e3, so blade i * blade j lands on i ^ j
+var<private> gp_table: array<array<u32, 8>, 8> = array<array<u32, 8>, 8>(
+    array<u32, 8>(0u, 1u, 2u, 3u, 4u, 5u, 6u, 7u), // 1 * ...
+    array<u32, 8>(1u, 0u, 3u, 2u, 5u, 4u, 7u, 6u), // e1 * ...
+    array<u32, 8>(2u, 3u, 0u, 1u, 6u, 7u, 4u, 5u), // e2 * ...
+    array<u32, 8>(3u, 2u, 1u, 0u, 7u, 6u, 5u, 4u), // e12 * ...
+    array<u32, 8>(4u, 5u, 6u, 7u, 0u, 1u, 2u, 3u), // e3 * ...
+    array<u32, 8>(5u, 4u, 7u, 6u, 1u, 0u, 3u, 2u), // e13 * ...
+    array<u32, 8>(6u, 7u, 4u, 5u, 2u, 3u, 0u, 1u), // e23 * ...
+    array<u32, 8>(7u, 6u, 5u, 4u, 3u, 2u, 1u, 0u)  // e123 * ...
 );
 
 // Sign table for geometric product
 var<private> gp_signs: array<array<f32, 8>, 8> = array<array<f32, 8>, 8>(
     array<f32, 8>( 1.0,  1.0,  1.0,  1.0,  1.0,  1.0,  1.0,  1.0),
-    array<f32, 8>( 1.0,  1.0,  1.0, -1.0,  1.0, -1.0, -1.0,  1.0),
-    array<f32, 8>( 1.0, -1.0,  1.0,  1.0,  1.0,  1.0, -1.0, -1.0),
-    array<f32, 8>( 1.0,  1.0, -1.0,  1.0,  1.0, -1.0,  1.0, -1.0),
-    array<f32, 8>( 1.0, -1.0, -1.0, -1.0,  1.0,  1.0,  1.0,  1.0),
-    array<f32, 8>( 1.0,  1.0,  1.0, -1.0, -1.0,  1.0, -1.0,  1.0),
-    array<f32, 8>( 1.0, -1.0,  1.0,  1.0, -1.0,  1.0,  1.0, -1.0),
-    array<f32, 8>( 1.0,  1.0,  1.0,  1.0, -1.0, -1.0,  1.0,  1.0)
+    array<f32, 8>( 1.0,  1.0,  1.0,  1.0,  1.0,  1.0,  1.0,  1.0),
+    array<f32, 8>( 1.0, -1.0,  1.0, -1.0,  1.0, -1.0,  1.0, -1.0),
+    array<f32, 8>( 1.0, -1.0,  1.0, -1.0,  1.0, -1.0,  1.0, -1.0),
+    array<f32, 8>( 1.0, -1.0, -1.0,  1.0,  1.0, -1.0, -1.0,  1.0),
+    array<f32, 8>( 1.0, -1.0, -1.0,  1.0,  1.0, -1.0, -1.0,  1.0),
+    array<f32, 8>( 1.0,  1.0, -1.0, -1.0,  1.0,  1.0, -1.0, -1.0),
+    array<f32, 8>( 1.0,  1.0, -1.0, -1.0,  1.0,  1.0, -1.0, -1.0)
 );
 
 // Compute geometric product of two multivectors
@@ -42,14 +43,7 @@ fn geometric_product(a: Multivector, b: Multivector) -> Multivector {
     
     for (var i = 0u; i < 8u; i++) {
         for (var j = 0u; j < 8u; j++) {
-            let basis_result = gp_table[i][j];
-            let sign = gp_signs[i][j];
-            
-            if (basis_result >= 0) {
-                result.coeffs[basis_result] += a.coeffs[i] * b.coeffs[j] * sign;
-            } else {
-                result.coeffs[-basis_result] -= a.coeffs[i] * b.coeffs[j] * sign;
-            }
+            result.coeffs[gp_table[i][j]] += a.coeffs[i] * b.coeffs[j] * gp_signs[i][j];
         }
     }
     
@@ -0,0 +1,874 @@
+//! Batch evaluation of dataflow graphs.
+//!
+//! A whiteboard with thousands of strokes runs the same `DataflowGraph`
+//! over thousands of source states. `GraphKernel::compile` lowers a pure
+//! graph (sources, constants, transforms, combiners, conditionals and
+//! numeric projections) into a straight-line list of `KernelOp`s, which is:
+//!
+//! - printed as a WGSL compute kernel by `GraphKernel::wgsl`, built on the
+//!   functions in `shaders/geometric.wgsl` and run by
+//!   `GpuContext::batch_evaluate_graph`
+//! - interpreted on the CPU by `SimdBatch::evaluate_graph`, the reference
+//!   implementation
+//!
+//! Each output is the value reaching one `Sink` node, as a multivector.
+//! Projections use the same encoding as `DataflowValue::to_multivector`:
+//! `scalar` keeps the scalar part, and `bivector` moves the bivector
+//! components into the vector slots.
+//!
+//! ```rust
+//! use cliffy_core::dataflow::{GraphBuilder, RotationPlane, TransformType};
+//! use cliffy_gpu::{GpuMultivector, GraphKernel, SimdBatch};
+//!
+//! let graph = GraphBuilder::new()
+//!     .source("stroke")
+//!     .transform("turn", TransformType::Rotation { angle: 0.5, plane: RotationPlane::XY })
+//!     .project("xyz", "vector")
+//!     .sink("points", "d")
+//!     .build();
+//!
+//! let kernel = GraphKernel::compile(&graph).unwrap();
+//! let strokes = vec![GpuMultivector::vector(1.0, 0.0, 0.0); 1000];
+//! let outputs = SimdBatch::evaluate_graph(&kernel, &[strokes]).unwrap();
+//! assert_eq!(outputs[0].len(), 1000);
+//!
+//! // The same program for the GPU
+//! let shader = kernel.wgsl();
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use thiserror::Error;
+
+use crate::simd::{
+    addition_simd, dot_simd, geometric_product_simd, lerp_simd, max_simd, min_simd, sandwich_simd,
+    scalar_mul_simd, SimdBatch,
+};
+use crate::{GpuError, GpuMultivector};
+use cliffy_core::dataflow::{CombinerType, DataflowGraph, NodeId, NodeKind, TransformType};
+use cliffy_core::GA3;
+
+/// Name of the compute entry point in `GraphKernel::wgsl`.
+pub const GRAPH_KERNEL_ENTRY_POINT: &str = "dataflow_kernel";
+
+/// Errors that can occur while lowering a dataflow graph to a kernel.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum KernelError {
+    #[error("Dataflow graph contains a cycle")]
+    Cycle,
+
+    #[error("Dataflow graph has no source nodes")]
+    NoSources,
+
+    #[error("Dataflow graph has no sink nodes")]
+    NoSinks,
+
+    #[error("Node {node} cannot run in a kernel: {reason}")]
+    Unsupported { node: NodeId, reason: String },
+
+    #[error("Node {node} expects {expected} input(s), got {actual}")]
+    Arity {
+        node: NodeId,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// Index of the value written by an earlier op of a `GraphKernel`.
+pub type Register = usize;
+
+/// One step of a compiled graph. Op `n` writes register `n`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum KernelOp {
+    /// The state bound to source `n`
+    Source(usize),
+    /// A constant multivector
+    Constant([f32; 8]),
+    /// `rotor * input * ~rotor`
+    Sandwich { input: Register, rotor: [f32; 8] },
+    /// Add `offset` to the e1, e2, e3 components
+    Translate { input: Register, offset: [f32; 3] },
+    /// Multiply by a scalar
+    Scale { input: Register, factor: f32 },
+    /// `from * (1 - t) + to * t`
+    Lerp {
+        from: Register,
+        to: Register,
+        t: f32,
+    },
+    /// Sum of two values
+    Add(Register, Register),
+    /// Geometric product of two values
+    Product(Register, Register),
+    /// Componentwise minimum
+    Min(Register, Register),
+    /// Componentwise maximum
+    Max(Register, Register),
+    /// `then` if the condition's scalar part is above 0.5, else `otherwise`
+    Select {
+        condition: Register,
+        then: Register,
+        otherwise: Register,
+    },
+    /// Coefficient `i` of the result is `input[components[i]]`, or zero
+    Components {
+        input: Register,
+        components: [Option<usize>; 8],
+    },
+    /// The magnitude, as a scalar
+    Magnitude(Register),
+    /// 1 if the scalar part is above 0.5, else 0
+    Bool(Register),
+    /// The scalar part truncated towards zero
+    Truncate(Register),
+}
+
+impl KernelOp {
+    /// Evaluate this op for batch item `item`.
+    fn evaluate(
+        &self,
+        registers: &[GpuMultivector],
+        sources: &[Vec<GpuMultivector>],
+        item: usize,
+    ) -> GpuMultivector {
+        let r = |register: &Register| &registers[*register];
+        match self {
+            KernelOp::Source(slot) => sources[*slot][item],
+            KernelOp::Constant(coeffs) => GpuMultivector { coeffs: *coeffs },
+            KernelOp::Sandwich { input, rotor } => {
+                sandwich_simd(r(input), &GpuMultivector { coeffs: *rotor })
+            }
+            KernelOp::Translate {
+                input,
+                offset: [x, y, z],
+            } => addition_simd(r(input), &GpuMultivector::vector(*x, *y, *z)),
+            KernelOp::Scale { input, factor } => scalar_mul_simd(r(input), *factor),
+            KernelOp::Lerp { from, to, t } => lerp_simd(r(from), r(to), *t),
+            KernelOp::Add(a, b) => addition_simd(r(a), r(b)),
+            KernelOp::Product(a, b) => geometric_product_simd(r(a), r(b)),
+            KernelOp::Min(a, b) => min_simd(r(a), r(b)),
+            KernelOp::Max(a, b) => max_simd(r(a), r(b)),
+            KernelOp::Select {
+                condition,
+                then,
+                otherwise,
+            } => {
+                if r(condition).coeffs[0] > 0.5 {
+                    *r(then)
+                } else {
+                    *r(otherwise)
+                }
+            }
+            KernelOp::Components { input, components } => GpuMultivector {
+                coeffs: components.map(|c| c.map_or(0.0, |c| r(input).coeffs[c])),
+            },
+            KernelOp::Magnitude(input) => {
+                GpuMultivector::scalar(dot_simd(r(input), r(input)).sqrt())
+            }
+            KernelOp::Bool(input) => {
+                GpuMultivector::scalar(if r(input).coeffs[0] > 0.5 { 1.0 } else { 0.0 })
+            }
+            KernelOp::Truncate(input) => GpuMultivector::scalar(r(input).coeffs[0].trunc()),
+        }
+    }
+
+    /// This op as a WGSL expression.
+    fn wgsl(&self) -> String {
+        let v = |register: &Register| format!("v{}", register);
+        match self {
+            KernelOp::Source(slot) => format!("input_a[inputs + {}u]", slot),
+            KernelOp::Constant(coeffs) => multivector_literal(coeffs),
+            KernelOp::Sandwich { input, rotor } => {
+                format!("sandwich({}, {})", multivector_literal(rotor), v(input))
+            }
+            KernelOp::Translate {
+                input,
+                offset: [x, y, z],
+            } => format!(
+                "add_multivectors({}, {})",
+                v(input),
+                multivector_literal(&GpuMultivector::vector(*x, *y, *z).coeffs)
+            ),
+            KernelOp::Scale { input, factor } => {
+                format!("scale_multivector({}, {})", v(input), float(*factor))
+            }
+            KernelOp::Lerp { from, to, t } => format!(
+                "add_multivectors(scale_multivector({}, {}), scale_multivector({}, {}))",
+                v(from),
+                float(1.0 - t),
+                v(to),
+                float(*t)
+            ),
+            KernelOp::Add(a, b) => format!("add_multivectors({}, {})", v(a), v(b)),
+            KernelOp::Product(a, b) => format!("geometric_product({}, {})", v(a), v(b)),
+            KernelOp::Min(a, b) => format!("min_multivectors({}, {})", v(a), v(b)),
+            KernelOp::Max(a, b) => format!("max_multivectors({}, {})", v(a), v(b)),
+            KernelOp::Select {
+                condition,
+                then,
+                otherwise,
+            } => format!(
+                "select_multivector({}, {}, {})",
+                v(condition),
+                v(then),
+                v(otherwise)
+            ),
+            KernelOp::Components { input, components } => {
+                let coeffs: Vec<String> = components
+                    .iter()
+                    .map(|c| match c {
+                        Some(c) => format!("{}.coeffs[{}]", v(input), c),
+                        None => "0.0".to_string(),
+                    })
+                    .collect();
+                format!("Multivector(array<f32, 8>({}))", coeffs.join(", "))
+            }
+            KernelOp::Magnitude(input) => format!(
+                "multivector_from_scalar(sqrt(magnitude_squared({})))",
+                v(input)
+            ),
+            KernelOp::Bool(input) => format!(
+                "multivector_from_scalar(select(0.0, 1.0, {}.coeffs[0] > 0.5))",
+                v(input)
+            ),
+            KernelOp::Truncate(input) => {
+                format!("multivector_from_scalar(trunc({}.coeffs[0]))", v(input))
+            }
+        }
+    }
+}
+
+/// A dataflow graph compiled for batch evaluation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphKernel {
+    sources: Vec<String>,
+    outputs: Vec<(String, Register)>,
+    ops: Vec<KernelOp>,
+}
+
+impl GraphKernel {
+    /// Compile a graph.
+    ///
+    /// Only nodes feeding a `Sink` are compiled. Custom transforms and
+    /// combiners, and projections without a multivector form (such as
+    /// `to_string` or `color`), are rejected. Running `optimize` first
+    /// fuses chains of transforms into a single sandwich.
+    pub fn compile(graph: &DataflowGraph) -> Result<Self, KernelError> {
+        let order = graph.topological_sort().ok_or(KernelError::Cycle)?;
+        let source_ids: Vec<NodeId> = graph
+            .nodes()
+            .filter(|(_, node)| node.is_source())
+            .map(|(id, _)| id)
+            .collect();
+        let sink_ids: Vec<NodeId> = graph
+            .nodes()
+            .filter(|(_, node)| node.is_sink())
+            .map(|(id, _)| id)
+            .collect();
+        if source_ids.is_empty() {
+            return Err(KernelError::NoSources);
+        }
+        if sink_ids.is_empty() {
+            return Err(KernelError::NoSinks);
+        }
+
+        let mut needed = HashSet::new();
+        let mut stack = sink_ids.clone();
+        while let Some(id) = stack.pop() {
+            if needed.insert(id) {
+                stack.extend_from_slice(graph.incoming(id));
+            }
+        }
+
+        let mut kernel = GraphKernel {
+            sources: source_ids
+                .iter()
+                .map(|&id| graph.get_node(id).unwrap().name.clone().unwrap_or_default())
+                .collect(),
+            outputs: Vec::new(),
+            ops: Vec::new(),
+        };
+        let mut registers = vec![0; graph.node_count()];
+        for id in order.into_iter().filter(|id| needed.contains(id)) {
+            let node = graph.get_node(id).unwrap();
+            let inputs: Vec<Register> = graph.incoming(id).iter().map(|&i| registers[i]).collect();
+            let arity = |expected: usize| {
+                if inputs.len() == expected {
+                    Ok(())
+                } else {
+                    Err(KernelError::Arity {
+                        node: id,
+                        expected,
+                        actual: inputs.len(),
+                    })
+                }
+            };
+            let unsupported = |reason: String| KernelError::Unsupported { node: id, reason };
+
+            registers[id] = match &node.kind {
+                NodeKind::Source => {
+                    let slot = source_ids.iter().position(|&s| s == id).unwrap();
+                    kernel.push(KernelOp::Source(slot))
+                }
+                NodeKind::Constant => {
+                    let value = node.constant_value.clone().unwrap_or_else(GA3::zero);
+                    kernel.push(KernelOp::Constant(GpuMultivector::from(&value).coeffs))
+                }
+                NodeKind::Sink(_) => {
+                    arity(1)?;
+                    inputs[0]
+                }
+                NodeKind::Projection(spec) => {
+                    arity(1)?;
+                    let op = projection(&spec.projection_type, inputs[0]).ok_or_else(|| {
+                        unsupported(format!(
+                            "projection '{}' has no multivector form",
+                            spec.projection_type
+                        ))
+                    })?;
+                    kernel.push(op)
+                }
+                NodeKind::Transform(TransformType::Lerp { t }) => {
+                    arity(2)?;
+                    kernel.push(KernelOp::Lerp {
+                        from: inputs[0],
+                        to: inputs[1],
+                        t: *t as f32,
+                    })
+                }
+                NodeKind::Transform(TransformType::Scale { factor }) => {
+                    arity(1)?;
+                    kernel.push(KernelOp::Scale {
+                        input: inputs[0],
+                        factor: *factor as f32,
+                    })
+                }
+                NodeKind::Transform(TransformType::Custom { name }) => {
+                    return Err(unsupported(format!("custom transform '{}'", name)));
+                }
+                NodeKind::Transform(rigid) => {
+                    arity(1)?;
+                    let transform = rigid.to_transform().unwrap();
+                    let (rotor, translation) = transform.parts();
+                    let rotor = GpuMultivector::from(rotor.as_multivector()).coeffs;
+                    let (x, y, z) = translation.components();
+                    let mut register = inputs[0];
+                    if rotor != GpuMultivector::scalar(1.0).coeffs {
+                        register = kernel.push(KernelOp::Sandwich {
+                            input: register,
+                            rotor,
+                        });
+                    }
+                    if (x, y, z) != (0.0, 0.0, 0.0) {
+                        register = kernel.push(KernelOp::Translate {
+                            input: register,
+                            offset: [x as f32, y as f32, z as f32],
+                        });
+                    }
+                    register
+                }
+                NodeKind::Combine(CombinerType::Custom(name)) => {
+                    return Err(unsupported(format!("custom combiner '{}'", name)));
+                }
+                NodeKind::Combine(combiner) => {
+                    if inputs.is_empty() {
+                        return Err(KernelError::Arity {
+                            node: id,
+                            expected: 1,
+                            actual: 0,
+                        });
+                    }
+                    let op = match combiner {
+                        CombinerType::Sum | CombinerType::Average => KernelOp::Add,
+                        CombinerType::Product => KernelOp::Product,
+                        CombinerType::Min => KernelOp::Min,
+                        CombinerType::Max => KernelOp::Max,
+                        CombinerType::Custom(_) => unreachable!(),
+                    };
+                    let mut register = inputs[0];
+                    for &input in &inputs[1..] {
+                        register = kernel.push(op(register, input));
+                    }
+                    if *combiner == CombinerType::Average && inputs.len() > 1 {
+                        register = kernel.push(KernelOp::Scale {
+                            input: register,
+                            factor: 1.0 / inputs.len() as f32,
+                        });
+                    }
+                    register
+                }
+                NodeKind::Conditional => {
+                    // Inputs are condition, then, else
+                    arity(3)?;
+                    kernel.push(KernelOp::Select {
+                        condition: inputs[0],
+                        then: inputs[1],
+                        otherwise: inputs[2],
+                    })
+                }
+            };
+        }
+        kernel.outputs = sink_ids
+            .iter()
+            .map(|&id| {
+                let name = graph.get_node(id).unwrap().name.clone();
+                (name.unwrap_or_default(), registers[id])
+            })
+            .collect();
+        Ok(kernel)
+    }
+
+    /// Names of the source nodes, in the order their batches are passed.
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    /// Names of the sink nodes, in the order their results are returned.
+    pub fn outputs(&self) -> Vec<&str> {
+        self.outputs.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// The compiled program.
+    pub fn ops(&self) -> &[KernelOp] {
+        &self.ops
+    }
+
+    /// The program as a WGSL module with entry point
+    /// `GRAPH_KERNEL_ENTRY_POINT`.
+    ///
+    /// The module extends `shaders/geometric.wgsl`. `input_a` holds the
+    /// sources item by item (item `i`'s source `k` at `i * sources + k`),
+    /// `output` receives the outputs in the same layout, and `input_b` is
+    /// unused.
+    pub fn wgsl(&self) -> String {
+        let mut source = String::from(include_str!("../shaders/geometric.wgsl"));
+        source.push_str(KERNEL_HELPERS);
+
+        let _ = writeln!(source, "\n// Compiled dataflow graph");
+        let _ = writeln!(
+            source,
+            "const GRAPH_SOURCES: u32 = {}u;",
+            self.sources.len()
+        );
+        let _ = writeln!(
+            source,
+            "const GRAPH_OUTPUTS: u32 = {}u;\n",
+            self.outputs.len()
+        );
+        let _ = writeln!(source, "@compute @workgroup_size(64, 1, 1)");
+        let _ = writeln!(
+            source,
+            "fn {}(@builtin(global_invocation_id) global_id: vec3<u32>) {{",
+            GRAPH_KERNEL_ENTRY_POINT
+        );
+        source.push_str(
+            "    let index = global_id.x;\n    \
+             if (index >= arrayLength(&input_a) / GRAPH_SOURCES) {\n        \
+             return;\n    \
+             }\n\n    \
+             let inputs = index * GRAPH_SOURCES;\n    \
+             let outputs = index * GRAPH_OUTPUTS;\n",
+        );
+        for (register, op) in self.ops.iter().enumerate() {
+            let _ = writeln!(source, "    let v{} = {};", register, op.wgsl());
+        }
+        for (slot, (_, register)) in self.outputs.iter().enumerate() {
+            let _ = writeln!(source, "    output[outputs + {}u] = v{};", slot, register);
+        }
+        source.push_str("}\n");
+        source
+    }
+
+    /// Check one batch per source, all the same length, and return it.
+    pub(crate) fn batch_size(
+        &self,
+        lengths: impl ExactSizeIterator<Item = usize>,
+    ) -> Result<usize, GpuError> {
+        if lengths.len() != self.sources.len() {
+            return Err(GpuError::BufferSizeMismatch {
+                expected: self.sources.len(),
+                actual: lengths.len(),
+            });
+        }
+        let mut count = None;
+        for length in lengths {
+            match count {
+                None => count = Some(length),
+                Some(expected) if expected != length => {
+                    return Err(GpuError::BufferSizeMismatch {
+                        expected,
+                        actual: length,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(count.unwrap_or(0))
+    }
+
+    fn push(&mut self, op: KernelOp) -> Register {
+        self.ops.push(op);
+        self.ops.len() - 1
+    }
+}
+
+/// The op for a projection, if it has a multivector form.
+fn projection(projection_type: &str, input: Register) -> Option<KernelOp> {
+    let components = |components| Some(KernelOp::Components { input, components });
+    match projection_type {
+        "scalar" => components([Some(0), None, None, None, None, None, None, None]),
+        "vector" | "position3d" | "point" => {
+            components([None, Some(1), Some(2), None, Some(4), None, None, None])
+        }
+        // Reported as (e12, e13, e23) in the vector slots
+        "bivector" => components([None, Some(3), Some(5), None, Some(6), None, None, None]),
+        "magnitude" => Some(KernelOp::Magnitude(input)),
+        "bool" => Some(KernelOp::Bool(input)),
+        "int" => Some(KernelOp::Truncate(input)),
+        _ => None,
+    }
+}
+
+/// A WGSL float literal.
+fn float(value: f32) -> String {
+    format!("{:?}", value)
+}
+
+fn multivector_literal(coeffs: &[f32; 8]) -> String {
+    let coeffs: Vec<String> = coeffs.iter().map(|&c| float(c)).collect();
+    format!("Multivector(array<f32, 8>({}))", coeffs.join(", "))
+}
+
+/// WGSL functions used by compiled graphs beyond those in geometric.wgsl.
+const KERNEL_HELPERS: &str = "
+// Helpers for compiled dataflow graphs
+fn multivector_from_scalar(s: f32) -> Multivector {
+    var result = Multivector();
+    result.coeffs[0] = s;
+    return result;
+}
+
+fn min_multivectors(a: Multivector, b: Multivector) -> Multivector {
+    var result = Multivector();
+    for (var i = 0u; i < 8u; i++) {
+        result.coeffs[i] = min(a.coeffs[i], b.coeffs[i]);
+    }
+    return result;
+}
+
+fn max_multivectors(a: Multivector, b: Multivector) -> Multivector {
+    var result = Multivector();
+    for (var i = 0u; i < 8u; i++) {
+        result.coeffs[i] = max(a.coeffs[i], b.coeffs[i]);
+    }
+    return result;
+}
+
+fn select_multivector(condition: Multivector, a: Multivector, b: Multivector) -> Multivector {
+    if (condition.coeffs[0] > 0.5) {
+        return a;
+    }
+    return b;
+}
+";
+
+impl SimdBatch {
+    /// Batch evaluation of a compiled dataflow graph.
+    ///
+    /// `sources` holds one batch per kernel source, all the same length;
+    /// the result holds one batch per output. This is the reference
+    /// implementation for `GraphKernel::wgsl`.
+    pub fn evaluate_graph(
+        kernel: &GraphKernel,
+        sources: &[Vec<GpuMultivector>],
+    ) -> Result<Vec<Vec<GpuMultivector>>, GpuError> {
+        let count = kernel.batch_size(sources.iter().map(Vec::len))?;
+        let mut outputs = vec![Vec::with_capacity(count); kernel.outputs.len()];
+        let mut registers = Vec::with_capacity(kernel.ops.len());
+        for item in 0..count {
+            registers.clear();
+            for op in &kernel.ops {
+                let value = op.evaluate(&registers, sources, item);
+                registers.push(value);
+            }
+            for (output, (_, register)) in outputs.iter_mut().zip(&kernel.outputs) {
+                output.push(registers[*register]);
+            }
+        }
+        Ok(outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cliffy_core::dataflow::{GraphBuilder, Node, RotationPlane};
+    use cliffy_core::{DataflowRuntime, GeometricState};
+
+    /// A graph using every kind of op
+    fn stroke_graph() -> DataflowGraph {
+        let mut graph = DataflowGraph::new();
+        let position = graph.add_node(Node::source("position"));
+        let pressure = graph.add_node(Node::source("pressure"));
+        let turn = graph.add_node(Node::transform(
+            "turn",
+            TransformType::Rotation {
+                angle: 0.7,
+                plane: RotationPlane::ZX,
+            },
+        ));
+        let shift = graph.add_node(Node::transform(
+            "shift",
+            TransformType::Translation {
+                x: 1.0,
+                y: -2.0,
+                z: 0.5,
+            },
+        ));
+        let width = graph.add_node(Node::transform(
+            "width",
+            TransformType::Scale { factor: 3.0 },
+        ));
+        let origin = graph.add_node(Node::constant("origin", GA3::scalar(0.25)));
+        let blend = graph.add_node(Node::transform("blend", TransformType::Lerp { t: 0.3 }));
+        let product = graph.add_node(Node::combine("product", CombinerType::Product));
+        let average = graph.add_node(Node::combine("average", CombinerType::Average));
+        let clamp = graph.add_node(Node::combine("clamp", CombinerType::Max));
+        let floor = graph.add_node(Node::combine("floor", CombinerType::Min));
+        let heavy = graph.add_node(Node::projection("heavy", "bool"));
+        let choose = graph.add_node(Node::conditional("choose"));
+        let xyz = graph.add_node(Node::projection("xyz", "vector"));
+        let planes = graph.add_node(Node::projection("planes", "bivector"));
+        let length = graph.add_node(Node::projection("length", "magnitude"));
+        let steps = graph.add_node(Node::projection("steps", "int"));
+        let edges = [
+            (position, turn),
+            (turn, shift),
+            (pressure, width),
+            (shift, blend),
+            (origin, blend),
+            (blend, product),
+            (width, product),
+            (product, average),
+            (shift, average),
+            (pressure, average),
+            (average, clamp),
+            (origin, clamp),
+            (clamp, floor),
+            (shift, floor),
+            (pressure, heavy),
+            (heavy, choose),
+            (floor, choose),
+            (blend, choose),
+            (choose, xyz),
+            (product, planes),
+            (average, length),
+            (width, steps),
+        ];
+        for (from, to) in edges {
+            graph.connect(from, to);
+        }
+        for (name, input) in [
+            ("out_xyz", xyz),
+            ("out_planes", planes),
+            ("out_length", length),
+            ("out_steps", steps),
+        ] {
+            let sink = graph.add_node(Node::sink(name, "value"));
+            graph.connect(input, sink);
+        }
+        graph
+    }
+
+    fn stroke_inputs(count: usize) -> (Vec<GA3>, Vec<GA3>) {
+        let positions = (0..count)
+            .map(|i| {
+                let i = i as f64;
+                GA3::from_coefficients(vec![
+                    0.0,
+                    i.sin(),
+                    (0.3 * i).cos(),
+                    0.0,
+                    0.1 * i,
+                    0.0,
+                    0.0,
+                    0.0,
+                ])
+            })
+            .collect();
+        let pressures = (0..count)
+            .map(|i| GA3::scalar(i as f64 / count as f64 * 2.0))
+            .collect();
+        (positions, pressures)
+    }
+
+    #[test]
+    fn test_simd_matches_runtime() {
+        let graph = stroke_graph();
+        let kernel = GraphKernel::compile(&graph).unwrap();
+        assert_eq!(kernel.sources(), ["position", "pressure"]);
+        assert_eq!(
+            kernel.outputs(),
+            ["out_xyz", "out_planes", "out_length", "out_steps"]
+        );
+
+        let (positions, pressures) = stroke_inputs(64);
+        let outputs = SimdBatch::evaluate_graph(
+            &kernel,
+            &[
+                SimdBatch::from_ga3(&positions),
+                SimdBatch::from_ga3(&pressures),
+            ],
+        )
+        .unwrap();
+
+        let runtime = DataflowRuntime::new(graph).unwrap();
+        let position = GeometricState::new(GA3::zero());
+        let pressure = GeometricState::new(GA3::zero());
+        runtime.bind("position", &position).unwrap();
+        runtime.bind("pressure", &pressure).unwrap();
+        for item in 0..positions.len() {
+            position.set(positions[item].clone());
+            pressure.set(pressures[item].clone());
+            for (output, name) in outputs.iter().zip(kernel.outputs()) {
+                let expected = GpuMultivector::from(&runtime.value(name).unwrap().to_multivector());
+                for (a, b) in output[item].coeffs.iter().zip(expected.coeffs) {
+                    assert!(
+                        (a - b).abs() < 1e-4,
+                        "{} at {}: {:?} != {:?}",
+                        name,
+                        item,
+                        output[item],
+                        expected
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_wgsl_follows_ops() {
+        let kernel = GraphKernel::compile(&stroke_graph()).unwrap();
+        let shader = kernel.wgsl();
+        assert!(shader.contains(&format!("fn {}(", GRAPH_KERNEL_ENTRY_POINT)));
+        assert!(shader.contains("const GRAPH_SOURCES: u32 = 2u;"));
+        for (register, op) in kernel.ops().iter().enumerate() {
+            assert!(shader.contains(&format!("    let v{} = {};\n", register, op.wgsl())));
+        }
+        assert_eq!(shader.matches("    output[outputs + ").count(), 4);
+
+        let module = wgpu::naga::front::wgsl::parse_str(&shader).unwrap();
+        wgpu::naga::valid::Validator::new(
+            wgpu::naga::valid::ValidationFlags::all(),
+            wgpu::naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .unwrap();
+    }
+
+    #[test]
+    fn test_rigid_transforms_lower_to_sandwich_and_translate() {
+        let graph = GraphBuilder::new()
+            .source("stroke")
+            .transform(
+                "turn",
+                TransformType::Rotation {
+                    angle: 0.5,
+                    plane: RotationPlane::XY,
+                },
+            )
+            .transform(
+                "shift",
+                TransformType::Translation {
+                    x: 1.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+            )
+            .sink("out", "value")
+            .build();
+
+        let kernel = GraphKernel::compile(&graph).unwrap();
+        assert_eq!(kernel.ops().len(), 3);
+        assert!(matches!(
+            kernel.ops()[1],
+            KernelOp::Sandwich { input: 0, .. }
+        ));
+        assert_eq!(
+            kernel.ops()[2],
+            KernelOp::Translate {
+                input: 1,
+                offset: [1.0, 0.0, 0.0]
+            }
+        );
+
+        // Fused by the optimiser into one node, still two ops
+        let (optimized, _) = graph.optimize();
+        assert_eq!(GraphKernel::compile(&optimized).unwrap().ops().len(), 3);
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let custom = GraphBuilder::new()
+            .source("stroke")
+            .transform(
+                "warp",
+                TransformType::Custom {
+                    name: "warp".into(),
+                },
+            )
+            .sink("out", "value")
+            .build();
+        assert!(matches!(
+            GraphKernel::compile(&custom),
+            Err(KernelError::Unsupported { node: 1, .. })
+        ));
+
+        let text = GraphBuilder::new()
+            .source("stroke")
+            .project("label", "to_string")
+            .sink("out", "textContent")
+            .build();
+        assert!(matches!(
+            GraphKernel::compile(&text),
+            Err(KernelError::Unsupported { node: 1, .. })
+        ));
+
+        let no_sinks = GraphBuilder::new()
+            .source("stroke")
+            .project("x", "scalar")
+            .build();
+        assert_eq!(GraphKernel::compile(&no_sinks), Err(KernelError::NoSinks));
+
+        // Unsupported nodes that feed no sink are skipped
+        let dead = GraphBuilder::new()
+            .source("stroke")
+            .sink("out", "value")
+            .from("stroke")
+            .project("label", "to_string")
+            .build();
+        assert!(GraphKernel::compile(&dead).is_ok());
+    }
+
+    #[test]
+    fn test_batch_size_mismatch() {
+        let kernel = GraphKernel::compile(&stroke_graph()).unwrap();
+        let one = vec![GpuMultivector::zero(); 3];
+        let two = vec![GpuMultivector::zero(); 2];
+        assert!(matches!(
+            SimdBatch::evaluate_graph(&kernel, std::slice::from_ref(&one)),
+            Err(GpuError::BufferSizeMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+        assert!(matches!(
+            SimdBatch::evaluate_graph(&kernel, &[one, two]),
+            Err(GpuError::BufferSizeMismatch {
+                expected: 3,
+                actual: 2
+            })
+        ));
+    }
+}
@@ -30,7 +30,9 @@
 //! - **SIMD Fallback**: Optimized CPU operations when GPU unavailable
 //! - **WASM Support**: Works in browsers with WebGPU
 //! - **Compute Shaders**: WGSL shaders for geometric product, sandwich, exp, slerp
+//! - **Dataflow Kernels**: Compile a `DataflowGraph` to WGSL and evaluate it over a batch
 
+pub mod dataflow;
 pub mod simd;
 
 #[cfg(feature = "wasm")]
@@ -43,6 +45,7 @@ use thiserror::Error;
 use wgpu::util::DeviceExt;
 
 use cliffy_core::GA3;
+pub use dataflow::{GraphKernel, KernelError, KernelOp, GRAPH_KERNEL_ENTRY_POINT};
 pub use simd::{addition_simd, geometric_product_simd, sandwich_simd, SimdBatch};
 
 #[cfg(feature = "wasm")]
@@ -156,6 +159,7 @@ pub struct GpuContext {
     exp_pipeline: wgpu::ComputePipeline,
     rotor_slerp_pipeline: wgpu::ComputePipeline,
     bind_group_layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
 }
 
 impl GpuContext {
@@ -296,6 +300,7 @@ impl GpuContext {
             exp_pipeline,
             rotor_slerp_pipeline,
             bind_group_layout,
+            pipeline_layout,
         })
     }
 
@@ -408,6 +413,57 @@ impl GpuContext {
         Ok(result.into_iter().map(Into::into).collect())
     }
 
+    /// Batch evaluation of a compiled dataflow graph.
+    ///
+    /// `sources` holds one batch per kernel source, all the same length;
+    /// the result holds one batch per output. The shader is compiled on
+    /// each call.
+    pub async fn batch_evaluate_graph(
+        &self,
+        kernel: &GraphKernel,
+        sources: &[Vec<GA3>],
+    ) -> Result<Vec<Vec<GA3>>, GpuError> {
+        let count = kernel.batch_size(sources.iter().map(Vec::len))?;
+        let output_count = kernel.outputs().len();
+        if count == 0 {
+            return Ok(vec![Vec::new(); output_count]);
+        }
+
+        // Sources item by item, as the kernel reads them
+        let inputs: Vec<GpuMultivector> = (0..count)
+            .flat_map(|item| sources.iter().map(move |batch| (&batch[item]).into()))
+            .collect();
+        // The kernel doesn't read input_b, but the binding can't be empty
+        let unused = [GpuMultivector::zero()];
+
+        let shader_module = self
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Dataflow Graph Shader"),
+                source: wgpu::ShaderSource::Wgsl(Cow::Owned(kernel.wgsl())),
+            });
+        let pipeline = self
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("Dataflow Graph Pipeline"),
+                layout: Some(&self.pipeline_layout),
+                module: &shader_module,
+                entry_point: Some(GRAPH_KERNEL_ENTRY_POINT),
+                compilation_options: Default::default(),
+                cache: None,
+            });
+
+        let result = self.run_kernel(&pipeline, &inputs, &unused, count * output_count, count)?;
+
+        Ok((0..output_count)
+            .map(|output| {
+                (0..count)
+                    .map(|item| result[item * output_count + output].into())
+                    .collect()
+            })
+            .collect())
+    }
+
     /// Run a binary compute kernel (two input buffers, one output).
     fn run_binary_kernel(
         &self,
@@ -415,8 +471,20 @@ impl GpuContext {
         a: &[GpuMultivector],
         b: &[GpuMultivector],
     ) -> Result<Vec<GpuMultivector>, GpuError> {
-        let count = a.len();
-        if count == 0 {
+        self.run_kernel(pipeline, a, b, a.len(), a.len())
+    }
+
+    /// Run a compute kernel with `invocations` threads writing `output_len`
+    /// multivectors.
+    fn run_kernel(
+        &self,
+        pipeline: &wgpu::ComputePipeline,
+        a: &[GpuMultivector],
+        b: &[GpuMultivector],
+        output_len: usize,
+        invocations: usize,
+    ) -> Result<Vec<GpuMultivector>, GpuError> {
+        if invocations == 0 || output_len == 0 {
             return Ok(Vec::new());
         }
 
@@ -438,7 +506,7 @@ impl GpuContext {
             });
 
         // Create output buffer
-        let output_size = std::mem::size_of_val(a) as u64;
+        let output_size = (output_len * std::mem::size_of::<GpuMultivector>()) as u64;
         let output_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Output Buffer"),
             size: output_size,
@@ -490,7 +558,7 @@ impl GpuContext {
             compute_pass.set_bind_group(0, &bind_group, &[]);
 
             // Dispatch workgroups (64 threads per group)
-            let workgroup_count = count.div_ceil(64) as u32;
+            let workgroup_count = invocations.div_ceil(64) as u32;
             compute_pass.dispatch_workgroups(workgroup_count, 1, 1);
         }
 
@@ -686,6 +754,33 @@ impl AutoDispatcher {
         let result = SimdBatch::rotor_slerp(&a_gpu, &b_gpu, t);
         Ok(SimdBatch::to_ga3(&result))
     }
+
+    /// Batch evaluation of a compiled dataflow graph with automatic dispatch.
+    ///
+    /// Uses GPU for large batches, SIMD-optimized CPU for small batches.
+    pub async fn evaluate_graph(
+        &self,
+        kernel: &GraphKernel,
+        sources: &[Vec<GA3>],
+    ) -> Result<Vec<Vec<GA3>>, GpuError> {
+        let count = kernel.batch_size(sources.iter().map(Vec::len))?;
+        if let Some(ref ctx) = self.gpu_ctx {
+            if count >= self.threshold {
+                return ctx.batch_evaluate_graph(kernel, sources).await;
+            }
+        }
+
+        // SIMD-optimized CPU fallback
+        let sources_gpu: Vec<Vec<GpuMultivector>> = sources
+            .iter()
+            .map(|batch| SimdBatch::from_ga3(batch))
+            .collect();
+        let result = SimdBatch::evaluate_graph(kernel, &sources_gpu)?;
+        Ok(result
+            .iter()
+            .map(|batch| SimdBatch::to_ga3(batch))
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -46,7 +46,7 @@ pub fn geometric_product_simd(a: &GpuMultivector, b: &GpuMultivector) -> GpuMult
            + a.coeffs[3] * b.coeffs[2]   // e12*e2 = e1
            - a.coeffs[4] * b.coeffs[5]   // e3*e13 = -e1
            + a.coeffs[5] * b.coeffs[4]   // e13*e3 = e1
-           + a.coeffs[6] * b.coeffs[7]   // e23*e123 = e1
+           - a.coeffs[6] * b.coeffs[7]   // e23*e123 = -e1
            - a.coeffs[7] * b.coeffs[6]; // e123*e23 = -e1
 
     // e2 component
@@ -55,7 +55,7 @@ pub fn geometric_product_simd(a: &GpuMultivector, b: &GpuMultivector) -> GpuMult
            + a.coeffs[2] * b.coeffs[0]   // e2*1
            - a.coeffs[3] * b.coeffs[1]   // e12*e1 = -e2
            - a.coeffs[4] * b.coeffs[6]   // e3*e23 = -e2
-           - a.coeffs[5] * b.coeffs[7]   // e13*e123 = -e2
+           + a.coeffs[5] * b.coeffs[7]   // e13*e123 = e2
            + a.coeffs[6] * b.coeffs[4]   // e23*e3 = e2
            + a.coeffs[7] * b.coeffs[5]; // e123*e13 = e2
 
@@ -65,15 +65,15 @@ pub fn geometric_product_simd(a: &GpuMultivector, b: &GpuMultivector) -> GpuMult
            - a.coeffs[2] * b.coeffs[1]   // e2*e1 = -e12
            + a.coeffs[3] * b.coeffs[0]   // e12*1
            + a.coeffs[4] * b.coeffs[7]   // e3*e123 = e12
-           + a.coeffs[5] * b.coeffs[6]   // e13*e23 = e12
-           - a.coeffs[6] * b.coeffs[5]   // e23*e13 = -e12
+           - a.coeffs[5] * b.coeffs[6]   // e13*e23 = -e12
+           + a.coeffs[6] * b.coeffs[5]   // e23*e13 = e12
            + a.coeffs[7] * b.coeffs[4]; // e123*e3 = e12
 
     // e3 component
     let c4 = a.coeffs[0] * b.coeffs[4]   // 1*e3
            + a.coeffs[1] * b.coeffs[5]   // e1*e13 = e3
            + a.coeffs[2] * b.coeffs[6]   // e2*e23 = e3
-           + a.coeffs[3] * b.coeffs[7]   // e12*e123 = e3
+           - a.coeffs[3] * b.coeffs[7]   // e12*e123 = -e3
            + a.coeffs[4] * b.coeffs[0]   // e3*1
            - a.coeffs[5] * b.coeffs[1]   // e13*e1 = -e3
            - a.coeffs[6] * b.coeffs[2]   // e23*e2 = -e3
@@ -83,21 +83,21 @@ pub fn geometric_product_simd(a: &GpuMultivector, b: &GpuMultivector) -> GpuMult
     let c5 = a.coeffs[0] * b.coeffs[5]   // 1*e13
            + a.coeffs[1] * b.coeffs[4]   // e1*e3 = e13
            - a.coeffs[2] * b.coeffs[7]   // e2*e123 = -e13
-           - a.coeffs[3] * b.coeffs[6]   // e12*e23 = -e13
+           + a.coeffs[3] * b.coeffs[6]   // e12*e23 = e13
            - a.coeffs[4] * b.coeffs[1]   // e3*e1 = -e13
            + a.coeffs[5] * b.coeffs[0]   // e13*1
-           + a.coeffs[6] * b.coeffs[3]   // e23*e12 = e13
-           + a.coeffs[7] * b.coeffs[2]; // e123*e2 = e13
+           - a.coeffs[6] * b.coeffs[3]   // e23*e12 = -e13
+           - a.coeffs[7] * b.coeffs[2]; // e123*e2 = -e13
 
     // e23 component
     let c6 = a.coeffs[0] * b.coeffs[6]   // 1*e23
            + a.coeffs[1] * b.coeffs[7]   // e1*e123 = e23
            + a.coeffs[2] * b.coeffs[4]   // e2*e3 = e23
-           + a.coeffs[3] * b.coeffs[5]   // e12*e13 = e23
+           - a.coeffs[3] * b.coeffs[5]   // e12*e13 = -e23
            - a.coeffs[4] * b.coeffs[2]   // e3*e2 = -e23
-           - a.coeffs[5] * b.coeffs[3]   // e13*e12 = -e23
+           + a.coeffs[5] * b.coeffs[3]   // e13*e12 = e23
            + a.coeffs[6] * b.coeffs[0]   // e23*1
-           - a.coeffs[7] * b.coeffs[1]; // e123*e1 = -e23
+           + a.coeffs[7] * b.coeffs[1]; // e123*e1 = e23
 
     // e123 component
     let c7 = a.coeffs[0] * b.coeffs[7]   // 1*e123
@@ -147,6 +147,26 @@ pub fn scalar_mul_simd(a: &GpuMultivector, scalar: f32) -> GpuMultivector {
     }
 }
 
+/// SIMD-optimized componentwise minimum.
+#[inline]
+pub fn min_simd(a: &GpuMultivector, b: &GpuMultivector) -> GpuMultivector {
+    let a_vec = f32x8::from(a.coeffs);
+    let b_vec = f32x8::from(b.coeffs);
+    GpuMultivector {
+        coeffs: a_vec.min(b_vec).to_array(),
+    }
+}
+
+/// SIMD-optimized componentwise maximum.
+#[inline]
+pub fn max_simd(a: &GpuMultivector, b: &GpuMultivector) -> GpuMultivector {
+    let a_vec = f32x8::from(a.coeffs);
+    let b_vec = f32x8::from(b.coeffs);
+    GpuMultivector {
+        coeffs: a_vec.max(b_vec).to_array(),
+    }
+}
+
 /// SIMD-optimized reverse (reversion) of a multivector.
 ///
 /// For Cl(3,0), the reverse negates bivector and pseudoscalar components:
@@ -454,6 +474,56 @@ mod tests {
         assert!((result.coeffs[3] - 1.0).abs() < 1e-6); // e12 component
     }
 
+    #[test]
+    fn test_geometric_product_matches_ga3() {
+        for i in 0..8 {
+            for j in 0..8 {
+                let mut a = GpuMultivector::zero();
+                a.coeffs[i] = 1.0;
+                let mut b = GpuMultivector::zero();
+                b.coeffs[j] = 1.0;
+                let expected = GA3::from(a).geometric_product(&GA3::from(b));
+                let result = geometric_product_simd(&a, &b);
+                assert_eq!(result.coeffs, GpuMultivector::from(&expected).coeffs);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shader_product_tables_match_simd() {
+        // The WGSL geometric product is table driven, so check its tables
+        // against the SIMD product to keep GPU and CPU results in step
+        let shader = include_str!("../shaders/geometric.wgsl");
+        let table = |name: &str| -> Vec<Vec<f32>> {
+            let start = shader.find(&format!("var<private> {}", name)).unwrap();
+            shader[start..]
+                .lines()
+                .skip(1)
+                .take(8)
+                .map(|line| {
+                    let row = &line[line.find("8>(").unwrap() + 3..line.find(')').unwrap()];
+                    row.split(',')
+                        .map(|c| c.trim().trim_end_matches('u').parse().unwrap())
+                        .collect()
+                })
+                .collect()
+        };
+        let blades = table("gp_table");
+        let signs = table("gp_signs");
+
+        for i in 0..8 {
+            for j in 0..8 {
+                let mut a = GpuMultivector::zero();
+                a.coeffs[i] = 1.0;
+                let mut b = GpuMultivector::zero();
+                b.coeffs[j] = 1.0;
+                let mut expected = GpuMultivector::zero();
+                expected.coeffs[blades[i][j] as usize] = signs[i][j];
+                assert_eq!(geometric_product_simd(&a, &b).coeffs, expected.coeffs);
+            }
+        }
+    }
+
     #[test]
     fn test_reverse_simd() {
         let mut a = GpuMultivector::zero();
@@ -1,11 +1,21 @@
 //! Serde serialization helpers for GA3 multivectors
 //!
 //! Since amari-core's Multivector doesn't implement Serialize/Deserialize,
-//! we provide manual serialization as coefficient arrays.
+//! we provide manual serialization as coefficient arrays. An array that
+//! doesn't hold exactly eight coefficients is a deserialization error.
 
 use cliffy_core::GA3;
+use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+/// Build a GA3 from deserialized coefficients, checking their count
+fn from_coefficients<E: Error>(coeffs: &[f64]) -> Result<GA3, E> {
+    if coeffs.len() != 8 {
+        return Err(E::invalid_length(coeffs.len(), &"8 GA3 coefficients"));
+    }
+    Ok(GA3::from_slice(coeffs))
+}
+
 /// Serialize a GA3 as a Vec<f64> of coefficients
 pub fn serialize<S>(mv: &GA3, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -20,7 +30,7 @@ where
     D: Deserializer<'de>,
 {
     let coeffs = Vec::<f64>::deserialize(deserializer)?;
-    Ok(GA3::from_slice(&coeffs))
+    from_coefficients(&coeffs)
 }
 
 /// Module for serializing Option<GA3>
@@ -42,6 +52,87 @@ pub mod option {
         D: Deserializer<'de>,
     {
         let opt = Option::<Vec<f64>>::deserialize(deserializer)?;
-        Ok(opt.map(|coeffs| GA3::from_slice(&coeffs)))
+        opt.map(|coeffs| from_coefficients(&coeffs)).transpose()
+    }
+}
+
+/// Module for serializing a GeometricBlob as a list of coefficient arrays
+///
+/// ```rust
+/// use cliffy_core::GeometricBlob;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Payload {
+///     #[serde(with = "cliffy_protocols::serde_ga3::blob")]
+///     value: GeometricBlob,
+/// }
+/// ```
+pub mod blob {
+    use super::*;
+    use cliffy_core::GeometricBlob;
+
+    pub fn serialize<S>(blob: &GeometricBlob, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let chunks: Vec<&[f64]> = blob.chunks().iter().map(|mv| mv.as_slice()).collect();
+        chunks.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<GeometricBlob, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let chunks = Vec::<Vec<f64>>::deserialize(deserializer)?;
+        let chunks = chunks
+            .iter()
+            .map(|coeffs| from_coefficients(coeffs))
+            .collect::<Result<_, _>>()?;
+        Ok(GeometricBlob::from_chunks(chunks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cliffy_core::{FromGeometricBlob, GeometricBlob, IntoGeometricBlob, GA3};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct Payload {
+        #[serde(with = "super")]
+        state: GA3,
+        #[serde(with = "super::blob")]
+        value: GeometricBlob,
+    }
+
+    fn roundtrip(value: GeometricBlob) -> GeometricBlob {
+        let payload = Payload {
+            state: GA3::scalar(1.0),
+            value,
+        };
+        let json = serde_json::to_string(&payload).unwrap();
+        serde_json::from_str::<Payload>(&json).unwrap().value
+    }
+
+    #[test]
+    fn test_blob_roundtrip() {
+        let text = "the quick brown fox jumps over the lazy dog, twice over".to_string();
+        let blob = roundtrip(text.clone().into_geometric_blob());
+        assert_eq!(String::from_geometric_blob(&blob), Some(text));
+
+        let values = vec![3i32, -1, 4, 1, 5, 9];
+        let blob = roundtrip(values.clone().into_geometric_blob());
+        assert_eq!(Vec::<i32>::from_geometric_blob(&blob), Some(values));
+    }
+
+    #[test]
+    fn test_wrong_coefficient_count_is_an_error() {
+        let short_chunk = r#"{"state":[1,0,0,0,0,0,0,0],"value":[[1,2,0,0,0,0,0,0],[1,2]]}"#;
+        let error = serde_json::from_str::<Payload>(short_chunk).err().unwrap();
+        assert!(error.to_string().contains("invalid length 2"), "{}", error);
+
+        let long_state = r#"{"state":[1,0,0,0,0,0,0,0,0],"value":[]}"#;
+        assert!(serde_json::from_str::<Payload>(long_state).is_err());
     }
 }